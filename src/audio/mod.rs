@@ -0,0 +1,10 @@
+//! Audio and speech metrics over paired waveforms.
+//!
+//! All types implement [`Metric`](crate::core::Metric) on batches of `(predictions, targets)`
+//! waveforms and average the per-waveform values.
+
+pub mod scale_invariant;
+pub mod snr;
+
+pub use scale_invariant::{ScaleInvariantSignalDistortionRatio, ScaleInvariantSignalNoiseRatio};
+pub use snr::SignalNoiseRatio;
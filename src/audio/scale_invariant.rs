@@ -0,0 +1,158 @@
+use crate::core::{Metric, MetricError};
+
+use super::snr::{centered, decibels, energy, verify_waveforms};
+
+/// Scale-invariant signal-to-distortion ratio (SI-SDR) in decibels, averaged over waveforms.
+///
+/// The target is rescaled by `alpha = <p, t> / |t|^2` to best match the prediction, and
+/// `10 log10(|alpha t|^2 / |alpha t - p|^2)` is reported, so the score ignores the prediction's
+/// gain. Inputs are batches of `(predictions, targets)` waveforms as for
+/// [`SignalNoiseRatio`](crate::SignalNoiseRatio); [`with_zero_mean`](Self::with_zero_mean)
+/// centers both first.
+///
+/// ```
+/// use rust_metrics::{Metric, ScaleInvariantSignalDistortionRatio};
+///
+/// let preds: [&[f64]; 1] = [&[2.5, 0.0, 2.0, 8.0]];
+/// let target: [&[f64]; 1] = [&[3.0, -0.5, 2.0, 7.0]];
+///
+/// let mut si_sdr = ScaleInvariantSignalDistortionRatio::default();
+/// si_sdr.update((&preds, &target)).unwrap();
+/// assert!((si_sdr.compute().unwrap() - 18.4030).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScaleInvariantSignalDistortionRatio {
+    zero_mean: bool,
+    sum: f64,
+    count: usize,
+}
+
+impl ScaleInvariantSignalDistortionRatio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subtract each waveform's mean before comparing them, which makes this SI-SNR.
+    pub fn with_zero_mean(mut self) -> Self {
+        self.zero_mean = true;
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[f64]])> for ScaleInvariantSignalDistortionRatio {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[f64]]),
+    ) -> Result<(), MetricError> {
+        verify_waveforms(predictions, targets)?;
+        for (prediction, target) in predictions.iter().zip(targets) {
+            self.sum += scale_invariant_ratio(prediction, target, self.zero_mean);
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.count = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.sum / self.count as f64)
+    }
+}
+
+/// Scale-invariant signal-to-noise ratio (SI-SNR) in decibels: SI-SDR of centered waveforms,
+/// averaged over waveforms.
+///
+/// Speech separation and enhancement papers report it next to SI-SDR; see
+/// [`ScaleInvariantSignalDistortionRatio`] for the computation.
+///
+/// ```
+/// use rust_metrics::{Metric, ScaleInvariantSignalNoiseRatio};
+///
+/// let preds: [&[f64]; 1] = [&[2.5, 0.0, 2.0, 8.0]];
+/// let target: [&[f64]; 1] = [&[3.0, -0.5, 2.0, 7.0]];
+///
+/// let mut si_snr = ScaleInvariantSignalNoiseRatio::default();
+/// si_snr.update((&preds, &target)).unwrap();
+/// assert!((si_snr.compute().unwrap() - 15.0918).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScaleInvariantSignalNoiseRatio {
+    si_sdr: ScaleInvariantSignalDistortionRatio,
+}
+
+impl Default for ScaleInvariantSignalNoiseRatio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScaleInvariantSignalNoiseRatio {
+    pub fn new() -> Self {
+        Self {
+            si_sdr: ScaleInvariantSignalDistortionRatio::new().with_zero_mean(),
+        }
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[f64]])> for ScaleInvariantSignalNoiseRatio {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[&[f64]], &[&[f64]])) -> Result<(), MetricError> {
+        self.si_sdr.update(input)
+    }
+
+    fn reset(&mut self) {
+        self.si_sdr.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.si_sdr.compute()
+    }
+}
+
+fn scale_invariant_ratio(prediction: &[f64], target: &[f64], zero_mean: bool) -> f64 {
+    let (prediction, target) = if zero_mean {
+        (centered(prediction), centered(target))
+    } else {
+        (prediction.to_vec(), target.to_vec())
+    };
+    let dot: f64 = prediction.iter().zip(&target).map(|(p, t)| p * t).sum();
+    let alpha = (dot + f64::EPSILON) / (energy(target.iter().copied()) + f64::EPSILON);
+    let scaled_target: Vec<f64> = target.iter().map(|t| alpha * t).collect();
+    let noise = scaled_target.iter().zip(&prediction).map(|(s, p)| s - p);
+    decibels(energy(scaled_target.iter().copied()), energy(noise))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScaleInvariantSignalDistortionRatio, ScaleInvariantSignalNoiseRatio};
+    use crate::core::Metric;
+
+    #[test]
+    fn gain_does_not_change_the_score() {
+        let target: [&[f64]; 1] = [&[3.0, -0.5, 2.0, 7.0]];
+        let preds: [&[f64]; 1] = [&[2.5, 0.0, 2.0, 8.0]];
+        let louder: [&[f64]; 1] = [&[5.0, 0.0, 4.0, 16.0]];
+
+        let mut quiet = ScaleInvariantSignalNoiseRatio::new();
+        quiet.update((&preds, &target)).unwrap();
+        let mut loud = ScaleInvariantSignalNoiseRatio::new();
+        loud.update((&louder, &target)).unwrap();
+        assert!((quiet.compute().unwrap() - loud.compute().unwrap()).abs() < 1e-9);
+
+        let mut si_sdr = ScaleInvariantSignalDistortionRatio::new().with_zero_mean();
+        si_sdr.update((&preds, &target)).unwrap();
+        assert_eq!(si_sdr.compute(), quiet.compute());
+
+        quiet.reset();
+        assert_eq!(quiet.compute(), None);
+    }
+}
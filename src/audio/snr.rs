@@ -0,0 +1,140 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::verify_range;
+
+/// Signal-to-noise ratio in decibels, `10 log10(|t|^2 / |t - p|^2)`, averaged over waveforms.
+///
+/// Each update takes a batch of `(predictions, targets)` waveforms, paired by position and of
+/// equal length within a pair. With [`with_zero_mean`](SignalNoiseRatio::with_zero_mean) both
+/// waveforms are centered first, which ignores DC offsets.
+///
+/// ```
+/// use rust_metrics::{Metric, SignalNoiseRatio};
+///
+/// let preds: [&[f64]; 1] = [&[2.5, 0.0, 2.0, 8.0]];
+/// let target: [&[f64]; 1] = [&[3.0, -0.5, 2.0, 7.0]];
+///
+/// let mut snr = SignalNoiseRatio::default();
+/// snr.update((&preds, &target)).unwrap();
+/// assert!((snr.compute().unwrap() - 16.1805).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SignalNoiseRatio {
+    zero_mean: bool,
+    sum: f64,
+    count: usize,
+}
+
+impl SignalNoiseRatio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subtract each waveform's mean before comparing them.
+    pub fn with_zero_mean(mut self) -> Self {
+        self.zero_mean = true;
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[f64]])> for SignalNoiseRatio {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[f64]]),
+    ) -> Result<(), MetricError> {
+        verify_waveforms(predictions, targets)?;
+        for (prediction, target) in predictions.iter().zip(targets) {
+            let (prediction, target) = if self.zero_mean {
+                (centered(prediction), centered(target))
+            } else {
+                (prediction.to_vec(), target.to_vec())
+            };
+            let noise = prediction.iter().zip(&target).map(|(p, t)| t - p);
+            self.sum += decibels(energy(target.iter().copied()), energy(noise));
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.count = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.sum / self.count as f64)
+    }
+}
+
+/// Check that waveforms pair up, have equal lengths, and hold finite samples.
+pub(crate) fn verify_waveforms(
+    predictions: &[&[f64]],
+    targets: &[&[f64]],
+) -> Result<(), MetricError> {
+    if predictions.len() != targets.len() {
+        return Err(MetricError::LengthMismatch {
+            predictions: predictions.len(),
+            targets: targets.len(),
+        });
+    }
+    for (prediction, target) in predictions.iter().zip(targets) {
+        if prediction.len() != target.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: prediction.len(),
+                targets: target.len(),
+            });
+        }
+        for &sample in prediction.iter().chain(target.iter()) {
+            verify_range(sample, f64::MIN, f64::MAX)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn centered(waveform: &[f64]) -> Vec<f64> {
+    let mean = waveform.iter().sum::<f64>() / waveform.len().max(1) as f64;
+    waveform.iter().map(|sample| sample - mean).collect()
+}
+
+pub(crate) fn energy(samples: impl Iterator<Item = f64>) -> f64 {
+    samples.map(|sample| sample * sample).sum()
+}
+
+/// `10 log10(signal / noise)`, with both energies offset by machine epsilon so silent
+/// waveforms stay finite.
+pub(crate) fn decibels(signal: f64, noise: f64) -> f64 {
+    10.0 * ((signal + f64::EPSILON) / (noise + f64::EPSILON)).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignalNoiseRatio;
+    use crate::core::Metric;
+
+    #[test]
+    fn zero_mean_ignores_offsets() {
+        let target: [&[f64]; 2] = [&[1.0, -1.0, 1.0, -1.0], &[0.5, 0.0, -0.5, 0.0]];
+        let offset: [&[f64]; 2] = [&[3.0, 1.0, 3.0, 1.0], &[0.5, 0.0, -0.5, 0.0]];
+
+        let mut snr = SignalNoiseRatio::new();
+        snr.update((&offset[..1], &target[..1])).unwrap();
+        // noise energy 16 against signal energy 4
+        assert!((snr.compute().unwrap() - 10.0 * 0.25_f64.log10()).abs() < 1e-9);
+
+        let mut centered = SignalNoiseRatio::new().with_zero_mean();
+        centered.update((&offset, &target)).unwrap();
+        assert!(centered.compute().unwrap() > 150.0);
+
+        assert!(
+            centered
+                .update((&[&[1.0][..]], &[&[1.0, 2.0][..]]))
+                .is_err()
+        );
+        centered.reset();
+        assert_eq!(centered.compute(), None);
+    }
+}
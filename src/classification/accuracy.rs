@@ -1,7 +1,10 @@
-use crate::core::{Metric, MetricError};
-use crate::utils::AverageMethod;
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{AverageMethod, InputTransform, verify_range, verify_reverted_count};
 
-use super::stat_scores::{BinaryStatScores, MulticlassStatScores};
+use super::stat_scores::{
+    BinaryStatScores, MulticlassStatScores, MultilabelStatScores, verify_class_weights,
+    verify_multilabel_batch, weighted_class_mean,
+};
 
 /// Binary accuracy over thresholded probabilities.
 ///
@@ -25,6 +28,27 @@ impl BinaryAccuracy {
         let stat_scores = BinaryStatScores::new(threshold);
         Self { stat_scores }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+
+    /// Score already thresholded `0`/`1` predictions, see [`BinaryStatScores::update_labels`].
+    pub fn update_labels(
+        &mut self,
+        predictions: &[usize],
+        targets: &[usize],
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update_labels(predictions, targets)
+    }
 }
 
 impl Metric<(&[f64], &[usize])> for BinaryAccuracy {
@@ -49,6 +73,12 @@ impl Metric<(&[f64], &[usize])> for BinaryAccuracy {
     }
 }
 
+impl Revertible<(&[f64], &[usize])> for BinaryAccuracy {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 /// Macro/micro accuracy for multi-class classification.
 /// # Example
 ///
@@ -72,6 +102,7 @@ impl Metric<(&[f64], &[usize])> for BinaryAccuracy {
 pub struct MulticlassAccuracy {
     stat_scores: MulticlassStatScores,
     average_method: AverageMethod,
+    class_weights: Option<Vec<f64>>,
 }
 
 impl MulticlassAccuracy {
@@ -81,8 +112,29 @@ impl MulticlassAccuracy {
         Self {
             stat_scores,
             average_method,
+            class_weights: None,
         }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`MulticlassStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Average per-class values with explicit class weights (e.g. business costs) instead of
+    /// the configured [`AverageMethod`].
+    pub fn with_class_weights(mut self, weights: Vec<f64>) -> Self {
+        verify_class_weights(&weights, self.stat_scores.num_classes);
+        self.class_weights = Some(weights);
+        self
+    }
+
+    /// One-vs-rest accuracy `(TP + TN) / N` of every class; their mean is the `Macro` value.
+    pub fn compute_per_class(&self) -> Option<Vec<f64>> {
+        self.stat_scores
+            .per_class(|tp, fp, fn_, tn| (tp + tn, tp + fp + fn_ + tn))
+    }
 }
 
 impl Metric<(&[&[f64]], &[usize])> for MulticlassAccuracy {
@@ -99,6 +151,11 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassAccuracy {
     }
 
     fn compute(&self) -> Option<Self::Output> {
+        if let Some(weights) = &self.class_weights {
+            return self
+                .compute_per_class()
+                .map(|values| weighted_class_mean(&values, weights));
+        }
         if self.stat_scores.total == 0 {
             return None;
         }
@@ -141,11 +198,211 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassAccuracy {
     }
 }
 
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassAccuracy {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// Multilabel accuracy: every label is thresholded independently and scored as its own binary
+/// problem against multi-hot targets.
+///
+/// `Micro` pools every `(sample, label)` decision, `Macro` averages the per-label accuracies and
+/// `Weighted` weights them by label support (`TP + FN`).
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelAccuracy};
+/// use rust_metrics::utils::AverageMethod;
+///
+/// let preds: [&[f64]; 2] = [&[0.11, 0.22, 0.84], &[0.73, 0.33, 0.92]];
+/// let targets: [&[usize]; 2] = [&[0, 1, 0], &[1, 0, 1]];
+///
+/// let mut metric = MultilabelAccuracy::new(3, 0.5, AverageMethod::Macro);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelAccuracy {
+    stat_scores: MultilabelStatScores,
+    average_method: AverageMethod,
+}
+
+impl MultilabelAccuracy {
+    pub fn new(num_labels: usize, threshold: f64, average_method: AverageMethod) -> Self {
+        Self {
+            stat_scores: MultilabelStatScores::new(num_labels, threshold),
+            average_method,
+        }
+    }
+
+    /// Use a separate decision threshold for each label.
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        self.stat_scores = self.stat_scores.with_thresholds(thresholds);
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelAccuracy {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let total = self.stat_scores.total;
+        if total == 0 {
+            return None;
+        }
+        let tp = &self.stat_scores.true_positive;
+        let tn = &self.stat_scores.true_negative;
+        let accuracies: Vec<f64> = tp
+            .iter()
+            .zip(tn)
+            .map(|(tp, tn)| (tp + tn) as f64 / total as f64)
+            .collect();
+
+        match self.average_method {
+            AverageMethod::Micro => {
+                let correct: usize = tp.iter().sum::<usize>() + tn.iter().sum::<usize>();
+                Some(correct as f64 / (total * self.stat_scores.num_labels) as f64)
+            }
+            AverageMethod::Macro => Some(accuracies.iter().sum::<f64>() / accuracies.len() as f64),
+            AverageMethod::Weighted => {
+                let support = self.stat_scores.support();
+                let total_support: usize = support.iter().sum();
+                if total_support == 0 {
+                    return None;
+                }
+                let weighted_sum: f64 = accuracies
+                    .iter()
+                    .zip(&support)
+                    .map(|(accuracy, &support)| accuracy * support as f64)
+                    .sum();
+                Some(weighted_sum / total_support as f64)
+            }
+        }
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[usize]])> for MultilabelAccuracy {
+    fn revert(&mut self, input: (&[&[f64]], &[&[usize]])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// Multilabel exact-match (subset) accuracy: a sample counts as correct only when every
+/// thresholded label agrees with its target row.
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelExactMatch};
+///
+/// let preds: [&[f64]; 3] = [&[0.9, 0.2, 0.7], &[0.6, 0.4, 0.1], &[0.3, 0.8, 0.55]];
+/// let targets: [&[usize]; 3] = [&[1, 0, 1], &[1, 1, 0], &[0, 1, 1]];
+///
+/// let mut metric = MultilabelExactMatch::new(3, 0.5);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelExactMatch {
+    thresholds: Vec<f64>,
+    correct: usize,
+    total: usize,
+}
+
+impl MultilabelExactMatch {
+    pub fn new(num_labels: usize, threshold: f64) -> Self {
+        assert!(num_labels >= 1, "num_labels must be at least 1");
+        verify_range(threshold, 0.0, 1.0).unwrap();
+        Self {
+            thresholds: vec![threshold; num_labels],
+            correct: 0,
+            total: 0,
+        }
+    }
+
+    /// Use a separate decision threshold for each label.
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        assert_eq!(
+            thresholds.len(),
+            self.thresholds.len(),
+            "expected one threshold per label"
+        );
+        for &threshold in &thresholds {
+            verify_range(threshold, 0.0, 1.0).unwrap();
+        }
+        self.thresholds = thresholds;
+        self
+    }
+
+    fn count_correct(
+        &self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<usize, MetricError> {
+        verify_multilabel_batch(predictions, targets, self.thresholds.len())?;
+        let correct = predictions
+            .iter()
+            .zip(targets.iter())
+            .filter(|(prediction, target)| {
+                prediction
+                    .iter()
+                    .zip(target.iter())
+                    .zip(&self.thresholds)
+                    .all(|((&score, &label), &threshold)| (score > threshold) == (label == 1))
+            })
+            .count();
+        Ok(correct)
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelExactMatch {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[&[f64]], &[&[usize]])) -> Result<(), MetricError> {
+        self.correct += self.count_correct(input)?;
+        self.total += input.0.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.correct = 0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.correct as f64 / self.total as f64)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[usize]])> for MultilabelExactMatch {
+    fn revert(&mut self, input: (&[&[f64]], &[&[usize]])) -> Result<(), MetricError> {
+        let correct = self.count_correct(input)?;
+        verify_reverted_count(input.0.len(), self.total)?;
+        verify_reverted_count(correct, self.correct)?;
+        self.correct -= correct;
+        self.total -= input.0.len();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::BinaryAccuracy;
     use super::MulticlassAccuracy;
-    use crate::core::Metric;
+    use super::MultilabelAccuracy;
+    use super::MultilabelExactMatch;
+    use crate::core::{Metric, Revertible};
 
     #[test]
     fn binary_accuracy() {
@@ -160,6 +417,16 @@ mod tests {
         assert_eq!(metric.compute(), None);
     }
 
+    #[test]
+    fn binary_accuracy_from_hard_labels() {
+        let mut metric = BinaryAccuracy::default();
+        let targets = [0, 1, 0, 1, 0, 1];
+        metric.update_labels(&[0, 0, 1, 1, 0, 1], &targets).unwrap();
+        assert!((metric.compute().unwrap() - (2.0 / 3.0)).abs() < f64::EPSILON);
+
+        assert!(metric.update_labels(&[2], &[1]).is_err());
+    }
+
     #[test]
     fn multiclass_accuracy() {
         let mut metric = MulticlassAccuracy::new(3, super::AverageMethod::Macro);
@@ -174,6 +441,81 @@ mod tests {
         let result = metric.compute().unwrap();
         assert!((result - 0.8333333333333334).abs() < f64::EPSILON);
 
+        assert_eq!(metric.compute_per_class(), Some(vec![0.75, 0.75, 1.0]));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn multiclass_accuracy_revert() {
+        let preds: [&[f64]; 2] = [&[0.16, 0.26, 0.58][..], &[0.22, 0.61, 0.17][..]];
+        let duplicated: [&[f64]; 2] = [&[0.71, 0.09, 0.20][..], &[0.05, 0.82, 0.13][..]];
+
+        let mut expected = MulticlassAccuracy::new(3, super::AverageMethod::Macro);
+        expected.update((&preds, &[2, 1])).unwrap();
+
+        let mut metric = MulticlassAccuracy::new(3, super::AverageMethod::Macro);
+        metric.update((&preds, &[2, 1])).unwrap();
+        metric.update((&duplicated, &[0, 0])).unwrap();
+        assert_ne!(metric.compute(), expected.compute());
+
+        metric.revert((&duplicated, &[0, 0])).unwrap();
+        assert_eq!(metric.compute(), expected.compute());
+
+        assert!(metric.revert((&duplicated, &[0, 0])).is_err());
+        assert_eq!(metric.compute(), expected.compute());
+    }
+
+    #[test]
+    fn multilabel_accuracy_averaging() {
+        let preds: [&[f64]; 4] = [
+            &[0.9, 0.2, 0.6][..],
+            &[0.3, 0.8, 0.4][..],
+            &[0.7, 0.1, 0.2][..],
+            &[0.2, 0.6, 0.9][..],
+        ];
+        let targets: [&[usize]; 4] = [
+            &[1, 0, 0][..],
+            &[0, 1, 0][..],
+            &[0, 0, 0][..],
+            &[0, 1, 1][..],
+        ];
+
+        // per-label accuracy: [0.75, 1.0, 0.75], support: [1, 2, 1]
+        let mut micro = MultilabelAccuracy::new(3, 0.5, super::AverageMethod::Micro);
+        micro.update((&preds[..2], &targets[..2])).unwrap();
+        micro.update((&preds[2..], &targets[2..])).unwrap();
+        assert!((micro.compute().unwrap() - 10.0 / 12.0).abs() < f64::EPSILON);
+
+        let mut macro_acc = MultilabelAccuracy::new(3, 0.5, super::AverageMethod::Macro);
+        macro_acc.update((&preds, &targets)).unwrap();
+        assert!((macro_acc.compute().unwrap() - 2.5 / 3.0).abs() < f64::EPSILON);
+
+        let mut weighted = MultilabelAccuracy::new(3, 0.5, super::AverageMethod::Weighted);
+        weighted.update((&preds, &targets)).unwrap();
+        assert!((weighted.compute().unwrap() - 3.5 / 4.0).abs() < f64::EPSILON);
+
+        assert!(weighted.update((&preds[..1], &[&[1, 0][..]])).is_err());
+
+        micro.reset();
+        assert_eq!(micro.compute(), None);
+    }
+
+    #[test]
+    fn multilabel_exact_match_revert() {
+        let preds: [&[f64]; 2] = [&[0.9, 0.2][..], &[0.6, 0.4][..]];
+        let targets: [&[usize]; 2] = [&[1, 0][..], &[1, 1][..]];
+
+        let mut metric = MultilabelExactMatch::new(2, 0.5).with_thresholds(vec![0.5, 0.3]);
+        metric.update((&preds[..1], &targets[..1])).unwrap();
+        metric.update((&preds[1..], &targets[1..])).unwrap();
+        assert_eq!(metric.compute(), Some(1.0));
+
+        metric.revert((&preds[1..], &targets[1..])).unwrap();
+        assert_eq!(metric.compute(), Some(1.0));
+        assert!(metric.revert((&preds, &targets)).is_err());
+
         metric.reset();
         assert_eq!(metric.compute(), None);
     }
@@ -0,0 +1,92 @@
+use crate::core::{Metric, MetricError};
+
+use super::curve::BinaryScores;
+
+/// Area under the precision-recall-gain curve for binary classification.
+///
+/// Precision and recall are rescaled into gains relative to the positive rate, so the baseline
+/// of an always-positive classifier sits at zero regardless of class imbalance. Like
+/// [`BinaryAuroc`](crate::BinaryAuroc), `0` bins keeps every sample and any value `> 1` uses a
+/// histogram approximation.
+///
+/// ```
+/// use rust_metrics::{BinaryAuprg, Metric};
+///
+/// let preds = [0.9, 0.8, 0.7, 0.1];
+/// let target = [1_usize, 0, 1, 0];
+///
+/// let mut auprg = BinaryAuprg::new(0);
+/// auprg.update((&preds, &target)).unwrap();
+/// assert!((auprg.compute().unwrap() - 0.25).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryAuprg {
+    scores: BinaryScores,
+}
+
+impl Default for BinaryAuprg {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl BinaryAuprg {
+    pub fn new(bins: usize) -> Self {
+        Self {
+            scores: BinaryScores::new(bins),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryAuprg {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.scores.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.scores.auprg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryAuprg;
+    use crate::core::Metric;
+
+    #[test]
+    fn binary_auprg() {
+        let mut auprg = BinaryAuprg::new(0);
+        auprg
+            .update((&[0.9, 0.8, 0.2, 0.1], &[1, 1, 0, 0]))
+            .expect("update should succeed");
+        assert!((auprg.compute().unwrap() - 1.0).abs() < f64::EPSILON);
+
+        // ties at every threshold reduce to the always-positive baseline
+        auprg.reset();
+        auprg
+            .update((&[0.5, 0.5, 0.5, 0.5], &[1, 0, 0, 0]))
+            .expect("update should succeed");
+        assert!(auprg.compute().unwrap().abs() < f64::EPSILON);
+
+        auprg.reset();
+        assert_eq!(auprg.compute(), None);
+    }
+
+    #[test]
+    fn binned_auprg_matches_exact_on_bin_edges() {
+        let preds = [0.0, 0.25, 0.5, 0.75, 1.0, 0.5];
+        let target = [0, 0, 1, 0, 1, 1];
+
+        let mut exact = BinaryAuprg::new(0);
+        exact.update((&preds, &target)).unwrap();
+        let mut binned = BinaryAuprg::new(5);
+        binned.update((&preds, &target)).unwrap();
+        assert!((exact.compute().unwrap() - binned.compute().unwrap()).abs() < 1e-12);
+    }
+}
@@ -1,19 +1,7 @@
-use std::cmp::Ordering;
+use crate::core::{Metric, MetricError, Snapshot};
 
-use crate::core::{Metric, MetricError};
-use crate::utils::{verify_binary_label, verify_range};
-
-#[derive(Debug, Clone)]
-enum BinaryAurocMode {
-    Exact {
-        samples: Vec<(f64, bool)>,
-    },
-    Binned {
-        bins: usize,
-        pos_hist: Vec<u64>,
-        neg_hist: Vec<u64>,
-    },
-}
+use super::curve::BinaryScores;
+use super::delong::{AurocVariance, DeLong};
 
 /// ROC AUC for binary classification with exact or histogrammed accumulation.
 ///
@@ -31,9 +19,23 @@ enum BinaryAurocMode {
 /// auroc.update((&preds, &target)).unwrap();
 /// assert!((auroc.compute().unwrap() - 0.5).abs() < f64::EPSILON);
 /// ```
+///
+/// [`BinaryAuroc::with_max_fpr`] restricts the area to low false-positive rates:
+///
+/// ```
+/// use rust_metrics::{BinaryAuroc, Metric};
+///
+/// let preds = [0.1, 0.4, 0.35, 0.8];
+/// let target = [0_usize, 0, 1, 1];
+///
+/// let mut auroc = BinaryAuroc::new(0).with_max_fpr(0.5);
+/// auroc.update((&preds, &target)).unwrap();
+/// assert!((auroc.compute().unwrap() - 2.0 / 3.0).abs() < 1e-12);
+/// ```
 #[derive(Debug, Clone)]
 pub struct BinaryAuroc {
-    mode: BinaryAurocMode,
+    scores: BinaryScores,
+    max_fpr: Option<f64>,
 }
 
 impl Default for BinaryAuroc {
@@ -44,19 +46,29 @@ impl Default for BinaryAuroc {
 
 impl BinaryAuroc {
     pub fn new(bins: usize) -> Self {
-        let mode = match bins {
-            0 => BinaryAurocMode::Exact {
-                samples: Vec::new(),
-            },
-            1 => panic!("bins must be 0 (exact) or greater than 1 (binned)"),
-            _ => BinaryAurocMode::Binned {
-                bins,
-                pos_hist: vec![0; bins],
-                neg_hist: vec![0; bins],
-            },
-        };
-
-        Self { mode }
+        Self {
+            scores: BinaryScores::new(bins),
+            max_fpr: None,
+        }
+    }
+
+    /// Only integrate the ROC curve up to a false-positive rate of `max_fpr` and apply the
+    /// McClish correction, so `0.5` still means chance level and `1.0` a perfect ranking.
+    pub fn with_max_fpr(mut self, max_fpr: f64) -> Self {
+        assert!(
+            max_fpr > 0.0 && max_fpr <= 1.0,
+            "max_fpr must lie in (0, 1]"
+        );
+        self.max_fpr = Some(max_fpr);
+        self
+    }
+
+    /// DeLong variance of the accumulated AUROC, see [`DeLong`].
+    ///
+    /// Only available in exact mode and once at least two samples of each class were seen. The
+    /// estimate always covers the full curve, regardless of [`BinaryAuroc::with_max_fpr`].
+    pub fn delong(&self) -> Option<AurocVariance> {
+        DeLong::from_samples(&self.scores.samples()?)
     }
 }
 
@@ -64,136 +76,103 @@ impl Metric<(&[f64], &[usize])> for BinaryAuroc {
     type Output = f64;
 
     fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
-        if predictions.len() != targets.len() {
-            return Err(MetricError::LengthMismatch {
-                predictions: predictions.len(),
-                targets: targets.len(),
-            });
+        self.scores.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        compute_auroc(&self.scores, self.max_fpr)
+    }
+}
+
+fn compute_auroc(scores: &BinaryScores, max_fpr: Option<f64>) -> Option<f64> {
+    match max_fpr {
+        Some(max_fpr) => scores.partial_auroc(max_fpr),
+        None => scores.auroc(),
+    }
+}
+
+impl Snapshot for BinaryAuroc {
+    type Snapshot = BinaryAurocSnapshot;
+
+    fn snapshot(&self) -> Self::Snapshot {
+        BinaryAurocSnapshot {
+            scores: self.scores.clone(),
+            max_fpr: self.max_fpr,
         }
+    }
+}
+
+/// Frozen state of a [`BinaryAuroc`], see [`Snapshot`].
+#[derive(Debug, Clone)]
+pub struct BinaryAurocSnapshot {
+    scores: BinaryScores,
+    max_fpr: Option<f64>,
+}
+
+impl BinaryAurocSnapshot {
+    pub fn compute(&self) -> Option<f64> {
+        compute_auroc(&self.scores, self.max_fpr)
+    }
+}
+
+/// Gini coefficient (`2 * AUROC - 1`) for binary classification, as reported in credit risk.
+///
+/// Shares the exact/binned accumulation of [`BinaryAuroc`]: `0` keeps every sample, any value
+/// `> 1` buckets scores into that many bins.
+///
+/// ```
+/// use rust_metrics::{BinaryGini, Metric};
+///
+/// let preds = [0.1, 0.4, 0.35, 0.8];
+/// let target = [0_usize, 0, 1, 1];
+///
+/// let mut gini = BinaryGini::new(0);
+/// gini.update((&preds, &target)).unwrap();
+/// assert!((gini.compute().unwrap() - 0.5).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryGini {
+    scores: BinaryScores,
+}
 
-        match &mut self.mode {
-            BinaryAurocMode::Exact { samples } => {
-                for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
-                    verify_range(prediction, 0.0, 1.0)?;
-                    verify_binary_label(target)?;
-                    let target_bool = target == 1;
-                    samples.push((prediction, target_bool));
-                }
-                Ok(())
-            }
-            BinaryAurocMode::Binned {
-                bins,
-                pos_hist,
-                neg_hist,
-            } => {
-                let max_bin_idx = (*bins - 1) as f64;
-                for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
-                    verify_range(prediction, 0.0, 1.0)?;
-                    verify_binary_label(target)?;
-                    let bin_index = ((prediction * max_bin_idx).round()) as usize;
-                    if target == 1 {
-                        pos_hist[bin_index] += 1;
-                    } else {
-                        neg_hist[bin_index] += 1;
-                    }
-                }
-                Ok(())
-            }
+impl Default for BinaryGini {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl BinaryGini {
+    pub fn new(bins: usize) -> Self {
+        Self {
+            scores: BinaryScores::new(bins),
         }
     }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryGini {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.scores.update(predictions, targets)
+    }
 
     fn reset(&mut self) {
-        match &mut self.mode {
-            BinaryAurocMode::Exact { samples } => samples.clear(),
-            BinaryAurocMode::Binned {
-                pos_hist, neg_hist, ..
-            } => {
-                for value in pos_hist.iter_mut() {
-                    *value = 0;
-                }
-                for value in neg_hist.iter_mut() {
-                    *value = 0;
-                }
-            }
-        }
+        self.scores.reset();
     }
 
     fn compute(&self) -> Option<Self::Output> {
-        match &self.mode {
-            BinaryAurocMode::Exact { samples } => {
-                if samples.is_empty() {
-                    return None;
-                }
-
-                let mut sorted = samples.to_vec();
-                sorted.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
-
-                let total_pos = sorted.iter().filter(|(_, t)| *t).count() as f64;
-                let total_neg = sorted.len() as f64 - total_pos;
-
-                if total_pos == 0.0 || total_neg == 0.0 {
-                    return None;
-                }
-
-                let mut tp = 0.0;
-                let mut fp = 0.0;
-                let mut auc = 0.0;
-                let mut idx = 0;
-
-                while idx < sorted.len() {
-                    let current_score = sorted[idx].0;
-                    let prev_tp = tp;
-                    let prev_fp = fp;
-
-                    let mut group_pos = 0.0;
-                    let mut group_neg = 0.0;
-
-                    while idx < sorted.len() && sorted[idx].0 == current_score {
-                        if sorted[idx].1 {
-                            group_pos += 1.0;
-                        } else {
-                            group_neg += 1.0;
-                        }
-                        idx += 1;
-                    }
-
-                    tp += group_pos;
-                    fp += group_neg;
-                    auc += (fp - prev_fp) * (tp + prev_tp) / 2.0;
-                }
-
-                Some(auc / (total_pos * total_neg))
-            }
-            BinaryAurocMode::Binned {
-                pos_hist, neg_hist, ..
-            } => {
-                let mut tp = 0.0;
-                let mut fp = 0.0;
-                let total_pos: f64 = pos_hist.iter().sum::<u64>() as f64;
-                let total_neg: f64 = neg_hist.iter().sum::<u64>() as f64;
-                if total_pos == 0.0 && total_neg == 0.0 {
-                    return None;
-                }
-                let mut auc = 0.0;
-
-                for (p, n) in pos_hist.iter().zip(neg_hist.iter()).rev() {
-                    let prev_tp = tp;
-                    let prev_fp = fp;
-                    tp += *p as f64;
-                    fp += *n as f64;
-                    auc += (fp - prev_fp) * (tp + prev_tp) / 2.0;
-                }
-
-                Some(auc / (total_pos * total_neg))
-            }
-        }
+        self.scores.auroc().map(|auroc| 2.0 * auroc - 1.0)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::BinaryAuroc;
-    use crate::core::Metric;
+    use super::{BinaryAuroc, BinaryGini};
+    use crate::core::{Metric, Snapshot};
 
     #[test]
     fn binary_auroc() {
@@ -211,4 +190,60 @@ mod tests {
         exact.reset();
         assert_eq!(exact.compute(), None);
     }
+
+    #[test]
+    fn partial_auroc_is_standardized() {
+        let preds = [0.1, 0.2, 0.3, 0.4, 0.6, 0.7, 0.8, 0.9];
+        let target = [0_usize, 1, 0, 0, 1, 0, 1, 1];
+
+        let mut perfect = BinaryAuroc::new(0).with_max_fpr(0.3);
+        perfect
+            .update((&[0.1, 0.2, 0.8, 0.9], &[0, 0, 1, 1]))
+            .unwrap();
+        assert!((perfect.compute().unwrap() - 1.0).abs() < 1e-12);
+
+        let mut full = BinaryAuroc::new(0);
+        let mut capped = BinaryAuroc::new(0).with_max_fpr(1.0);
+        full.update((&preds, &target)).unwrap();
+        capped.update((&preds, &target)).unwrap();
+        assert_eq!(full.compute(), capped.compute());
+
+        // ROC: (0, 0) (0, .25) (0, .5) (.25, .5) (.25, .75) (.5, .75) ...; the area up to
+        // FPR 0.4 is 0.125 + 0.15 * 0.75 = 0.2375
+        let mut partial = BinaryAuroc::new(0).with_max_fpr(0.4);
+        partial.update((&preds, &target)).unwrap();
+        let expected = 0.5 * (1.0 + (0.2375 - 0.08) / (0.4 - 0.08));
+        assert!((partial.compute().unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn gini_tracks_auroc() {
+        let preds = [0.0, 0.5, 0.7, 0.8, 0.95];
+        let target = [0_usize, 1, 1, 0, 1];
+
+        for bins in [0, 50] {
+            let mut auroc = BinaryAuroc::new(bins);
+            let mut gini = BinaryGini::new(bins);
+            auroc.update((&preds, &target)).unwrap();
+            gini.update((&preds[..2], &target[..2])).unwrap();
+            gini.update((&preds[2..], &target[2..])).unwrap();
+            let expected = 2.0 * auroc.compute().unwrap() - 1.0;
+            assert!((gini.compute().unwrap() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn snapshot_is_isolated_from_later_updates() {
+        let mut auroc = BinaryAuroc::new(0);
+        auroc
+            .update((&[0.0, 0.5, 0.7, 0.8], &[0, 1, 1, 0]))
+            .unwrap();
+        let snapshot = auroc.snapshot();
+
+        auroc.update((&[0.9, 0.1], &[1, 0])).unwrap();
+        auroc.reset();
+
+        assert!((snapshot.compute().unwrap() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(auroc.compute(), None);
+    }
 }
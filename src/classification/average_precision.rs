@@ -0,0 +1,270 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{AverageMethod, InputTransform, verify_label};
+
+use super::curve::BinaryScores;
+
+/// Average precision (area under the precision-recall curve) for binary classification.
+///
+/// Uses the step-wise `sum_n (R_n - R_{n-1}) P_n` definition over decreasing thresholds. Passing
+/// `0` to [`BinaryAveragePrecision::new`] keeps every sample; any value `> 1` buckets scores into
+/// that many bins so memory stays bounded while streaming.
+///
+/// ```
+/// use rust_metrics::{BinaryAveragePrecision, Metric};
+///
+/// let preds = [0.1, 0.4, 0.35, 0.8];
+/// let target = [0_usize, 0, 1, 1];
+///
+/// let mut metric = BinaryAveragePrecision::new(0);
+/// metric.update((&preds, &target)).unwrap();
+/// assert!((metric.compute().unwrap() - 0.8333333333333333).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryAveragePrecision {
+    scores: BinaryScores,
+}
+
+impl Default for BinaryAveragePrecision {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl BinaryAveragePrecision {
+    pub fn new(bins: usize) -> Self {
+        Self {
+            scores: BinaryScores::new(bins),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryAveragePrecision {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.scores.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.scores.average_precision()
+    }
+}
+
+/// One-vs-rest average precision (area under the precision-recall curve) for multiclass tasks.
+///
+/// Each class is scored as a binary problem on its own probability column using the step-wise
+/// `sum_n (R_n - R_{n-1}) P_n` definition. `Macro` averages classes that have at least one
+/// positive sample, `Weighted` weights them by support, and `Micro` pools every
+/// `(score, is_target)` pair into a single binary problem. Scores are kept exactly unless
+/// [`with_bins`](Self::with_bins) is used.
+///
+/// ```
+/// use rust_metrics::{Metric, MulticlassAveragePrecision};
+/// use rust_metrics::utils::AverageMethod;
+///
+/// let preds: [&[f64]; 5] = [
+///     &[0.75, 0.05, 0.05, 0.05, 0.05],
+///     &[0.05, 0.75, 0.05, 0.05, 0.05],
+///     &[0.05, 0.05, 0.75, 0.05, 0.05],
+///     &[0.05, 0.05, 0.05, 0.75, 0.05],
+///     &[0.05, 0.05, 0.05, 0.05, 0.75],
+/// ];
+/// let target = [0, 1, 3, 2, 4];
+///
+/// let mut metric = MulticlassAveragePrecision::new(5, AverageMethod::Macro);
+/// metric.update((&preds, &target)).unwrap();
+/// assert!((metric.compute().unwrap() - 0.68).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MulticlassAveragePrecision {
+    num_classes: usize,
+    average_method: AverageMethod,
+    input_transform: InputTransform,
+    per_class: Vec<BinaryScores>,
+    pooled: BinaryScores,
+    total: usize,
+}
+
+impl MulticlassAveragePrecision {
+    pub fn new(num_classes: usize, average_method: AverageMethod) -> Self {
+        assert!(num_classes >= 2, "num_classes must be at least 2");
+        Self {
+            num_classes,
+            average_method,
+            input_transform: InputTransform::None,
+            per_class: vec![BinaryScores::new(0); num_classes],
+            pooled: BinaryScores::new(0),
+            total: 0,
+        }
+    }
+
+    /// Bucket each class's scores into `bins` evenly spaced bins instead of keeping every sample.
+    pub fn with_bins(mut self, bins: usize) -> Self {
+        assert!(bins > 1, "bins must be greater than 1");
+        self.per_class = vec![BinaryScores::new(bins); self.num_classes];
+        self.pooled = BinaryScores::new(bins);
+        self
+    }
+
+    /// Map each row of raw scores to probabilities first, e.g. [`InputTransform::Softmax`] for
+    /// logits.
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.input_transform = input_transform;
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[usize])> for MulticlassAveragePrecision {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        let mut probabilities = Vec::with_capacity(predictions.len());
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            verify_label(target, self.num_classes)?;
+            if prediction.len() != self.num_classes {
+                return Err(MetricError::IncompatibleInput {
+                    expected: format!(
+                        "length of predictions must be equal to number of classes: {}",
+                        self.num_classes
+                    ),
+                    got: format!("got {}", prediction.len()),
+                });
+            }
+            probabilities.push(self.input_transform.probabilities(prediction)?);
+        }
+
+        let mut batches = vec![Vec::with_capacity(predictions.len()); self.num_classes];
+        for (prediction, &target) in probabilities.iter().zip(targets.iter()) {
+            for (class_idx, &score) in prediction.iter().enumerate() {
+                batches[class_idx].push((score, class_idx == target));
+            }
+        }
+        if let AverageMethod::Micro = self.average_method {
+            self.pooled.extend(batches.concat());
+        } else {
+            for (scores, batch) in self.per_class.iter_mut().zip(batches) {
+                scores.extend(batch);
+            }
+        }
+        self.total += predictions.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        for scores in self.per_class.iter_mut() {
+            scores.reset();
+        }
+        self.pooled.reset();
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+
+        match self.average_method {
+            AverageMethod::Micro => self.pooled.average_precision(),
+            AverageMethod::Macro => {
+                let values: Vec<f64> = self
+                    .per_class
+                    .iter()
+                    .filter_map(|scores| scores.average_precision())
+                    .collect();
+                if values.is_empty() {
+                    None
+                } else {
+                    Some(values.iter().sum::<f64>() / values.len() as f64)
+                }
+            }
+            AverageMethod::Weighted => {
+                let mut weighted_sum = 0.0;
+                let mut support_sum = 0.0;
+                for scores in &self.per_class {
+                    if let Some(ap) = scores.average_precision() {
+                        let (support, _) = scores.totals();
+                        weighted_sum += ap * support;
+                        support_sum += support;
+                    }
+                }
+                if support_sum == 0.0 {
+                    None
+                } else {
+                    Some(weighted_sum / support_sum)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryAveragePrecision, MulticlassAveragePrecision};
+    use crate::core::Metric;
+    use crate::utils::AverageMethod;
+
+    #[test]
+    fn multiclass_average_precision_over_batches() {
+        let preds: [&[f64]; 4] = [
+            &[0.16, 0.26, 0.58][..],
+            &[0.22, 0.61, 0.17][..],
+            &[0.71, 0.09, 0.20][..],
+            &[0.05, 0.82, 0.13][..],
+        ];
+        let target = [2, 1, 0, 0];
+
+        let mut macro_ap = MulticlassAveragePrecision::new(3, AverageMethod::Macro);
+        macro_ap.update((&preds[..2], &target[..2])).unwrap();
+        macro_ap.update((&preds[2..], &target[2..])).unwrap();
+        // class 0: 0.75, class 1: 0.5, class 2: 1.0
+        assert!((macro_ap.compute().unwrap() - 0.75).abs() < 1e-12);
+
+        let mut weighted = MulticlassAveragePrecision::new(3, AverageMethod::Weighted);
+        weighted.update((&preds, &target)).unwrap();
+        assert!((weighted.compute().unwrap() - 0.75).abs() < 1e-12);
+
+        macro_ap.reset();
+        assert_eq!(macro_ap.compute(), None);
+    }
+
+    #[test]
+    fn micro_pools_all_classes() {
+        let preds: [&[f64]; 2] = [&[0.9, 0.1][..], &[0.3, 0.7][..]];
+        let mut micro = MulticlassAveragePrecision::new(2, AverageMethod::Micro);
+        micro.update((&preds, &[0, 1])).unwrap();
+        assert_eq!(micro.compute(), Some(1.0));
+    }
+
+    #[test]
+    fn binned_average_precision_approximates_exact() {
+        let preds = [0.12, 0.48, 0.33, 0.81, 0.67, 0.05, 0.92, 0.4];
+        let target = [0_usize, 1, 0, 1, 0, 0, 1, 1];
+
+        let mut exact = BinaryAveragePrecision::default();
+        exact.update((&preds, &target)).unwrap();
+        let mut binned = BinaryAveragePrecision::new(101);
+        binned.update((&preds[..4], &target[..4])).unwrap();
+        binned.update((&preds[4..], &target[4..])).unwrap();
+        assert!((exact.compute().unwrap() - binned.compute().unwrap()).abs() < 1e-12);
+
+        let columns: Vec<[f64; 2]> = preds.iter().map(|&p| [1.0 - p, p]).collect();
+        let rows: Vec<&[f64]> = columns.iter().map(|c| &c[..]).collect();
+        let mut multiclass =
+            MulticlassAveragePrecision::new(2, AverageMethod::Macro).with_bins(101);
+        multiclass.update((&rows, &target)).unwrap();
+        assert!(multiclass.compute().is_some());
+
+        binned.reset();
+        assert_eq!(binned.compute(), None);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::InputTransform;
+
+use super::confusion_matrix::MulticlassConfusionMatrix;
+use super::stat_scores::BinaryStatScores;
+
+/// How disagreements between classes are penalized by Cohen's kappa.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KappaWeighting {
+    /// Every disagreement costs the same.
+    #[default]
+    None,
+    /// Disagreements cost `|i - j|`, for ordinal classes.
+    Linear,
+    /// Disagreements cost `(i - j)^2`, penalizing distant classes more.
+    Quadratic,
+}
+
+impl KappaWeighting {
+    fn weight(&self, i: usize, j: usize) -> f64 {
+        let distance = i.abs_diff(j) as f64;
+        match self {
+            KappaWeighting::None => f64::from(u8::from(i != j)),
+            KappaWeighting::Linear => distance,
+            KappaWeighting::Quadratic => distance * distance,
+        }
+    }
+}
+
+/// `1 - sum(w * observed) / sum(w * expected)` over a `[target][prediction]` count matrix.
+fn kappa(counts: &[Vec<usize>], weighting: KappaWeighting) -> Option<f64> {
+    let total: usize = counts.iter().flatten().sum();
+    if total == 0 {
+        return None;
+    }
+    let row_totals: Vec<f64> = counts
+        .iter()
+        .map(|row| row.iter().sum::<usize>() as f64)
+        .collect();
+    let column_totals: Vec<f64> = (0..counts.len())
+        .map(|j| counts.iter().map(|row| row[j]).sum::<usize>() as f64)
+        .collect();
+
+    let mut observed = 0.0;
+    let mut expected = 0.0;
+    for (i, row) in counts.iter().enumerate() {
+        for (j, &count) in row.iter().enumerate() {
+            let weight = weighting.weight(i, j);
+            observed += weight * count as f64;
+            expected += weight * row_totals[i] * column_totals[j] / total as f64;
+        }
+    }
+    if expected == 0.0 {
+        return None;
+    }
+    Some(1.0 - observed / expected)
+}
+
+/// Cohen's kappa for binary classification over thresholded probabilities.
+///
+/// Measures agreement between predictions and targets corrected for the agreement expected by
+/// chance. `None` is returned when chance agreement is perfect (e.g. a single class throughout).
+///
+/// ```
+/// use rust_metrics::{BinaryCohenKappa, Metric};
+///
+/// let target = [0_usize, 1, 0, 1, 0, 1];
+/// let preds = [0.11, 0.22, 0.84, 0.73, 0.33, 0.92];
+///
+/// let mut metric = BinaryCohenKappa::default();
+/// metric.update((&preds, &target)).unwrap();
+/// assert!((metric.compute().unwrap() - 1.0 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BinaryCohenKappa {
+    stat_scores: BinaryStatScores,
+    weighting: KappaWeighting,
+}
+
+impl BinaryCohenKappa {
+    pub fn new(threshold: f64, weighting: KappaWeighting) -> Self {
+        Self {
+            stat_scores: BinaryStatScores::new(threshold),
+            weighting,
+        }
+    }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryCohenKappa {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let counts = [
+            vec![
+                self.stat_scores.true_negative,
+                self.stat_scores.false_positive,
+            ],
+            vec![
+                self.stat_scores.false_negative,
+                self.stat_scores.true_positive,
+            ],
+        ];
+        kappa(&counts, self.weighting)
+    }
+}
+
+impl Revertible<(&[f64], &[usize])> for BinaryCohenKappa {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// Cohen's kappa for multiclass classification, computed from the accumulated
+/// [`MulticlassConfusionMatrix`].
+///
+/// With [`KappaWeighting::Linear`] or [`KappaWeighting::Quadratic`] the class indices are treated
+/// as ordinal, so predicting a neighbouring class is penalized less than a distant one.
+///
+/// ```
+/// use rust_metrics::{KappaWeighting, Metric, MulticlassCohenKappa};
+///
+/// let targets = [2, 1, 0, 0, 1, 2];
+/// let preds: [&[f64]; 6] = [
+///     &[0.1, 0.2, 0.7],
+///     &[0.6, 0.3, 0.1],
+///     &[0.8, 0.1, 0.1],
+///     &[0.5, 0.3, 0.2],
+///     &[0.2, 0.7, 0.1],
+///     &[0.1, 0.5, 0.4],
+/// ];
+///
+/// let mut metric = MulticlassCohenKappa::new(3, KappaWeighting::Quadratic);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 0.75).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MulticlassCohenKappa {
+    confusion_matrix: MulticlassConfusionMatrix,
+    weighting: KappaWeighting,
+}
+
+impl MulticlassCohenKappa {
+    pub fn new(num_classes: usize, weighting: KappaWeighting) -> Self {
+        Self {
+            confusion_matrix: MulticlassConfusionMatrix::new(num_classes),
+            weighting,
+        }
+    }
+
+    /// Skip samples whose target equals `ignore_index`, see
+    /// [`MulticlassConfusionMatrix::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.confusion_matrix = self.confusion_matrix.with_ignore_index(ignore_index);
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[usize])> for MulticlassCohenKappa {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        self.confusion_matrix.update(input)
+    }
+
+    fn reset(&mut self) {
+        self.confusion_matrix.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        kappa(self.confusion_matrix.counts(), self.weighting)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassCohenKappa {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        self.confusion_matrix.revert(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryCohenKappa, KappaWeighting, MulticlassCohenKappa};
+    use crate::core::Metric;
+
+    #[test]
+    fn multiclass_kappa_weightings() {
+        let targets = [2, 1, 0, 0, 1, 2];
+        let preds: [&[f64]; 6] = [
+            &[0.1, 0.2, 0.7][..],
+            &[0.6, 0.3, 0.1][..],
+            &[0.8, 0.1, 0.1][..],
+            &[0.5, 0.3, 0.2][..],
+            &[0.2, 0.7, 0.1][..],
+            &[0.1, 0.5, 0.4][..],
+        ];
+        let expected = [
+            (KappaWeighting::None, 0.5),
+            (KappaWeighting::Linear, 0.625),
+            (KappaWeighting::Quadratic, 0.75),
+        ];
+        for (weighting, value) in expected {
+            let mut metric = MulticlassCohenKappa::new(3, weighting);
+            metric.update((&preds[..3], &targets[..3])).unwrap();
+            metric.update((&preds[3..], &targets[3..])).unwrap();
+            assert!((metric.compute().unwrap() - value).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn binary_kappa_degenerate_agreement() {
+        let mut metric = BinaryCohenKappa::default();
+        metric.update((&[0.9, 0.8], &[1, 1])).unwrap();
+        assert_eq!(metric.compute(), None);
+
+        metric.update((&[0.2, 0.1], &[0, 0])).unwrap();
+        assert_eq!(metric.compute(), Some(1.0));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
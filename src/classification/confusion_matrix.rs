@@ -1,14 +1,37 @@
-use crate::core::{Metric, MetricError};
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{InputTransform, verify_label, verify_range, verify_reverted_count};
 
 use super::stat_scores::BinaryStatScores;
 
+/// How a confusion matrix is normalized by `compute_normalized`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Normalization {
+    /// Raw counts, as floats.
+    #[default]
+    None,
+    /// Divide by the number of samples of each target class, so every target class sums to 1.
+    True,
+    /// Divide by the number of samples predicted as each class.
+    Pred,
+    /// Divide by the total number of samples.
+    All,
+}
+
+fn normalized(count: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        count as f64 / denominator as f64
+    }
+}
+
 /// 2×2 confusion matrix for binary classification.
 ///
 /// Returns a matrix in the
 /// `[[TP, FP], [FN, TN]]` layout.
 ///
 /// ```
-/// use rust_metrics::{BinaryConfusionMatrix, Metric};
+/// use rust_metrics::{BinaryConfusionMatrix, Metric, Normalization};
 ///
 /// let target = [1_usize, 1, 0, 0];
 /// let preds = [0.35, 0.85, 0.48, 0.01];
@@ -16,6 +39,9 @@ use super::stat_scores::BinaryStatScores;
 /// let mut bcm = BinaryConfusionMatrix::default();
 /// bcm.update((&preds, &target)).unwrap();
 /// assert_eq!(bcm.compute().unwrap(), [[1, 0], [1, 2]]);
+///
+/// let recall_view = bcm.compute_normalized(Normalization::True).unwrap();
+/// assert_eq!(recall_view, [[0.5, 0.0], [0.5, 1.0]]);
 /// ```
 #[derive(Debug, Clone, Default)]
 pub struct BinaryConfusionMatrix {
@@ -27,6 +53,43 @@ impl BinaryConfusionMatrix {
         let stat_scores = BinaryStatScores::new(threshold);
         Self { stat_scores }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+
+    /// The confusion matrix in the same `[[TP, FP], [FN, TN]]` layout, normalized per
+    /// [`Normalization`]: with `True` each column (target class) sums to 1, with `Pred` each row.
+    pub fn compute_normalized(&self, normalization: Normalization) -> Option<[[f64; 2]; 2]> {
+        let [[tp, fp], [fn_, tn]] = self.compute()?;
+        let matrix = match normalization {
+            Normalization::None => [[tp as f64, fp as f64], [fn_ as f64, tn as f64]],
+            Normalization::True => [
+                [normalized(tp, tp + fn_), normalized(fp, fp + tn)],
+                [normalized(fn_, tp + fn_), normalized(tn, fp + tn)],
+            ],
+            Normalization::Pred => [
+                [normalized(tp, tp + fp), normalized(fp, tp + fp)],
+                [normalized(fn_, fn_ + tn), normalized(tn, fn_ + tn)],
+            ],
+            Normalization::All => {
+                let total = tp + fp + fn_ + tn;
+                [
+                    [normalized(tp, total), normalized(fp, total)],
+                    [normalized(fn_, total), normalized(tn, total)],
+                ]
+            }
+        };
+        Some(matrix)
+    }
 }
 
 impl Metric<(&[f64], &[usize])> for BinaryConfusionMatrix {
@@ -56,3 +119,213 @@ impl Metric<(&[f64], &[usize])> for BinaryConfusionMatrix {
         Some(confusion_matrix)
     }
 }
+
+impl Revertible<(&[f64], &[usize])> for BinaryConfusionMatrix {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// `num_classes × num_classes` confusion matrix for multiclass classification.
+///
+/// Rows are indexed by the target class and columns by the predicted (argmax) class.
+///
+/// ```
+/// use rust_metrics::{Metric, MulticlassConfusionMatrix};
+///
+/// let targets = [2, 1, 0, 0];
+/// let preds: [&[f64]; 4] = [
+///     &[0.16, 0.26, 0.58],
+///     &[0.22, 0.61, 0.17],
+///     &[0.71, 0.09, 0.20],
+///     &[0.05, 0.82, 0.13],
+/// ];
+///
+/// let mut metric = MulticlassConfusionMatrix::new(3);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert_eq!(metric.compute().unwrap(), vec![vec![1, 1, 0], vec![0, 1, 0], vec![0, 0, 1]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MulticlassConfusionMatrix {
+    num_classes: usize,
+    counts: Vec<Vec<usize>>,
+    total: usize,
+    ignore_index: Option<usize>,
+}
+
+impl MulticlassConfusionMatrix {
+    pub fn new(num_classes: usize) -> Self {
+        assert!(num_classes >= 2, "num_classes must be at least 2");
+        Self {
+            num_classes,
+            counts: vec![vec![0; num_classes]; num_classes],
+            total: 0,
+            ignore_index: None,
+        }
+    }
+
+    /// Skip samples whose target equals `ignore_index` (e.g. padding) during updates.
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.ignore_index = Some(ignore_index);
+        self
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.num_classes
+    }
+
+    /// Accumulated counts, indexed as `[target][prediction]`.
+    pub fn counts(&self) -> &[Vec<usize>] {
+        &self.counts
+    }
+
+    /// The confusion matrix normalized per [`Normalization`]: with `True` every row (target
+    /// class) sums to 1, with `Pred` every column. Empty rows or columns stay zero.
+    pub fn compute_normalized(&self, normalization: Normalization) -> Option<Vec<Vec<f64>>> {
+        if self.total == 0 {
+            return None;
+        }
+        let row_totals: Vec<usize> = self.counts.iter().map(|row| row.iter().sum()).collect();
+        let column_totals: Vec<usize> = (0..self.num_classes)
+            .map(|j| self.counts.iter().map(|row| row[j]).sum())
+            .collect();
+        let matrix = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &count)| match normalization {
+                        Normalization::None => count as f64,
+                        Normalization::True => normalized(count, row_totals[i]),
+                        Normalization::Pred => normalized(count, column_totals[j]),
+                        Normalization::All => normalized(count, self.total),
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(matrix)
+    }
+
+    fn batch_counts(
+        &self,
+        (predictions, targets): (&[&[f64]], &[usize]),
+    ) -> Result<Vec<Vec<usize>>, MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+
+        let mut counts = vec![vec![0; self.num_classes]; self.num_classes];
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            if Some(target) == self.ignore_index {
+                continue;
+            }
+            verify_label(target, self.num_classes)?;
+            if prediction.len() != self.num_classes {
+                return Err(MetricError::IncompatibleInput {
+                    expected: format!(
+                        "length of predictions must be equal to number of classes: {}",
+                        self.num_classes
+                    ),
+                    got: format!("got {}", prediction.len()),
+                });
+            }
+            for &score in prediction {
+                verify_range(score, f64::MIN, f64::MAX)?;
+            }
+            let prediction_idx = prediction
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(i, _)| i)
+                .expect("Vector is empty");
+            counts[target][prediction_idx] += 1;
+        }
+        Ok(counts)
+    }
+}
+
+impl Metric<(&[&[f64]], &[usize])> for MulticlassConfusionMatrix {
+    type Output = Vec<Vec<usize>>;
+
+    fn update(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        let batch = self.batch_counts(input)?;
+        self.total += batch.iter().flatten().sum::<usize>();
+        for (row, batch_row) in self.counts.iter_mut().zip(batch) {
+            for (count, batch_count) in row.iter_mut().zip(batch_row) {
+                *count += batch_count;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.counts = vec![vec![0; self.num_classes]; self.num_classes];
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.counts.clone())
+    }
+}
+
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassConfusionMatrix {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        let batch = self.batch_counts(input)?;
+        for (row, batch_row) in self.counts.iter().zip(&batch) {
+            for (&count, &batch_count) in row.iter().zip(batch_row) {
+                verify_reverted_count(batch_count, count)?;
+            }
+        }
+        self.total -= batch.iter().flatten().sum::<usize>();
+        for (row, batch_row) in self.counts.iter_mut().zip(batch) {
+            for (count, batch_count) in row.iter_mut().zip(batch_row) {
+                *count -= batch_count;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MulticlassConfusionMatrix, Normalization};
+    use crate::core::{Metric, Revertible};
+
+    #[test]
+    fn multiclass_confusion_matrix_normalization() {
+        let targets = [2, 1, 0, 0];
+        let preds: [&[f64]; 4] = [
+            &[0.16, 0.26, 0.58][..],
+            &[0.22, 0.61, 0.17][..],
+            &[0.71, 0.09, 0.20][..],
+            &[0.05, 0.82, 0.13][..],
+        ];
+        let mut metric = MulticlassConfusionMatrix::new(3);
+        metric.update((&preds, &targets)).unwrap();
+
+        let by_target = metric.compute_normalized(Normalization::True).unwrap();
+        assert_eq!(by_target[0], vec![0.5, 0.5, 0.0]);
+        let by_pred = metric.compute_normalized(Normalization::Pred).unwrap();
+        assert_eq!(by_pred[1], vec![0.0, 0.5, 0.0]);
+        let overall = metric.compute_normalized(Normalization::All).unwrap();
+        assert_eq!(overall[2][2], 0.25);
+
+        metric.revert((&preds, &targets)).unwrap();
+        assert_eq!(metric.compute_normalized(Normalization::None), None);
+    }
+    #[test]
+    fn multiclass_confusion_matrix_rejects_nan_scores() {
+        let mut metric = MulticlassConfusionMatrix::new(2);
+        let preds: [&[f64]; 2] = [&[0.3, 0.7][..], &[f64::NAN, 0.5][..]];
+        assert!(metric.update((&preds, &[1, 0])).is_err());
+        assert_eq!(metric.compute(), None);
+    }
+}
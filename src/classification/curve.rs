@@ -0,0 +1,313 @@
+use std::cmp::Ordering;
+
+use crate::core::MetricError;
+use crate::utils::{SampleBuffer, verify_binary_label, verify_range};
+
+/// Cumulative counts when every sample scoring at least `threshold` is predicted positive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CurvePoint {
+    pub threshold: f64,
+    pub true_positive: f64,
+    pub false_positive: f64,
+}
+
+/// Exact or histogrammed accumulation of binary scores, shared by the curve-based metrics.
+///
+/// `bins == 0` keeps every sample; any value `> 1` buckets scores into that many evenly spaced
+/// bins over `[0, 1]`.
+#[derive(Debug, Clone)]
+pub(crate) enum BinaryScores {
+    Exact {
+        samples: SampleBuffer<(f64, bool)>,
+    },
+    Binned {
+        bins: usize,
+        pos_hist: Vec<u64>,
+        neg_hist: Vec<u64>,
+    },
+}
+
+impl BinaryScores {
+    pub fn new(bins: usize) -> Self {
+        match bins {
+            0 => BinaryScores::Exact {
+                samples: SampleBuffer::new(),
+            },
+            1 => panic!("bins must be 0 (exact) or greater than 1 (binned)"),
+            _ => BinaryScores::Binned {
+                bins,
+                pos_hist: vec![0; bins],
+                neg_hist: vec![0; bins],
+            },
+        }
+    }
+
+    pub fn update(&mut self, predictions: &[f64], targets: &[usize]) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        let mut batch = Vec::with_capacity(predictions.len());
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            verify_range(prediction, 0.0, 1.0)?;
+            verify_binary_label(target)?;
+            batch.push((prediction, target == 1));
+        }
+        self.extend(batch);
+        Ok(())
+    }
+
+    /// Add already validated `(score, is_positive)` samples.
+    pub fn extend(&mut self, batch: Vec<(f64, bool)>) {
+        match self {
+            BinaryScores::Exact { samples } => samples.push_batch(batch),
+            BinaryScores::Binned {
+                bins,
+                pos_hist,
+                neg_hist,
+            } => {
+                let max_bin_idx = (*bins - 1) as f64;
+                for (prediction, positive) in batch {
+                    let bin_index = ((prediction * max_bin_idx).round()) as usize;
+                    if positive {
+                        pos_hist[bin_index] += 1;
+                    } else {
+                        neg_hist[bin_index] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        match self {
+            BinaryScores::Exact { samples } => samples.clear(),
+            BinaryScores::Binned {
+                pos_hist, neg_hist, ..
+            } => {
+                pos_hist.fill(0);
+                neg_hist.fill(0);
+            }
+        }
+    }
+
+    /// Total number of positive and negative samples.
+    pub fn totals(&self) -> (f64, f64) {
+        match self {
+            BinaryScores::Exact { samples } => {
+                let total_pos = samples.iter().filter(|(_, t)| *t).count() as f64;
+                (total_pos, samples.len() as f64 - total_pos)
+            }
+            BinaryScores::Binned {
+                pos_hist, neg_hist, ..
+            } => (
+                pos_hist.iter().sum::<u64>() as f64,
+                neg_hist.iter().sum::<u64>() as f64,
+            ),
+        }
+    }
+
+    /// Stored `(score, is_positive)` samples; `None` in binned mode.
+    pub fn samples(&self) -> Option<Vec<(f64, bool)>> {
+        match self {
+            BinaryScores::Exact { samples } => Some(samples.to_vec()),
+            BinaryScores::Binned { .. } => None,
+        }
+    }
+
+    /// One point per distinct score (or bin), ordered by decreasing threshold.
+    pub fn curve(&self) -> Vec<CurvePoint> {
+        let mut points = Vec::new();
+        let mut tp = 0.0;
+        let mut fp = 0.0;
+        match self {
+            BinaryScores::Exact { samples } => {
+                let mut sorted = samples.to_vec();
+                sorted.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+                let mut idx = 0;
+                while idx < sorted.len() {
+                    let current_score = sorted[idx].0;
+                    while idx < sorted.len() && sorted[idx].0 == current_score {
+                        if sorted[idx].1 {
+                            tp += 1.0;
+                        } else {
+                            fp += 1.0;
+                        }
+                        idx += 1;
+                    }
+                    points.push(CurvePoint {
+                        threshold: current_score,
+                        true_positive: tp,
+                        false_positive: fp,
+                    });
+                }
+            }
+            BinaryScores::Binned {
+                bins,
+                pos_hist,
+                neg_hist,
+            } => {
+                let max_bin_idx = (*bins - 1) as f64;
+                for (idx, (p, n)) in pos_hist.iter().zip(neg_hist.iter()).enumerate().rev() {
+                    tp += *p as f64;
+                    fp += *n as f64;
+                    points.push(CurvePoint {
+                        threshold: idx as f64 / max_bin_idx,
+                        true_positive: tp,
+                        false_positive: fp,
+                    });
+                }
+            }
+        }
+        points
+    }
+
+    /// `(fpr, tpr, thresholds)` starting from `(0, 0)` at an infinite threshold; `None` unless
+    /// both classes were observed.
+    pub fn roc(&self) -> Option<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+        let (total_pos, total_neg) = self.totals();
+        if total_pos == 0.0 || total_neg == 0.0 {
+            return None;
+        }
+
+        let curve = self.curve();
+        let mut fpr = Vec::with_capacity(curve.len() + 1);
+        let mut tpr = Vec::with_capacity(curve.len() + 1);
+        let mut thresholds = Vec::with_capacity(curve.len() + 1);
+        fpr.push(0.0);
+        tpr.push(0.0);
+        thresholds.push(f64::INFINITY);
+        for point in curve {
+            fpr.push(point.false_positive / total_neg);
+            tpr.push(point.true_positive / total_pos);
+            thresholds.push(point.threshold);
+        }
+        Some((fpr, tpr, thresholds))
+    }
+
+    /// Area under the ROC curve; `None` unless both classes were observed.
+    pub fn auroc(&self) -> Option<f64> {
+        let (total_pos, total_neg) = self.totals();
+        if total_pos == 0.0 || total_neg == 0.0 {
+            return None;
+        }
+
+        let mut prev_tp = 0.0;
+        let mut prev_fp = 0.0;
+        let mut auc = 0.0;
+        for point in self.curve() {
+            auc += (point.false_positive - prev_fp) * (point.true_positive + prev_tp) / 2.0;
+            prev_tp = point.true_positive;
+            prev_fp = point.false_positive;
+        }
+        Some(auc / (total_pos * total_neg))
+    }
+
+    /// McClish-standardized area under the ROC curve up to `max_fpr`, interpolating the curve
+    /// linearly at the ceiling; `None` unless both classes were observed.
+    pub fn partial_auroc(&self, max_fpr: f64) -> Option<f64> {
+        let (fpr, tpr, _) = self.roc()?;
+        let stop = fpr.partition_point(|&x| x <= max_fpr);
+        if stop == fpr.len() {
+            return self.auroc();
+        }
+
+        let mut area = 0.0;
+        for idx in 1..stop {
+            area += (fpr[idx] - fpr[idx - 1]) * (tpr[idx] + tpr[idx - 1]) / 2.0;
+        }
+        let (x0, x1) = (fpr[stop - 1], fpr[stop]);
+        let (y0, y1) = (tpr[stop - 1], tpr[stop]);
+        let tpr_at_ceiling = y0 + (y1 - y0) * (max_fpr - x0) / (x1 - x0);
+        area += (max_fpr - x0) * (tpr_at_ceiling + y0) / 2.0;
+
+        let min_area = max_fpr * max_fpr / 2.0;
+        Some(0.5 * (1.0 + (area - min_area) / (max_fpr - min_area)))
+    }
+
+    /// Area under the precision-recall-gain curve (Flach & Kull, 2015); `None` unless both
+    /// classes were observed.
+    ///
+    /// Only the part of the curve with non-negative recall gain contributes; the point where
+    /// recall gain crosses zero is interpolated linearly between neighbouring thresholds.
+    pub fn auprg(&self) -> Option<f64> {
+        let (total_pos, total_neg) = self.totals();
+        if total_pos == 0.0 || total_neg == 0.0 {
+            return None;
+        }
+
+        let ratio = total_pos / total_neg;
+        let gains = |tp: f64, fp: f64| {
+            let recall_gain = 1.0 - ratio * (total_pos - tp) / tp;
+            let precision_gain = 1.0 - ratio * fp / tp;
+            (recall_gain, precision_gain)
+        };
+        let crossing_tp = total_pos * total_pos / (total_pos + total_neg);
+
+        let mut prev_tp = 0.0;
+        let mut prev_fp = 0.0;
+        let mut prev_gains: Option<(f64, f64)> = None;
+        let mut area = 0.0;
+        for point in self.curve() {
+            let (tp, fp) = (point.true_positive, point.false_positive);
+            if prev_gains.is_none() && tp >= crossing_tp {
+                let fp_at_crossing =
+                    prev_fp + (fp - prev_fp) * (crossing_tp - prev_tp) / (tp - prev_tp);
+                prev_gains = Some(gains(crossing_tp, fp_at_crossing));
+            }
+            if let Some((prev_recall_gain, prev_precision_gain)) = prev_gains {
+                let (recall_gain, precision_gain) = gains(tp, fp);
+                area +=
+                    (recall_gain - prev_recall_gain) * (precision_gain + prev_precision_gain) / 2.0;
+                prev_gains = Some((recall_gain, precision_gain));
+            }
+            prev_tp = tp;
+            prev_fp = fp;
+        }
+        Some(area)
+    }
+
+    /// Average precision, `sum_n (R_n - R_{n-1}) P_n`; `None` without positive samples.
+    pub fn average_precision(&self) -> Option<f64> {
+        let (total_pos, _) = self.totals();
+        if total_pos == 0.0 {
+            return None;
+        }
+
+        let mut prev_recall = 0.0;
+        let mut ap = 0.0;
+        for point in self.curve() {
+            let predicted = point.true_positive + point.false_positive;
+            if predicted == 0.0 {
+                continue;
+            }
+            let recall = point.true_positive / total_pos;
+            ap += (recall - prev_recall) * point.true_positive / predicted;
+            prev_recall = recall;
+        }
+        Some(ap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryScores;
+
+    #[test]
+    fn exact_curve_groups_ties() {
+        let mut scores = BinaryScores::new(0);
+        scores.update(&[0.1, 0.4, 0.4, 0.8], &[0, 1, 0, 1]).unwrap();
+        let curve = scores.curve();
+        let thresholds: Vec<f64> = curve.iter().map(|p| p.threshold).collect();
+        let tps: Vec<f64> = curve.iter().map(|p| p.true_positive).collect();
+        let fps: Vec<f64> = curve.iter().map(|p| p.false_positive).collect();
+        assert_eq!(thresholds, vec![0.8, 0.4, 0.1]);
+        assert_eq!(tps, vec![1.0, 2.0, 2.0]);
+        assert_eq!(fps, vec![0.0, 1.0, 2.0]);
+
+        assert!((scores.average_precision().unwrap() - 0.8333333333333333).abs() < 1e-12);
+    }
+}
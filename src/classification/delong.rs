@@ -0,0 +1,255 @@
+use std::cmp::Ordering;
+
+use crate::core::MetricError;
+use crate::utils::{normal_cdf, normal_quantile, verify_binary_label, verify_range};
+
+/// Exact AUROC together with its DeLong variance estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AurocVariance {
+    pub auroc: f64,
+    pub variance: f64,
+}
+
+impl AurocVariance {
+    pub fn standard_error(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// Two-sided normal confidence interval at `level` (e.g. `0.95`), clipped to `[0, 1]`.
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        let z = normal_quantile(0.5 + level / 2.0);
+        let half_width = z * self.standard_error();
+        (
+            (self.auroc - half_width).max(0.0),
+            (self.auroc + half_width).min(1.0),
+        )
+    }
+}
+
+/// Result of comparing two correlated AUROCs measured on the same samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeLongTest {
+    pub auroc_a: f64,
+    pub auroc_b: f64,
+    pub z: f64,
+    /// Two-sided p-value for the null hypothesis that both AUROCs are equal.
+    pub p_value: f64,
+}
+
+/// DeLong's nonparametric variance of the exact AUROC and the paired comparison test, computed
+/// with the midrank formulation of Sun & Xu (2014).
+///
+/// Both functions need at least two positive and two negative samples.
+///
+/// ```
+/// use rust_metrics::DeLong;
+///
+/// let target = [1_usize, 1, 1, 1, 0, 0, 0, 0, 0, 1];
+/// let model_a = [0.9, 0.8, 0.35, 0.6, 0.1, 0.4, 0.3, 0.55, 0.2, 0.7];
+/// let model_b = [0.7, 0.9, 0.5, 0.3, 0.2, 0.6, 0.1, 0.4, 0.45, 0.8];
+///
+/// let estimate = DeLong::variance(&model_a, &target).unwrap();
+/// assert!((estimate.auroc - 0.92).abs() < 1e-12);
+/// let (lower, upper) = estimate.confidence_interval(0.95);
+/// assert!((lower - 0.736139).abs() < 1e-6 && upper == 1.0);
+///
+/// let test = DeLong::compare(&model_a, &model_b, &target).unwrap();
+/// assert!((test.p_value - 0.5791).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DeLong;
+
+impl DeLong {
+    pub fn variance(predictions: &[f64], targets: &[usize]) -> Result<AurocVariance, MetricError> {
+        let labels = verify_inputs(predictions, targets)?;
+        let components = StructuralComponents::new(predictions, &labels);
+        Ok(AurocVariance {
+            auroc: components.auroc(),
+            variance: components.covariance(&components),
+        })
+    }
+
+    pub fn compare(
+        predictions_a: &[f64],
+        predictions_b: &[f64],
+        targets: &[usize],
+    ) -> Result<DeLongTest, MetricError> {
+        let labels = verify_inputs(predictions_a, targets)?;
+        verify_inputs(predictions_b, targets)?;
+        let a = StructuralComponents::new(predictions_a, &labels);
+        let b = StructuralComponents::new(predictions_b, &labels);
+
+        let auroc_a = a.auroc();
+        let auroc_b = b.auroc();
+        let difference = auroc_a - auroc_b;
+        let variance = a.covariance(&a) + b.covariance(&b) - 2.0 * a.covariance(&b);
+        let z = if variance > 0.0 {
+            difference / variance.sqrt()
+        } else if difference == 0.0 {
+            0.0
+        } else {
+            difference.signum() * f64::INFINITY
+        };
+        Ok(DeLongTest {
+            auroc_a,
+            auroc_b,
+            z,
+            p_value: 2.0 * (1.0 - normal_cdf(z.abs())),
+        })
+    }
+
+    /// DeLong estimate from already validated `(score, is_positive)` samples.
+    pub(crate) fn from_samples(samples: &[(f64, bool)]) -> Option<AurocVariance> {
+        let (predictions, labels): (Vec<f64>, Vec<bool>) = samples.iter().copied().unzip();
+        let positives = labels.iter().filter(|&&label| label).count();
+        if positives < 2 || labels.len() - positives < 2 {
+            return None;
+        }
+        let components = StructuralComponents::new(&predictions, &labels);
+        Some(AurocVariance {
+            auroc: components.auroc(),
+            variance: components.covariance(&components),
+        })
+    }
+}
+
+fn verify_inputs(predictions: &[f64], targets: &[usize]) -> Result<Vec<bool>, MetricError> {
+    if predictions.len() != targets.len() {
+        return Err(MetricError::LengthMismatch {
+            predictions: predictions.len(),
+            targets: targets.len(),
+        });
+    }
+    let mut labels = Vec::with_capacity(targets.len());
+    for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+        verify_range(prediction, 0.0, 1.0)?;
+        verify_binary_label(target)?;
+        labels.push(target == 1);
+    }
+    let positives = labels.iter().filter(|&&label| label).count();
+    let negatives = labels.len() - positives;
+    if positives < 2 || negatives < 2 {
+        return Err(MetricError::IncompatibleInput {
+            expected: "at least two positive and two negative samples".to_string(),
+            got: format!("{} positive and {} negative samples", positives, negatives),
+        });
+    }
+    Ok(labels)
+}
+
+/// Per-sample placement values: `v10[i]` is the fraction of negatives ranked below positive `i`,
+/// `v01[j]` the fraction of positives ranked above negative `j` (ties count one half).
+struct StructuralComponents {
+    v10: Vec<f64>,
+    v01: Vec<f64>,
+}
+
+impl StructuralComponents {
+    fn new(predictions: &[f64], labels: &[bool]) -> Self {
+        let positives: Vec<f64> = predictions
+            .iter()
+            .zip(labels)
+            .filter(|(_, label)| **label)
+            .map(|(prediction, _)| *prediction)
+            .collect();
+        let negatives: Vec<f64> = predictions
+            .iter()
+            .zip(labels)
+            .filter(|(_, label)| !**label)
+            .map(|(prediction, _)| *prediction)
+            .collect();
+        let m = positives.len();
+        let n = negatives.len();
+
+        let combined: Vec<f64> = positives.iter().chain(negatives.iter()).copied().collect();
+        let combined_ranks = midranks(&combined);
+        let positive_ranks = midranks(&positives);
+        let negative_ranks = midranks(&negatives);
+
+        let v10 = (0..m)
+            .map(|i| (combined_ranks[i] - positive_ranks[i]) / n as f64)
+            .collect();
+        let v01 = (0..n)
+            .map(|j| 1.0 - (combined_ranks[m + j] - negative_ranks[j]) / m as f64)
+            .collect();
+        Self { v10, v01 }
+    }
+
+    fn auroc(&self) -> f64 {
+        self.v10.iter().sum::<f64>() / self.v10.len() as f64
+    }
+
+    fn covariance(&self, other: &StructuralComponents) -> f64 {
+        sample_covariance(&self.v10, &other.v10) / self.v10.len() as f64
+            + sample_covariance(&self.v01, &other.v01) / self.v01.len() as f64
+    }
+}
+
+fn midranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap_or(Ordering::Equal));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end) as f64 / 2.0 + 1.0;
+        for &idx in &order[start..=end] {
+            ranks[idx] = rank;
+        }
+        start = end + 1;
+    }
+    ranks
+}
+
+fn sample_covariance(a: &[f64], b: &[f64]) -> f64 {
+    let mean_a = a.iter().sum::<f64>() / a.len() as f64;
+    let mean_b = b.iter().sum::<f64>() / b.len() as f64;
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - mean_a) * (y - mean_b))
+        .sum::<f64>()
+        / (a.len() - 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeLong;
+    use crate::core::Metric;
+    use crate::{BinaryAuroc, MetricError};
+
+    #[test]
+    fn delong_variance_and_comparison() {
+        let target = [1, 1, 1, 1, 0, 0, 0, 0, 0, 1];
+        let model_a = [0.9, 0.8, 0.35, 0.6, 0.1, 0.4, 0.3, 0.55, 0.2, 0.7];
+        let model_b = [0.7, 0.9, 0.5, 0.3, 0.2, 0.6, 0.1, 0.4, 0.45, 0.8];
+
+        let a = DeLong::variance(&model_a, &target).unwrap();
+        assert!((a.variance - 0.0088).abs() < 1e-12);
+        let b = DeLong::variance(&model_b, &target).unwrap();
+        assert!((b.auroc - 0.84).abs() < 1e-12);
+        assert!((b.variance - 0.0192).abs() < 1e-12);
+
+        let test = DeLong::compare(&model_a, &model_b, &target).unwrap();
+        assert!((test.z - 0.554_700_196_225_229_7).abs() < 1e-12);
+        assert!((test.p_value - 0.579_099_741_953_918_6).abs() < 1e-6);
+
+        let same = DeLong::compare(&model_a, &model_a, &target).unwrap();
+        assert_eq!(same.z, 0.0);
+        assert!((same.p_value - 1.0).abs() < 1e-6);
+
+        let mut auroc = BinaryAuroc::new(0);
+        auroc.update((&model_a, &target)).unwrap();
+        assert_eq!(auroc.delong(), Some(a));
+        assert_eq!(BinaryAuroc::new(10).delong(), None);
+    }
+
+    #[test]
+    fn delong_requires_both_classes() {
+        let result = DeLong::variance(&[0.2, 0.4, 0.9], &[0, 1, 1]);
+        assert!(matches!(result, Err(MetricError::IncompatibleInput { .. })));
+    }
+}
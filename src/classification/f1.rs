@@ -1,7 +1,10 @@
-use crate::core::{Metric, MetricError};
-use crate::utils::AverageMethod;
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{AverageMethod, InputTransform};
 
-use super::stat_scores::{BinaryStatScores, MulticlassStatScores};
+use super::stat_scores::{
+    BinaryStatScores, MulticlassStatScores, MultilabelStatScores, verify_class_weights,
+    weighted_class_mean,
+};
 
 /// Binary F1 Score, computed as the harmonic mean of precision and recall.
 ///
@@ -26,6 +29,27 @@ impl BinaryF1Score {
         let stat_scores = BinaryStatScores::new(threshold);
         Self { stat_scores }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+
+    /// Score already thresholded `0`/`1` predictions, see [`BinaryStatScores::update_labels`].
+    pub fn update_labels(
+        &mut self,
+        predictions: &[usize],
+        targets: &[usize],
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update_labels(predictions, targets)
+    }
 }
 
 impl Metric<(&[f64], &[usize])> for BinaryF1Score {
@@ -53,6 +77,12 @@ impl Metric<(&[f64], &[usize])> for BinaryF1Score {
     }
 }
 
+impl Revertible<(&[f64], &[usize])> for BinaryF1Score {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 /// Compute F-1 score for multiclass tasks.
 ///
 ///
@@ -77,6 +107,7 @@ impl Metric<(&[f64], &[usize])> for BinaryF1Score {
 pub struct MulticlassF1Score {
     stat_scores: MulticlassStatScores,
     average_method: AverageMethod,
+    class_weights: Option<Vec<f64>>,
 }
 
 impl MulticlassF1Score {
@@ -85,8 +116,31 @@ impl MulticlassF1Score {
         Self {
             stat_scores,
             average_method,
+            class_weights: None,
         }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`MulticlassStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Average per-class values with explicit class weights (e.g. business costs) instead of
+    /// the configured [`AverageMethod`].
+    pub fn with_class_weights(mut self, weights: Vec<f64>) -> Self {
+        verify_class_weights(&weights, self.stat_scores.num_classes);
+        self.class_weights = Some(weights);
+        self
+    }
+
+    /// F1 score `2 TP / (2 TP + FP + FN)` of every class.
+    ///
+    /// Classes absent from both predictions and targets report `0.0`.
+    pub fn compute_per_class(&self) -> Option<Vec<f64>> {
+        self.stat_scores
+            .per_class(|tp, fp, fn_, _| (2 * tp, 2 * tp + fp + fn_))
+    }
 }
 
 impl Metric<(&[&[f64]], &[usize])> for MulticlassF1Score {
@@ -103,6 +157,11 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassF1Score {
     }
 
     fn compute(&self) -> Option<Self::Output> {
+        if let Some(weights) = &self.class_weights {
+            return self
+                .compute_per_class()
+                .map(|values| weighted_class_mean(&values, weights));
+        }
         if self.stat_scores.total == 0 {
             return None;
         }
@@ -167,9 +226,83 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassF1Score {
     }
 }
 
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassF1Score {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// Multilabel F1 score over independently thresholded label columns.
+///
+/// Labels are scored with `2 TP / (2 TP + FP + FN)`. `Micro` pools counts, `Macro` averages labels
+/// that were predicted or present, and `Weighted` weights them by support. The counts live in a
+/// [`MultilabelStatScores`], which also yields precision and recall from the same accumulation.
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelF1Score};
+/// use rust_metrics::utils::AverageMethod;
+///
+/// let preds: [&[f64]; 3] = [&[0.9, 0.6, 0.1], &[0.8, 0.3, 0.7], &[0.2, 0.4, 0.8]];
+/// let targets: [&[usize]; 3] = [&[1, 0, 0], &[1, 1, 1], &[0, 1, 1]];
+///
+/// let mut metric = MultilabelF1Score::new(3, 0.5, AverageMethod::Micro);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 8.0 / 11.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelF1Score {
+    stat_scores: MultilabelStatScores,
+    average_method: AverageMethod,
+}
+
+impl MultilabelF1Score {
+    pub fn new(num_labels: usize, threshold: f64, average_method: AverageMethod) -> Self {
+        Self {
+            stat_scores: MultilabelStatScores::new(num_labels, threshold),
+            average_method,
+        }
+    }
+
+    /// Use a separate decision threshold for each label.
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        self.stat_scores = self.stat_scores.with_thresholds(thresholds);
+        self
+    }
+
+    /// Accumulated per-label counts, for reading precision and recall alongside F1.
+    pub fn stat_scores(&self) -> &MultilabelStatScores {
+        &self.stat_scores
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelF1Score {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.stat_scores.f1_score(&self.average_method)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[usize]])> for MultilabelF1Score {
+    fn revert(&mut self, input: (&[&[f64]], &[&[usize]])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{AverageMethod, BinaryF1Score, MulticlassF1Score};
+    use super::{AverageMethod, BinaryF1Score, MulticlassF1Score, MultilabelF1Score};
     use crate::core::Metric;
 
     #[test]
@@ -201,6 +334,35 @@ mod tests {
         let result = metric.compute().unwrap();
         assert!((result - 0.7777777777777777).abs() < f64::EPSILON);
 
+        let per_class = metric.compute_per_class().unwrap();
+        assert!((per_class[0] - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(per_class[2], 1.0);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn f1_multilabel_matches_precision_and_recall() {
+        let preds: [&[f64]; 3] = [
+            &[0.9, 0.6, 0.1][..],
+            &[0.8, 0.3, 0.7][..],
+            &[0.2, 0.4, 0.8][..],
+        ];
+        let targets: [&[usize]; 3] = [&[1, 0, 0][..], &[1, 1, 1][..], &[0, 1, 1][..]];
+
+        let mut metric = MultilabelF1Score::new(3, 0.5, AverageMethod::Macro);
+        metric.update((&preds[..2], &targets[..2])).unwrap();
+        metric.update((&preds[2..], &targets[2..])).unwrap();
+        // per-label F1: [1.0, 0.0, 1.0]
+        assert!((metric.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+
+        let stat_scores = metric.stat_scores();
+        let precision = stat_scores.precision(&AverageMethod::Micro).unwrap();
+        let recall = stat_scores.recall(&AverageMethod::Micro).unwrap();
+        let micro = stat_scores.f1_score(&AverageMethod::Micro).unwrap();
+        assert!((micro - 2.0 * precision * recall / (precision + recall)).abs() < 1e-12);
+
         metric.reset();
         assert_eq!(metric.compute(), None);
     }
@@ -0,0 +1,286 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{verify_label, verify_reverted_count};
+
+/// Overlap of predicted and target ancestor sets, the state behind the hierarchical metrics.
+///
+/// Every class is augmented with all of its ancestors, so predicting a sibling of the target
+/// still earns credit for the shared parents (Kiritchenko et al., 2005).
+#[derive(Debug, Clone)]
+struct HierarchicalCounts {
+    /// Each class followed by its ancestors up to the root.
+    ancestors: Vec<Vec<usize>>,
+    intersection: usize,
+    predicted: usize,
+    actual: usize,
+    total: usize,
+}
+
+impl HierarchicalCounts {
+    fn new(parents: &[Option<usize>]) -> Self {
+        let num_classes = parents.len();
+        assert!(
+            num_classes >= 2,
+            "the hierarchy must contain at least 2 classes"
+        );
+        let ancestors = (0..num_classes)
+            .map(|class| {
+                let mut chain = vec![class];
+                let mut current = class;
+                while let Some(parent) = parents[current] {
+                    assert!(parent < num_classes, "parent {parent} is not a class");
+                    assert!(
+                        chain.len() < num_classes,
+                        "the class hierarchy must not contain cycles"
+                    );
+                    chain.push(parent);
+                    current = parent;
+                }
+                chain
+            })
+            .collect();
+        Self {
+            ancestors,
+            intersection: 0,
+            predicted: 0,
+            actual: 0,
+            total: 0,
+        }
+    }
+
+    fn update(&mut self, predictions: &[usize], targets: &[usize]) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            verify_label(prediction, self.ancestors.len())?;
+            verify_label(target, self.ancestors.len())?;
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            let predicted = &self.ancestors[prediction];
+            let actual = &self.ancestors[target];
+            self.intersection += predicted.iter().filter(|c| actual.contains(c)).count();
+            self.predicted += predicted.len();
+            self.actual += actual.len();
+        }
+        self.total += predictions.len();
+        Ok(())
+    }
+
+    fn revert(&mut self, predictions: &[usize], targets: &[usize]) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(predictions, targets)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.intersection -= batch.intersection;
+        self.predicted -= batch.predicted;
+        self.actual -= batch.actual;
+        self.total -= batch.total;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.intersection = 0;
+        self.predicted = 0;
+        self.actual = 0;
+        self.total = 0;
+    }
+
+    fn precision(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.intersection as f64 / self.predicted as f64)
+    }
+
+    fn recall(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.intersection as f64 / self.actual as f64)
+    }
+}
+
+/// Hierarchical precision: the share of predicted classes and their ancestors that are also the
+/// target class or one of its ancestors.
+///
+/// ```
+/// use rust_metrics::{HierarchicalPrecision, Metric};
+///
+/// // 0 animal, 1 mammal, 2 dog, 3 cat, 4 bird
+/// let parents = [None, Some(0), Some(1), Some(1), Some(0)];
+///
+/// let mut metric = HierarchicalPrecision::new(&parents);
+/// // dog vs cat shares {mammal, animal}; bird vs dog shares {animal}
+/// metric.update((&[2, 4], &[3, 2])).unwrap();
+/// assert!((metric.compute().unwrap() - 3.0 / 5.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HierarchicalPrecision {
+    counts: HierarchicalCounts,
+}
+
+impl HierarchicalPrecision {
+    /// `parents[c]` is the parent class of `c`, or `None` for top-level classes.
+    pub fn new(parents: &[Option<usize>]) -> Self {
+        Self {
+            counts: HierarchicalCounts::new(parents),
+        }
+    }
+}
+
+impl Metric<(&[usize], &[usize])> for HierarchicalPrecision {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        self.counts.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.counts.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.counts.precision()
+    }
+}
+
+impl Revertible<(&[usize], &[usize])> for HierarchicalPrecision {
+    fn revert(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        self.counts.revert(predictions, targets)
+    }
+}
+
+/// Hierarchical recall: the share of target classes and their ancestors that were also predicted.
+///
+/// ```
+/// use rust_metrics::{HierarchicalRecall, Metric};
+///
+/// let parents = [None, Some(0), Some(1), Some(1), Some(0)];
+///
+/// let mut metric = HierarchicalRecall::new(&parents);
+/// metric.update((&[2, 4], &[3, 2])).unwrap();
+/// assert!((metric.compute().unwrap() - 3.0 / 6.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HierarchicalRecall {
+    counts: HierarchicalCounts,
+}
+
+impl HierarchicalRecall {
+    /// `parents[c]` is the parent class of `c`, or `None` for top-level classes.
+    pub fn new(parents: &[Option<usize>]) -> Self {
+        Self {
+            counts: HierarchicalCounts::new(parents),
+        }
+    }
+}
+
+impl Metric<(&[usize], &[usize])> for HierarchicalRecall {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        self.counts.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.counts.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.counts.recall()
+    }
+}
+
+impl Revertible<(&[usize], &[usize])> for HierarchicalRecall {
+    fn revert(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        self.counts.revert(predictions, targets)
+    }
+}
+
+/// Harmonic mean of [`HierarchicalPrecision`] and [`HierarchicalRecall`].
+///
+/// ```
+/// use rust_metrics::{HierarchicalF1Score, Metric};
+///
+/// let parents = [None, Some(0), Some(1), Some(1), Some(0)];
+///
+/// let mut metric = HierarchicalF1Score::new(&parents);
+/// metric.update((&[2, 4], &[3, 2])).unwrap();
+/// assert!((metric.compute().unwrap() - 6.0 / 11.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HierarchicalF1Score {
+    counts: HierarchicalCounts,
+}
+
+impl HierarchicalF1Score {
+    /// `parents[c]` is the parent class of `c`, or `None` for top-level classes.
+    pub fn new(parents: &[Option<usize>]) -> Self {
+        Self {
+            counts: HierarchicalCounts::new(parents),
+        }
+    }
+}
+
+impl Metric<(&[usize], &[usize])> for HierarchicalF1Score {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        self.counts.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.counts.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let precision = self.counts.precision()?;
+        let recall = self.counts.recall()?;
+        if precision + recall == 0.0 {
+            return Some(0.0);
+        }
+        Some(2.0 * precision * recall / (precision + recall))
+    }
+}
+
+impl Revertible<(&[usize], &[usize])> for HierarchicalF1Score {
+    fn revert(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        self.counts.revert(predictions, targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HierarchicalF1Score, HierarchicalPrecision, HierarchicalRecall};
+    use crate::core::{Metric, Revertible};
+
+    const PARENTS: [Option<usize>; 5] = [None, Some(0), Some(1), Some(1), Some(0)];
+
+    #[test]
+    fn hierarchical_metrics_credit_shared_ancestors() {
+        let mut precision = HierarchicalPrecision::new(&PARENTS);
+        let mut recall = HierarchicalRecall::new(&PARENTS);
+        let mut f1 = HierarchicalF1Score::new(&PARENTS);
+
+        // exact match on a leaf, then a prediction that stops at the parent
+        let preds = [3, 1];
+        let target = [3, 2];
+        precision.update((&preds, &target)).unwrap();
+        recall.update((&preds, &target)).unwrap();
+        f1.update((&preds, &target)).unwrap();
+        assert_eq!(precision.compute(), Some(1.0));
+        assert!((recall.compute().unwrap() - 5.0 / 6.0).abs() < 1e-12);
+        assert!((f1.compute().unwrap() - 10.0 / 11.0).abs() < 1e-12);
+
+        precision.update((&[4], &[3])).unwrap();
+        precision.revert((&[4], &[3])).unwrap();
+        assert_eq!(precision.compute(), Some(1.0));
+        assert!(precision.update((&[5], &[0])).is_err());
+
+        precision.reset();
+        assert_eq!(precision.compute(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycles")]
+    fn hierarchy_rejects_cycles() {
+        HierarchicalPrecision::new(&[Some(1), Some(0)]);
+    }
+}
@@ -1,8 +1,37 @@
-use crate::core::{Metric, MetricError};
-use crate::utils::{verify_binary_label, verify_label, verify_range};
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{verify_binary_label, verify_label, verify_range, verify_reverted_count};
+
+/// How hinge-loss predictions are interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScoreKind {
+    /// Scores must lie in `[0, 1]`.
+    #[default]
+    Probability,
+    /// Unbounded decision-function margins; any finite value is accepted.
+    Margin,
+}
+
+impl ScoreKind {
+    fn verify(self, score: f64) -> Result<(), MetricError> {
+        match self {
+            ScoreKind::Probability => verify_range(score, 0.0, 1.0),
+            ScoreKind::Margin => verify_range(score, f64::MIN, f64::MAX),
+        }
+    }
+}
+
+fn verify_margin(margin: f64) {
+    assert!(
+        margin.is_finite() && margin > 0.0,
+        "margin must be a positive finite number"
+    );
+}
 
 /// Online hinge loss for binary classification.
 ///
+/// Targets are mapped to `-1`/`+1`. Predictions are probabilities by default; use
+/// [`BinaryHingeLoss::with_score_kind`] to pass raw margins instead and
+/// [`BinaryHingeLoss::with_margin`] to change the margin from `1.0`.
 ///
 /// ```
 /// use rust_metrics::{BinaryHingeLoss, Metric};
@@ -17,6 +46,8 @@ use crate::utils::{verify_binary_label, verify_label, verify_range};
 #[derive(Debug, Clone)]
 pub struct BinaryHingeLoss {
     squared: bool,
+    score_kind: ScoreKind,
+    margin: f64,
     measures: f64,
     total: usize,
 }
@@ -31,10 +62,24 @@ impl BinaryHingeLoss {
     pub fn new(squared: bool) -> Self {
         Self {
             squared,
+            score_kind: ScoreKind::default(),
+            margin: 1.0,
             measures: 0.0,
             total: 0,
         }
     }
+
+    pub fn with_score_kind(mut self, score_kind: ScoreKind) -> Self {
+        self.score_kind = score_kind;
+        self
+    }
+
+    /// Replace the default margin of `1.0` in `max(0, margin - y * s)`.
+    pub fn with_margin(mut self, margin: f64) -> Self {
+        verify_margin(margin);
+        self.margin = margin;
+        self
+    }
 }
 
 impl Metric<(&[f64], &[usize])> for BinaryHingeLoss {
@@ -49,11 +94,11 @@ impl Metric<(&[f64], &[usize])> for BinaryHingeLoss {
         }
         self.total += predictions.len();
         for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
-            verify_range(prediction, 0.0, 1.0)?;
+            self.score_kind.verify(prediction)?;
             verify_binary_label(target)?;
 
             let y = if target == 1 { 1.0 } else { -1.0 };
-            let mut measure = (1.0 - prediction * y).max(0.0);
+            let mut measure = (self.margin - prediction * y).max(0.0);
             if self.squared {
                 measure *= measure;
             }
@@ -76,11 +121,37 @@ impl Metric<(&[f64], &[usize])> for BinaryHingeLoss {
     }
 }
 
-/// Online hinge loss for multiclass classification. Currently only the Crammer-Singer loss is supported
+impl Revertible<(&[f64], &[usize])> for BinaryHingeLoss {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.measures -= batch.measures;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+/// Multiclass hinge loss formulation, mirroring torchmetrics' `multiclass_mode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MulticlassHingeMode {
+    /// `max(0, 1 - s_y + max_{k != y} s_k)` per sample.
+    #[default]
+    CrammerSinger,
+    /// A binary hinge loss per class against the one-hot target, averaged over classes.
+    OneVsAll,
+}
+
+/// Online hinge loss for multiclass classification.
 ///
+/// Uses the Crammer-Singer loss by default; [`MulticlassHingeLoss::with_mode`] switches to the
+/// one-vs-all formulation, whose per-class losses are available from
+/// [`MulticlassHingeLoss::compute_per_class`]. Raw margins are accepted after
+/// [`MulticlassHingeLoss::with_score_kind`] with [`ScoreKind::Margin`].
 ///
 /// ```
-/// use rust_metrics::{MulticlassHingeLoss, Metric};
+/// use rust_metrics::{MulticlassHingeLoss, MulticlassHingeMode, Metric};
 ///
 /// let mut hinge = MulticlassHingeLoss::new(3, false);
 /// let preds: [&[f64]; 4] = [
@@ -99,12 +170,23 @@ impl Metric<(&[f64], &[usize])> for BinaryHingeLoss {
 /// let mut hinge = MulticlassHingeLoss::new(3, true);
 /// hinge.update((&preds, &target)).unwrap();
 /// assert!((hinge.compute().unwrap() - 1.1131250000000001).abs() < 1e-12);
+///
+/// let mut hinge = MulticlassHingeLoss::new(3, false).with_mode(MulticlassHingeMode::OneVsAll);
+/// hinge.update((&preds, &target)).unwrap();
+/// let per_class = hinge.compute_per_class().unwrap();
+/// assert!((per_class[0] - 0.875).abs() < 1e-12);
+/// assert!((per_class[1] - 1.125).abs() < 1e-12);
+/// assert!((per_class[2] - 1.1).abs() < 1e-12);
 /// ```
 #[derive(Debug, Clone)]
 pub struct MulticlassHingeLoss {
     num_classes: usize,
     squared: bool,
+    mode: MulticlassHingeMode,
+    score_kind: ScoreKind,
+    margin: f64,
     measures: f64,
+    class_measures: Vec<f64>,
     total: usize,
 }
 
@@ -114,10 +196,49 @@ impl MulticlassHingeLoss {
         Self {
             num_classes,
             squared,
+            mode: MulticlassHingeMode::default(),
+            score_kind: ScoreKind::default(),
+            margin: 1.0,
             measures: 0.0,
+            class_measures: vec![0.0; num_classes],
             total: 0,
         }
     }
+
+    pub fn with_mode(mut self, mode: MulticlassHingeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_score_kind(mut self, score_kind: ScoreKind) -> Self {
+        self.score_kind = score_kind;
+        self
+    }
+
+    /// Replace the default margin of `1.0` in `max(0, margin - y * s)`.
+    pub fn with_margin(mut self, margin: f64) -> Self {
+        verify_margin(margin);
+        self.margin = margin;
+        self
+    }
+
+    /// Mean loss of every class in one-vs-all mode; `None` for Crammer-Singer or before any
+    /// update.
+    pub fn compute_per_class(&self) -> Option<Vec<f64>> {
+        if self.total == 0 || self.mode != MulticlassHingeMode::OneVsAll {
+            return None;
+        }
+        Some(
+            self.class_measures
+                .iter()
+                .map(|measure| measure / self.total as f64)
+                .collect(),
+        )
+    }
+
+    fn squash(&self, loss: f64) -> f64 {
+        if self.squared { loss * loss } else { loss }
+    }
 }
 
 impl Metric<(&[&[f64]], &[usize])> for MulticlassHingeLoss {
@@ -139,20 +260,32 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassHingeLoss {
             }
 
             verify_label(target, self.num_classes)?;
-            let true_score = prediction_batch[target];
-            let mut max_other_score: f64 = -1.0;
-            for (i, &prediction) in prediction_batch.iter().enumerate() {
-                verify_range(prediction, 0.0, 1.0)?;
-                if i == target {
-                    continue;
-                }
-                max_other_score = max_other_score.max(prediction);
+            for &prediction in prediction_batch {
+                self.score_kind.verify(prediction)?;
             }
-            let mut loss = (1.0 - true_score + max_other_score).max(0.0);
-            if self.squared {
-                loss *= loss;
+
+            match self.mode {
+                MulticlassHingeMode::CrammerSinger => {
+                    let true_score = prediction_batch[target];
+                    let mut max_other_score = f64::NEG_INFINITY;
+                    for (i, &prediction) in prediction_batch.iter().enumerate() {
+                        if i == target {
+                            continue;
+                        }
+                        max_other_score = max_other_score.max(prediction);
+                    }
+                    self.measures +=
+                        self.squash((self.margin - true_score + max_other_score).max(0.0));
+                }
+                MulticlassHingeMode::OneVsAll => {
+                    for (i, &prediction) in prediction_batch.iter().enumerate() {
+                        let y = if i == target { 1.0 } else { -1.0 };
+                        let loss = self.squash((self.margin - prediction * y).max(0.0));
+                        self.class_measures[i] += loss;
+                        self.measures += loss / self.num_classes as f64;
+                    }
+                }
             }
-            self.measures += loss;
             self.total += 1;
         }
 
@@ -161,6 +294,7 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassHingeLoss {
 
     fn reset(&mut self) {
         self.measures = 0.0;
+        self.class_measures.fill(0.0);
         self.total = 0;
     }
 
@@ -172,10 +306,25 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassHingeLoss {
     }
 }
 
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassHingeLoss {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.measures -= batch.measures;
+        for (measure, reverted) in self.class_measures.iter_mut().zip(batch.class_measures) {
+            *measure -= reverted;
+        }
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{BinaryHingeLoss, MulticlassHingeLoss};
-    use crate::core::Metric;
+    use super::{BinaryHingeLoss, MulticlassHingeLoss, MulticlassHingeMode, ScoreKind};
+    use crate::core::{Metric, Revertible};
 
     #[test]
     fn binary_hinge_computes_over_batches() {
@@ -219,4 +368,66 @@ mod tests {
         hinge.update((&preds, &target)).unwrap();
         assert!((hinge.compute().unwrap() - 1.1131250000000001).abs() < 1e-12);
     }
+
+    #[test]
+    fn multiclass_hinge_one_vs_all() {
+        let preds: [&[f64]; 4] = [
+            &[0.25, 0.20, 0.55][..],
+            &[0.55, 0.05, 0.40][..],
+            &[0.10, 0.30, 0.60][..],
+            &[0.90, 0.05, 0.05][..],
+        ];
+        let target = [0, 1, 2, 0];
+
+        let mut hinge = MulticlassHingeLoss::new(3, true).with_mode(MulticlassHingeMode::OneVsAll);
+        hinge.update((&preds, &target)).unwrap();
+        let per_class = hinge.compute_per_class().unwrap();
+        let expected = [1.04625, 1.28375, 1.40625];
+        for (value, expected) in per_class.iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+        let mean = expected.iter().sum::<f64>() / 3.0;
+        assert!((hinge.compute().unwrap() - mean).abs() < 1e-12);
+
+        hinge.reset();
+        assert_eq!(hinge.compute_per_class(), None);
+    }
+
+    #[test]
+    fn hinge_accepts_raw_margins() {
+        let mut binary = BinaryHingeLoss::default();
+        assert!(binary.update((&[-2.0], &[0])).is_err());
+
+        let mut binary = BinaryHingeLoss::default().with_score_kind(ScoreKind::Margin);
+        binary.update((&[-2.0, 0.5, 3.0], &[0, 1, 0])).unwrap();
+        // losses: max(0, 1 - 2) = 0, 1 - 0.5 = 0.5, 1 + 3 = 4
+        assert!((binary.compute().unwrap() - 1.5).abs() < 1e-12);
+        assert!(binary.update((&[f64::NAN], &[1])).is_err());
+
+        let preds: [&[f64]; 2] = [&[2.0, -1.5, 0.5][..], &[-3.0, -0.5, -2.0][..]];
+        let mut multiclass = MulticlassHingeLoss::new(3, false).with_score_kind(ScoreKind::Margin);
+        multiclass.update((&preds, &[0, 2])).unwrap();
+        // losses: max(0, 1 - 2 + 0.5) = 0, 1 + 2 - 0.5 = 2.5
+        assert!((multiclass.compute().unwrap() - 1.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hinge_with_custom_margin() {
+        let mut binary = BinaryHingeLoss::default()
+            .with_score_kind(ScoreKind::Margin)
+            .with_margin(2.0);
+        binary.update((&[-2.0, 0.5, 3.0], &[0, 1, 0])).unwrap();
+        // losses: max(0, 2 - 2) = 0, 2 - 0.5 = 1.5, 2 + 3 = 5
+        assert!((binary.compute().unwrap() - 6.5 / 3.0).abs() < 1e-12);
+        binary.revert((&[0.5], &[1])).unwrap();
+        assert!((binary.compute().unwrap() - 2.5).abs() < 1e-12);
+
+        let preds: [&[f64]; 2] = [&[2.0, -1.5, 0.5][..], &[-3.0, -0.5, -2.0][..]];
+        let mut multiclass = MulticlassHingeLoss::new(3, false)
+            .with_score_kind(ScoreKind::Margin)
+            .with_margin(0.5);
+        multiclass.update((&preds, &[0, 2])).unwrap();
+        // losses: max(0, 0.5 - 2 + 0.5) = 0, 0.5 + 2 - 0.5 = 2
+        assert!((multiclass.compute().unwrap() - 1.0).abs() < 1e-12);
+    }
 }
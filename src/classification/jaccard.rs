@@ -1,7 +1,9 @@
-use crate::core::{Metric, MetricError};
-use crate::utils::AverageMethod;
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{AverageMethod, InputTransform};
 
-use super::stat_scores::{BinaryStatScores, MulticlassStatScores};
+use super::stat_scores::{
+    BinaryStatScores, MulticlassStatScores, verify_class_weights, weighted_class_mean,
+};
 
 /// Calculate the Jaccard index for binary tasks.
 /// The `Jaccard index`_ (also known as the intersection over union or jaccard similarity coefficient) is an statistic
@@ -31,6 +33,18 @@ impl BinaryJaccardIndex {
         let stat_scores = BinaryStatScores::new(threshold);
         Self { stat_scores }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
 }
 
 impl Metric<(&[f64], &[usize])> for BinaryJaccardIndex {
@@ -59,6 +73,12 @@ impl Metric<(&[f64], &[usize])> for BinaryJaccardIndex {
     }
 }
 
+impl Revertible<(&[f64], &[usize])> for BinaryJaccardIndex {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 /// Calculate the Jaccard index for multiclass tasks.
 /// The `Jaccard index`_ (also known as the intersection over union or jaccard similarity coefficient) is an statistic
 /// that can be used to determine the similarity and diversity of a sample set. It is defined as the size of the
@@ -87,6 +107,7 @@ impl Metric<(&[f64], &[usize])> for BinaryJaccardIndex {
 pub struct MulticlassJaccardIndex {
     stat_scores: MulticlassStatScores,
     average_method: AverageMethod,
+    class_weights: Option<Vec<f64>>,
 }
 
 impl MulticlassJaccardIndex {
@@ -96,8 +117,31 @@ impl MulticlassJaccardIndex {
         Self {
             stat_scores,
             average_method,
+            class_weights: None,
         }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`MulticlassStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Average per-class values with explicit class weights (e.g. business costs) instead of
+    /// the configured [`AverageMethod`].
+    pub fn with_class_weights(mut self, weights: Vec<f64>) -> Self {
+        verify_class_weights(&weights, self.stat_scores.num_classes);
+        self.class_weights = Some(weights);
+        self
+    }
+
+    /// Jaccard index `TP / (TP + FP + FN)` of every class.
+    ///
+    /// A class that never occurs in predictions or targets has an empty union and scores `0.0`.
+    pub fn compute_per_class(&self) -> Option<Vec<f64>> {
+        self.stat_scores
+            .per_class(|tp, fp, fn_, _| (tp, tp + fp + fn_))
+    }
 }
 
 impl Metric<(&[&[f64]], &[usize])> for MulticlassJaccardIndex {
@@ -113,6 +157,11 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassJaccardIndex {
     }
 
     fn compute(&self) -> Option<Self::Output> {
+        if let Some(weights) = &self.class_weights {
+            return self
+                .compute_per_class()
+                .map(|values| weighted_class_mean(&values, weights));
+        }
         if self.stat_scores.total == 0 {
             return None;
         }
@@ -159,6 +208,12 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassJaccardIndex {
     }
 }
 
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassJaccardIndex {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::BinaryJaccardIndex;
@@ -192,6 +247,8 @@ mod tests {
         let result = metric.compute().unwrap();
         assert!((result - (2.0 / 3.0)).abs() < f64::EPSILON);
 
+        assert_eq!(metric.compute_per_class(), Some(vec![0.5, 0.5, 1.0]));
+
         metric.reset();
         assert_eq!(metric.compute(), None);
     }
@@ -0,0 +1,134 @@
+use std::cmp::Ordering;
+
+use crate::core::{Metric, MetricError};
+
+use super::curve::BinaryScores;
+
+/// One row of a [`BinaryLiftCurve`] table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiftBucket {
+    /// Samples in this bucket.
+    pub samples: usize,
+    /// Positive samples in this bucket.
+    pub positives: usize,
+    /// Fraction of all samples ranked at or above this bucket.
+    pub depth: f64,
+    /// Fraction of all positives captured at or above this bucket.
+    pub cumulative_gain: f64,
+    /// `cumulative_gain / depth`: how much better than random targeting the top `depth` is.
+    pub lift: f64,
+}
+
+/// Lift and cumulative gain table for binary classification.
+///
+/// Accumulated samples are sorted by decreasing score and split into `buckets` equally sized
+/// groups (deciles by default); ties are broken by arrival order. `compute` returns `None` until
+/// at least one positive and `buckets` samples were seen.
+///
+/// ```
+/// use rust_metrics::{BinaryLiftCurve, Metric};
+///
+/// let preds = [0.9, 0.8, 0.7, 0.6, 0.5, 0.4, 0.3, 0.2, 0.1, 0.05];
+/// let target = [1_usize, 1, 0, 1, 0, 0, 1, 0, 0, 0];
+///
+/// let mut metric = BinaryLiftCurve::new(5);
+/// metric.update((&preds, &target)).unwrap();
+/// let table = metric.compute().unwrap();
+/// assert_eq!(table[0].cumulative_gain, 0.5);
+/// assert!((table[0].lift - 2.5).abs() < 1e-12);
+/// assert_eq!(table[4].lift, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryLiftCurve {
+    buckets: usize,
+    scores: BinaryScores,
+}
+
+impl Default for BinaryLiftCurve {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl BinaryLiftCurve {
+    pub fn new(buckets: usize) -> Self {
+        assert!(buckets >= 1, "buckets must be at least 1");
+        Self {
+            buckets,
+            scores: BinaryScores::new(0),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryLiftCurve {
+    type Output = Vec<LiftBucket>;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.scores.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let mut samples = self.scores.samples()?;
+        let total = samples.len();
+        let total_pos = samples.iter().filter(|(_, positive)| *positive).count();
+        if total_pos == 0 || total < self.buckets {
+            return None;
+        }
+        samples.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+        let mut table = Vec::with_capacity(self.buckets);
+        let mut seen = 0;
+        let mut seen_pos = 0;
+        for bucket in 0..self.buckets {
+            let end = (bucket + 1) * total / self.buckets;
+            let positives = samples[seen..end]
+                .iter()
+                .filter(|(_, positive)| *positive)
+                .count();
+            let bucket_samples = end - seen;
+            seen = end;
+            seen_pos += positives;
+
+            let depth = seen as f64 / total as f64;
+            let cumulative_gain = seen_pos as f64 / total_pos as f64;
+            table.push(LiftBucket {
+                samples: bucket_samples,
+                positives,
+                depth,
+                cumulative_gain,
+                lift: cumulative_gain / depth,
+            });
+        }
+        Some(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryLiftCurve;
+    use crate::core::Metric;
+
+    #[test]
+    fn lift_table_over_batches() {
+        let preds = [0.9, 0.8, 0.7, 0.6, 0.5, 0.4, 0.3, 0.2, 0.1, 0.05];
+        let target = [1_usize, 1, 0, 1, 0, 0, 1, 0, 0, 0];
+
+        let mut metric = BinaryLiftCurve::new(5);
+        metric.update((&preds[5..], &target[5..])).unwrap();
+        metric.update((&preds[..5], &target[..5])).unwrap();
+        let table = metric.compute().unwrap();
+
+        let positives: Vec<usize> = table.iter().map(|b| b.positives).collect();
+        assert_eq!(positives, vec![2, 1, 0, 1, 0]);
+        let gains: Vec<f64> = table.iter().map(|b| b.cumulative_gain).collect();
+        assert_eq!(gains, vec![0.5, 0.75, 0.75, 1.0, 1.0]);
+        assert!((table[1].lift - 1.875).abs() < 1e-12);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
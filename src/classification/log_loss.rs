@@ -0,0 +1,267 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{InputTransform, verify_binary_label, verify_label, verify_reverted_count};
+
+const DEFAULT_EPSILON: f64 = 1e-15;
+
+fn clipped_nll(probability: f64, epsilon: f64) -> f64 {
+    -probability.clamp(epsilon, 1.0 - epsilon).ln()
+}
+
+fn verify_epsilon(epsilon: f64) {
+    assert!(
+        epsilon > 0.0 && epsilon < 0.5,
+        "epsilon must lie in (0, 0.5)"
+    );
+}
+
+/// Binary log loss (binary cross entropy) over predicted positive-class probabilities.
+///
+/// Probabilities are clipped to `[epsilon, 1 - epsilon]` so confident mistakes stay finite.
+///
+/// ```
+/// use rust_metrics::{BinaryLogLoss, Metric};
+///
+/// let preds = [0.9, 0.2, 0.6];
+/// let target = [1_usize, 0, 1];
+///
+/// let mut metric = BinaryLogLoss::default();
+/// metric.update((&preds, &target)).unwrap();
+/// let expected = -(0.9_f64.ln() + 0.8_f64.ln() + 0.6_f64.ln()) / 3.0;
+/// assert!((metric.compute().unwrap() - expected).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryLogLoss {
+    epsilon: f64,
+    input_transform: InputTransform,
+    measures: f64,
+    total: usize,
+}
+
+impl Default for BinaryLogLoss {
+    fn default() -> Self {
+        Self::new(DEFAULT_EPSILON)
+    }
+}
+
+impl BinaryLogLoss {
+    pub fn new(epsilon: f64) -> Self {
+        verify_epsilon(epsilon);
+        Self {
+            epsilon,
+            input_transform: InputTransform::None,
+            measures: 0.0,
+            total: 0,
+        }
+    }
+
+    /// Score raw logits by passing them through [`InputTransform::Sigmoid`] first.
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        assert_ne!(
+            input_transform,
+            InputTransform::Softmax,
+            "softmax needs a row of class scores"
+        );
+        self.input_transform = input_transform;
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryLogLoss {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        let mut probabilities = Vec::with_capacity(predictions.len());
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            probabilities.push(self.input_transform.probability(prediction)?);
+            verify_binary_label(target)?;
+        }
+        for (prediction, &target) in probabilities.into_iter().zip(targets.iter()) {
+            let probability = if target == 1 {
+                prediction
+            } else {
+                1.0 - prediction
+            };
+            self.measures += clipped_nll(probability, self.epsilon);
+        }
+        self.total += predictions.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.measures = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.measures / self.total as f64)
+    }
+}
+
+impl Revertible<(&[f64], &[usize])> for BinaryLogLoss {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.measures -= batch.measures;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+/// Multiclass log loss: the mean negative log probability assigned to the target class.
+///
+/// Rows are used as given (not renormalized); the target probability is clipped to
+/// `[epsilon, 1 - epsilon]`.
+///
+/// ```
+/// use rust_metrics::{Metric, MulticlassLogLoss};
+///
+/// let preds: [&[f64]; 2] = [&[0.7, 0.2, 0.1], &[0.1, 0.3, 0.6]];
+/// let target = [0, 1];
+///
+/// let mut metric = MulticlassLogLoss::new(3, 1e-15);
+/// metric.update((&preds, &target)).unwrap();
+/// let expected = -(0.7_f64.ln() + 0.3_f64.ln()) / 2.0;
+/// assert!((metric.compute().unwrap() - expected).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MulticlassLogLoss {
+    num_classes: usize,
+    epsilon: f64,
+    input_transform: InputTransform,
+    measures: f64,
+    total: usize,
+}
+
+impl MulticlassLogLoss {
+    pub fn new(num_classes: usize, epsilon: f64) -> Self {
+        assert!(num_classes >= 2, "num_classes must be at least 2");
+        verify_epsilon(epsilon);
+        Self {
+            num_classes,
+            epsilon,
+            input_transform: InputTransform::None,
+            measures: 0.0,
+            total: 0,
+        }
+    }
+
+    /// Map each row of raw scores to probabilities first, e.g. [`InputTransform::Softmax`] for
+    /// logits.
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.input_transform = input_transform;
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[usize])> for MulticlassLogLoss {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        let mut probabilities = Vec::with_capacity(predictions.len());
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            verify_label(target, self.num_classes)?;
+            if prediction.len() != self.num_classes {
+                return Err(MetricError::IncompatibleInput {
+                    expected: format!(
+                        "length of predictions must be equal to number of classes: {}",
+                        self.num_classes
+                    ),
+                    got: format!("got {}", prediction.len()),
+                });
+            }
+            probabilities.push(self.input_transform.probabilities(prediction)?);
+        }
+        for (prediction, &target) in probabilities.iter().zip(targets.iter()) {
+            self.measures += clipped_nll(prediction[target], self.epsilon);
+        }
+        self.total += predictions.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.measures = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.measures / self.total as f64)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassLogLoss {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.measures -= batch.measures;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryLogLoss, MulticlassLogLoss};
+    use crate::core::Metric;
+    use crate::utils::InputTransform;
+
+    #[test]
+    fn log_loss_clips_confident_mistakes() {
+        let mut metric = BinaryLogLoss::new(1e-7);
+        metric.update((&[1.0, 0.5], &[0, 1])).unwrap();
+        metric.update((&[0.0], &[0])).unwrap();
+        let expected = (-(1e-7_f64).ln() - 0.5_f64.ln() - (1.0 - 1e-7_f64).ln()) / 3.0;
+        assert!((metric.compute().unwrap() - expected).abs() < 1e-12);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn multiclass_log_loss_validates_rows() {
+        let mut metric = MulticlassLogLoss::new(3, 1e-15);
+        let preds: [&[f64]; 1] = [&[0.5, 0.5][..]];
+        assert!(metric.update((&preds, &[0])).is_err());
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn log_loss_from_logits() {
+        let mut binary = BinaryLogLoss::default().with_input_transform(InputTransform::Sigmoid);
+        binary.update((&[0.0, 3.0_f64.ln()], &[1, 1])).unwrap();
+        let expected = -(0.5_f64.ln() + 0.75_f64.ln()) / 2.0;
+        assert!((binary.compute().unwrap() - expected).abs() < 1e-12);
+
+        let mut multiclass =
+            MulticlassLogLoss::new(3, 1e-15).with_input_transform(InputTransform::Softmax);
+        let logits: [&[f64]; 1] = [&[4.0_f64.ln(), 0.0, 0.0][..]];
+        multiclass.update((&logits, &[0])).unwrap();
+        assert!((multiclass.compute().unwrap() + (2.0_f64 / 3.0).ln()).abs() < 1e-12);
+        assert!(
+            MulticlassLogLoss::new(3, 1e-15)
+                .update((&logits, &[0]))
+                .is_err()
+        );
+    }
+}
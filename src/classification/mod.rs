@@ -4,18 +4,50 @@
 //! batched updates plus `reset`/`compute` semantics.
 
 pub mod accuracy;
+pub mod auprg;
 pub mod auroc;
+pub mod average_precision;
+pub mod cohen_kappa;
 pub mod confusion_matrix;
+pub(crate) mod curve;
+pub mod delong;
 pub mod f1;
+pub mod hierarchical;
 pub mod hinge;
 pub mod jaccard;
+pub mod lift;
+pub mod log_loss;
 pub mod precision_recall;
+pub mod ranking;
+pub mod rates;
+pub mod report;
+pub mod roc;
 pub mod stat_scores;
+pub mod support;
+pub mod threshold_sweep;
 
-pub use accuracy::{BinaryAccuracy, MulticlassAccuracy};
-pub use auroc::BinaryAuroc;
-pub use confusion_matrix::BinaryConfusionMatrix;
-pub use f1::{BinaryF1Score, MulticlassF1Score};
-pub use hinge::{BinaryHingeLoss, MulticlassHingeLoss};
+pub use accuracy::{BinaryAccuracy, MulticlassAccuracy, MultilabelAccuracy, MultilabelExactMatch};
+pub use auprg::BinaryAuprg;
+pub use auroc::{BinaryAuroc, BinaryAurocSnapshot, BinaryGini};
+pub use average_precision::{BinaryAveragePrecision, MulticlassAveragePrecision};
+pub use cohen_kappa::{BinaryCohenKappa, KappaWeighting, MulticlassCohenKappa};
+pub use confusion_matrix::{BinaryConfusionMatrix, MulticlassConfusionMatrix, Normalization};
+pub use delong::{AurocVariance, DeLong, DeLongTest};
+pub use f1::{BinaryF1Score, MulticlassF1Score, MultilabelF1Score};
+pub use hierarchical::{HierarchicalF1Score, HierarchicalPrecision, HierarchicalRecall};
+pub use hinge::{BinaryHingeLoss, MulticlassHingeLoss, MulticlassHingeMode, ScoreKind};
 pub use jaccard::{BinaryJaccardIndex, MulticlassJaccardIndex};
-pub use precision_recall::{BinaryPrecision, BinaryRecall, MulticlassPrecision};
+pub use lift::{BinaryLiftCurve, LiftBucket};
+pub use log_loss::{BinaryLogLoss, MulticlassLogLoss};
+pub use precision_recall::{
+    BinaryPrecision, BinaryRecall, MulticlassPrecision, MultilabelPrecision, MultilabelRecall,
+};
+pub use ranking::{
+    MultilabelCoverageError, MultilabelRankingAveragePrecision, MultilabelRankingLoss,
+};
+pub use rates::{BinaryFalseNegativeRate, BinaryFalsePositiveRate, BinaryNegativePredictiveValue};
+pub use report::{BinaryClassificationReport, ClassificationStats};
+pub use roc::{BinaryRoc, BinarySensitivityAtSpecificity};
+pub use stat_scores::{BinaryStatScores, MulticlassStatScores, MultilabelStatScores, StatCounts};
+pub use support::{ClassCount, ClassSupport};
+pub use threshold_sweep::{ThresholdChoice, ThresholdObjective, ThresholdSweep};
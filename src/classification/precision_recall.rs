@@ -1,7 +1,10 @@
-use crate::core::{Metric, MetricError};
-use crate::utils::AverageMethod;
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{AverageMethod, InputTransform};
 
-use super::stat_scores::{BinaryStatScores, MulticlassStatScores};
+use super::stat_scores::{
+    BinaryStatScores, MulticlassStatScores, MultilabelStatScores, verify_class_weights,
+    weighted_class_mean,
+};
 
 /// Thresholded precision for binary classification probabilities.
 ///
@@ -25,6 +28,27 @@ impl BinaryPrecision {
         let stat_scores = BinaryStatScores::new(threshold);
         Self { stat_scores }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+
+    /// Score already thresholded `0`/`1` predictions, see [`BinaryStatScores::update_labels`].
+    pub fn update_labels(
+        &mut self,
+        predictions: &[usize],
+        targets: &[usize],
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update_labels(predictions, targets)
+    }
 }
 
 impl Metric<(&[f64], &[usize])> for BinaryPrecision {
@@ -51,10 +75,17 @@ impl Metric<(&[f64], &[usize])> for BinaryPrecision {
     }
 }
 
+impl Revertible<(&[f64], &[usize])> for BinaryPrecision {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MulticlassPrecision {
     stat_scores: MulticlassStatScores,
     average_method: AverageMethod,
+    class_weights: Option<Vec<f64>>,
 }
 
 /// Macro/micro precision for multi-class classification.
@@ -82,8 +113,30 @@ impl MulticlassPrecision {
         Self {
             stat_scores,
             average_method,
+            class_weights: None,
         }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`MulticlassStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Average per-class values with explicit class weights (e.g. business costs) instead of
+    /// the configured [`AverageMethod`].
+    pub fn with_class_weights(mut self, weights: Vec<f64>) -> Self {
+        verify_class_weights(&weights, self.stat_scores.num_classes);
+        self.class_weights = Some(weights);
+        self
+    }
+
+    /// Precision `TP / (TP + FP)` of every class.
+    ///
+    /// Classes that were never predicted report `0.0`.
+    pub fn compute_per_class(&self) -> Option<Vec<f64>> {
+        self.stat_scores.per_class(|tp, fp, _, _| (tp, tp + fp))
+    }
 }
 
 impl Metric<(&[&[f64]], &[usize])> for MulticlassPrecision {
@@ -100,6 +153,11 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassPrecision {
     }
 
     fn compute(&self) -> Option<Self::Output> {
+        if let Some(weights) = &self.class_weights {
+            return self
+                .compute_per_class()
+                .map(|values| weighted_class_mean(&values, weights));
+        }
         if self.stat_scores.total == 0 {
             return None;
         }
@@ -158,6 +216,12 @@ impl Metric<(&[&[f64]], &[usize])> for MulticlassPrecision {
     }
 }
 
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassPrecision {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 /// Binary recall (`TP / (TP + FN)`) over thresholded probabilities.
 ///
 /// ```
@@ -180,6 +244,27 @@ impl BinaryRecall {
         let stat_scores = BinaryStatScores::new(threshold);
         Self { stat_scores }
     }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+
+    /// Score already thresholded `0`/`1` predictions, see [`BinaryStatScores::update_labels`].
+    pub fn update_labels(
+        &mut self,
+        predictions: &[usize],
+        targets: &[usize],
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update_labels(predictions, targets)
+    }
 }
 
 impl Metric<(&[f64], &[usize])> for BinaryRecall {
@@ -206,9 +291,147 @@ impl Metric<(&[f64], &[usize])> for BinaryRecall {
     }
 }
 
+impl Revertible<(&[f64], &[usize])> for BinaryRecall {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// Multilabel precision over independently thresholded label columns.
+///
+/// `Micro` pools counts over all labels, `Macro` averages labels with at least one positive
+/// prediction and `Weighted` weights those labels by support. Thresholds default to a single
+/// value for all labels; [`with_thresholds`](Self::with_thresholds) sets one per label.
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelPrecision};
+/// use rust_metrics::utils::AverageMethod;
+///
+/// let preds: [&[f64]; 3] = [&[0.9, 0.6, 0.1], &[0.8, 0.3, 0.7], &[0.2, 0.4, 0.8]];
+/// let targets: [&[usize]; 3] = [&[1, 0, 0], &[1, 1, 1], &[0, 1, 1]];
+///
+/// let mut metric = MultilabelPrecision::new(3, 0.5, AverageMethod::Micro);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 4.0 / 5.0).abs() < f64::EPSILON);
+///
+/// let mut tuned = MultilabelPrecision::new(3, 0.5, AverageMethod::Micro)
+///     .with_thresholds(vec![0.5, 0.7, 0.5]);
+/// tuned.update((&preds, &targets)).unwrap();
+/// assert_eq!(tuned.compute(), Some(1.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelPrecision {
+    stat_scores: MultilabelStatScores,
+    average_method: AverageMethod,
+}
+
+impl MultilabelPrecision {
+    pub fn new(num_labels: usize, threshold: f64, average_method: AverageMethod) -> Self {
+        Self {
+            stat_scores: MultilabelStatScores::new(num_labels, threshold),
+            average_method,
+        }
+    }
+
+    /// Use a separate decision threshold for each label.
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        self.stat_scores = self.stat_scores.with_thresholds(thresholds);
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelPrecision {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.stat_scores.precision(&self.average_method)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[usize]])> for MultilabelPrecision {
+    fn revert(&mut self, input: (&[&[f64]], &[&[usize]])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// Multilabel recall over independently thresholded label columns.
+///
+/// Averaging follows [`MultilabelPrecision`], except that `Macro` and `Weighted` skip labels
+/// that never occur in the targets.
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelRecall};
+/// use rust_metrics::utils::AverageMethod;
+///
+/// let preds: [&[f64]; 3] = [&[0.9, 0.6, 0.1], &[0.8, 0.3, 0.7], &[0.2, 0.4, 0.8]];
+/// let targets: [&[usize]; 3] = [&[1, 0, 0], &[1, 1, 1], &[0, 1, 1]];
+///
+/// let mut metric = MultilabelRecall::new(3, 0.5, AverageMethod::Macro);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelRecall {
+    stat_scores: MultilabelStatScores,
+    average_method: AverageMethod,
+}
+
+impl MultilabelRecall {
+    pub fn new(num_labels: usize, threshold: f64, average_method: AverageMethod) -> Self {
+        Self {
+            stat_scores: MultilabelStatScores::new(num_labels, threshold),
+            average_method,
+        }
+    }
+
+    /// Use a separate decision threshold for each label.
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        self.stat_scores = self.stat_scores.with_thresholds(thresholds);
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelRecall {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.stat_scores.recall(&self.average_method)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[usize]])> for MultilabelRecall {
+    fn revert(&mut self, input: (&[&[f64]], &[&[usize]])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{BinaryPrecision, BinaryRecall, MulticlassPrecision};
+    use super::{
+        BinaryPrecision, BinaryRecall, MulticlassPrecision, MultilabelPrecision, MultilabelRecall,
+    };
     use crate::core::{Metric, MetricError};
 
     #[test]
@@ -252,6 +475,13 @@ mod tests {
         let result = metric.compute().unwrap();
         assert!((result - 0.8333333333333334).abs() < f64::EPSILON);
 
+        assert_eq!(metric.compute_per_class(), Some(vec![1.0, 0.5, 1.0]));
+
+        let mut costed = MulticlassPrecision::new(3, super::AverageMethod::Macro)
+            .with_class_weights(vec![1.0, 3.0, 0.0]);
+        costed.update((&preds, &targets)).unwrap();
+        assert!((costed.compute().unwrap() - 0.625).abs() < f64::EPSILON);
+
         metric.reset();
         assert_eq!(metric.compute(), None);
     }
@@ -283,4 +513,32 @@ mod tests {
             other => panic!("Expected IncompatibleInput error, got: {:?}", other),
         }
     }
+
+    #[test]
+    fn multilabel_precision_recall_share_counts() {
+        let preds: [&[f64]; 3] = [
+            &[0.9, 0.6, 0.1][..],
+            &[0.8, 0.3, 0.7][..],
+            &[0.2, 0.4, 0.8][..],
+        ];
+        let targets: [&[usize]; 3] = [&[1, 0, 0][..], &[1, 1, 1][..], &[0, 1, 1][..]];
+
+        // tp: [2, 0, 2], fp: [0, 1, 0], fn: [0, 2, 0]
+        let mut precision = MultilabelPrecision::new(3, 0.5, super::AverageMethod::Macro);
+        precision.update((&preds, &targets)).unwrap();
+        assert!((precision.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+
+        let mut recall = MultilabelRecall::new(3, 0.5, super::AverageMethod::Weighted);
+        recall.update((&preds[..1], &targets[..1])).unwrap();
+        recall.update((&preds[1..], &targets[1..])).unwrap();
+        assert!((recall.compute().unwrap() - 4.0 / 6.0).abs() < f64::EPSILON);
+
+        let mut per_label = MultilabelRecall::new(3, 0.5, super::AverageMethod::Micro)
+            .with_thresholds(vec![0.5, 0.2, 0.5]);
+        per_label.update((&preds, &targets)).unwrap();
+        assert_eq!(per_label.compute(), Some(1.0));
+
+        recall.reset();
+        assert_eq!(recall.compute(), None);
+    }
 }
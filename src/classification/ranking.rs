@@ -0,0 +1,313 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{MetricAggregator, Reduction, verify_binary_label, verify_range};
+
+/// Validate a batch of per-label scores against multi-hot targets. Scores only need to be
+/// finite, since the ranking metrics depend on their order alone.
+fn verify_ranking_batch(
+    predictions: &[&[f64]],
+    targets: &[&[usize]],
+    num_labels: usize,
+) -> Result<(), MetricError> {
+    if predictions.len() != targets.len() {
+        return Err(MetricError::LengthMismatch {
+            predictions: predictions.len(),
+            targets: targets.len(),
+        });
+    }
+    for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+        if prediction.len() != num_labels || target.len() != num_labels {
+            return Err(MetricError::IncompatibleInput {
+                expected: format!(
+                    "predictions and targets of length equal to number of labels: {}",
+                    num_labels
+                ),
+                got: format!("got {} and {}", prediction.len(), target.len()),
+            });
+        }
+        for (&score, &label) in prediction.iter().zip(target.iter()) {
+            verify_range(score, f64::MIN, f64::MAX)?;
+            verify_binary_label(label)?;
+        }
+    }
+    Ok(())
+}
+
+/// Label ranking loss: the fraction of (relevant, irrelevant) label pairs that are ordered
+/// wrongly, averaged over samples. Ties count as wrongly ordered.
+///
+/// Matches sklearn's `label_ranking_loss`: samples whose labels are all relevant or all
+/// irrelevant contribute a loss of `0`. Scores may be any finite values.
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelRankingLoss};
+///
+/// let preds: [&[f64]; 2] = [&[0.75, 0.5, 1.0], &[1.0, 0.2, 0.1]];
+/// let targets: [&[usize]; 2] = [&[1, 0, 0], &[0, 0, 1]];
+///
+/// let mut metric = MultilabelRankingLoss::new(3);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 0.75).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelRankingLoss {
+    num_labels: usize,
+    metric_aggregator: MetricAggregator,
+}
+
+impl MultilabelRankingLoss {
+    pub fn new(num_labels: usize) -> Self {
+        assert!(num_labels >= 1, "num_labels must be at least 1");
+        Self {
+            num_labels,
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelRankingLoss {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        verify_ranking_batch(predictions, targets, self.num_labels)?;
+
+        for (&scores, &labels) in predictions.iter().zip(targets.iter()) {
+            let relevant = labels.iter().filter(|&&label| label == 1).count();
+            let irrelevant = self.num_labels - relevant;
+            if relevant == 0 || irrelevant == 0 {
+                self.metric_aggregator.update(0.0);
+                continue;
+            }
+            let mut misordered = 0usize;
+            for (&positive_score, _) in scores.iter().zip(labels).filter(|(_, l)| **l == 1) {
+                misordered += scores
+                    .iter()
+                    .zip(labels)
+                    .filter(|&(&score, &label)| label == 0 && score >= positive_score)
+                    .count();
+            }
+            self.metric_aggregator
+                .update(misordered as f64 / (relevant * irrelevant) as f64);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+/// Coverage error: how many of the top-ranked labels must be taken, on average, to include
+/// every relevant label of a sample.
+///
+/// Matches sklearn's `coverage_error`: tied scores are all included, so ties are counted
+/// pessimistically, and samples without relevant labels contribute `0`. The best value is the
+/// average number of relevant labels per sample.
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelCoverageError};
+///
+/// let preds: [&[f64]; 2] = [&[0.75, 0.5, 1.0], &[1.0, 0.2, 0.1]];
+/// let targets: [&[usize]; 2] = [&[1, 0, 0], &[0, 0, 1]];
+///
+/// let mut metric = MultilabelCoverageError::new(3);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 2.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelCoverageError {
+    num_labels: usize,
+    metric_aggregator: MetricAggregator,
+}
+
+impl MultilabelCoverageError {
+    pub fn new(num_labels: usize) -> Self {
+        assert!(num_labels >= 1, "num_labels must be at least 1");
+        Self {
+            num_labels,
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelCoverageError {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        verify_ranking_batch(predictions, targets, self.num_labels)?;
+
+        for (&scores, &labels) in predictions.iter().zip(targets.iter()) {
+            let lowest_relevant = scores
+                .iter()
+                .zip(labels)
+                .filter(|(_, label)| **label == 1)
+                .map(|(&score, _)| score)
+                .fold(f64::INFINITY, f64::min);
+            let coverage = scores
+                .iter()
+                .filter(|&&score| score >= lowest_relevant)
+                .count();
+            self.metric_aggregator.update(coverage as f64);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+/// Label ranking average precision (LRAP): for every relevant label, the fraction of labels
+/// ranked at or above it that are also relevant, averaged over relevant labels and samples.
+///
+/// Matches sklearn's `label_ranking_average_precision_score`: samples whose labels are all
+/// relevant or all irrelevant score `1`. Higher is better, with `1` for a perfect ranking.
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelRankingAveragePrecision};
+///
+/// let preds: [&[f64]; 2] = [&[0.75, 0.5, 1.0], &[1.0, 0.2, 0.1]];
+/// let targets: [&[usize]; 2] = [&[1, 0, 0], &[0, 0, 1]];
+///
+/// let mut metric = MultilabelRankingAveragePrecision::new(3);
+/// metric.update((&preds, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 5.0 / 12.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelRankingAveragePrecision {
+    num_labels: usize,
+    metric_aggregator: MetricAggregator,
+}
+
+impl MultilabelRankingAveragePrecision {
+    pub fn new(num_labels: usize) -> Self {
+        assert!(num_labels >= 1, "num_labels must be at least 1");
+        Self {
+            num_labels,
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelRankingAveragePrecision {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        verify_ranking_batch(predictions, targets, self.num_labels)?;
+
+        for (&scores, &labels) in predictions.iter().zip(targets.iter()) {
+            let relevant: Vec<f64> = scores
+                .iter()
+                .zip(labels)
+                .filter(|(_, label)| **label == 1)
+                .map(|(&score, _)| score)
+                .collect();
+            if relevant.is_empty() || relevant.len() == self.num_labels {
+                self.metric_aggregator.update(1.0);
+                continue;
+            }
+            let precision_sum: f64 = relevant
+                .iter()
+                .map(|&score| {
+                    let rank = scores.iter().filter(|&&s| s >= score).count();
+                    let relevant_rank = relevant.iter().filter(|&&s| s >= score).count();
+                    relevant_rank as f64 / rank as f64
+                })
+                .sum();
+            self.metric_aggregator
+                .update(precision_sum / relevant.len() as f64);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        MultilabelCoverageError, MultilabelRankingAveragePrecision, MultilabelRankingLoss,
+    };
+    use crate::core::Metric;
+
+    #[test]
+    fn ranking_loss_counts_ties_and_degenerate_rows() {
+        let preds: [&[f64]; 3] = [
+            &[0.5, 0.5, 0.1][..],
+            &[0.9, 0.8, 0.7][..],
+            &[-2.0, 3.0, 1.0][..],
+        ];
+        let targets: [&[usize]; 3] = [&[1, 0, 0][..], &[1, 1, 1][..], &[0, 1, 1][..]];
+
+        let mut metric = MultilabelRankingLoss::new(3);
+        metric.update((&preds[..1], &targets[..1])).unwrap();
+        metric.update((&preds[1..], &targets[1..])).unwrap();
+        // row losses: 1/2 (tie with label 1), 0 (all relevant), 0
+        assert!((metric.compute().unwrap() - 0.5 / 3.0).abs() < 1e-12);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn coverage_error_includes_ties() {
+        let preds: [&[f64]; 3] = [
+            &[0.5, 0.5, 0.1][..],
+            &[0.9, 0.8, 0.7][..],
+            &[0.3, 0.2, 0.1][..],
+        ];
+        let targets: [&[usize]; 3] = [&[1, 0, 0][..], &[0, 0, 1][..], &[0, 0, 0][..]];
+
+        let mut metric = MultilabelCoverageError::new(3);
+        metric.update((&preds, &targets)).unwrap();
+        // row coverages: 2 (tie), 3, 0 (no relevant labels)
+        assert!((metric.compute().unwrap() - 5.0 / 3.0).abs() < 1e-12);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn lrap_perfect_and_degenerate_rows() {
+        let preds: [&[f64]; 3] = [
+            &[0.9, 0.1, 0.8][..],
+            &[0.2, 0.3, 0.4][..],
+            &[0.1, 0.4, 0.9][..],
+        ];
+        let targets: [&[usize]; 3] = [&[1, 0, 1][..], &[0, 0, 0][..], &[1, 0, 1][..]];
+
+        let mut metric = MultilabelRankingAveragePrecision::new(3);
+        metric.update((&preds[..2], &targets[..2])).unwrap();
+        assert_eq!(metric.compute(), Some(1.0));
+
+        // third row: label 2 ranks first (1/1), label 0 ranks third (2/3)
+        metric.update((&preds[2..], &targets[2..])).unwrap();
+        let expected = (1.0 + 1.0 + (1.0 + 2.0 / 3.0) / 2.0) / 3.0;
+        assert!((metric.compute().unwrap() - expected).abs() < 1e-12);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::InputTransform;
+
+use super::stat_scores::BinaryStatScores;
+
+fn ratio(numerator: usize, denominator: usize) -> Option<f64> {
+    if denominator == 0 {
+        return None;
+    }
+    Some(numerator as f64 / denominator as f64)
+}
+
+/// Negative predictive value (`TN / (TN + FN)`) over thresholded probabilities.
+///
+/// `None` until at least one sample is predicted negative.
+///
+/// ```
+/// use rust_metrics::{BinaryNegativePredictiveValue, Metric};
+///
+/// let target = [0_usize, 1, 0, 1, 0, 1];
+/// let preds = [0.11, 0.22, 0.84, 0.73, 0.33, 0.92];
+///
+/// let mut npv = BinaryNegativePredictiveValue::default();
+/// npv.update((&preds, &target)).unwrap();
+/// assert!((npv.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BinaryNegativePredictiveValue {
+    stat_scores: BinaryStatScores,
+}
+
+impl BinaryNegativePredictiveValue {
+    pub fn new(threshold: f64) -> Self {
+        let stat_scores = BinaryStatScores::new(threshold);
+        Self { stat_scores }
+    }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryNegativePredictiveValue {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let s = &self.stat_scores;
+        ratio(s.true_negative, s.true_negative + s.false_negative)
+    }
+}
+
+impl Revertible<(&[f64], &[usize])> for BinaryNegativePredictiveValue {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// False positive rate (`FP / (FP + TN)`) over thresholded probabilities.
+///
+/// `None` until at least one negative sample is seen.
+///
+/// ```
+/// use rust_metrics::{BinaryFalsePositiveRate, Metric};
+///
+/// let target = [0_usize, 1, 0, 1, 0, 1];
+/// let preds = [0.11, 0.22, 0.84, 0.73, 0.33, 0.92];
+///
+/// let mut fpr = BinaryFalsePositiveRate::default();
+/// fpr.update((&preds, &target)).unwrap();
+/// assert!((fpr.compute().unwrap() - 1.0 / 3.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BinaryFalsePositiveRate {
+    stat_scores: BinaryStatScores,
+}
+
+impl BinaryFalsePositiveRate {
+    pub fn new(threshold: f64) -> Self {
+        let stat_scores = BinaryStatScores::new(threshold);
+        Self { stat_scores }
+    }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryFalsePositiveRate {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let s = &self.stat_scores;
+        ratio(s.false_positive, s.false_positive + s.true_negative)
+    }
+}
+
+impl Revertible<(&[f64], &[usize])> for BinaryFalsePositiveRate {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+/// False negative rate (`FN / (FN + TP)`) over thresholded probabilities.
+///
+/// `None` until at least one positive sample is seen.
+///
+/// ```
+/// use rust_metrics::{BinaryFalseNegativeRate, Metric};
+///
+/// let target = [0_usize, 1, 0, 1, 0, 1];
+/// let preds = [0.11, 0.22, 0.84, 0.73, 0.33, 0.92];
+///
+/// let mut fnr = BinaryFalseNegativeRate::default();
+/// fnr.update((&preds, &target)).unwrap();
+/// assert!((fnr.compute().unwrap() - 1.0 / 3.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BinaryFalseNegativeRate {
+    stat_scores: BinaryStatScores,
+}
+
+impl BinaryFalseNegativeRate {
+    pub fn new(threshold: f64) -> Self {
+        let stat_scores = BinaryStatScores::new(threshold);
+        Self { stat_scores }
+    }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryFalseNegativeRate {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let s = &self.stat_scores;
+        ratio(s.false_negative, s.false_negative + s.true_positive)
+    }
+}
+
+impl Revertible<(&[f64], &[usize])> for BinaryFalseNegativeRate {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryFalseNegativeRate, BinaryFalsePositiveRate, BinaryNegativePredictiveValue};
+    use crate::core::Metric;
+
+    #[test]
+    fn rates_over_batches() {
+        let preds = [0.9, 0.2, 0.7, 0.4, 0.1];
+        let target = [1_usize, 1, 0, 0, 0];
+
+        let mut npv = BinaryNegativePredictiveValue::default();
+        let mut fpr = BinaryFalsePositiveRate::default();
+        let mut fnr = BinaryFalseNegativeRate::default();
+        for (p, t) in [(&preds[..2], &target[..2]), (&preds[2..], &target[2..])] {
+            npv.update((p, t)).unwrap();
+            fpr.update((p, t)).unwrap();
+            fnr.update((p, t)).unwrap();
+        }
+        assert!((npv.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert!((fpr.compute().unwrap() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert!((fnr.compute().unwrap() - 0.5).abs() < f64::EPSILON);
+
+        fnr.reset();
+        fnr.update((&[0.3], &[0])).unwrap();
+        assert_eq!(fnr.compute(), None);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::InputTransform;
+
+use super::stat_scores::{BinaryStatScores, StatCounts};
+
+/// Scores reported by [`BinaryClassificationReport`].
+///
+/// Ratios whose denominator is zero (e.g. precision without positive predictions) are `0.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassificationStats {
+    pub accuracy: f64,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1_score: f64,
+    pub specificity: f64,
+    /// Matthews correlation coefficient.
+    pub mcc: f64,
+    pub counts: StatCounts,
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / denominator
+    }
+}
+
+/// Accuracy, precision, recall, F1, specificity, and MCC from one pass over thresholded
+/// probabilities.
+///
+/// ```
+/// use rust_metrics::{BinaryClassificationReport, Metric};
+///
+/// let target = [0_usize, 1, 0, 1, 0, 1];
+/// let preds = [0.11, 0.22, 0.84, 0.73, 0.33, 0.92];
+///
+/// let mut report = BinaryClassificationReport::default();
+/// report.update((&preds, &target)).unwrap();
+/// let stats = report.compute().unwrap();
+/// assert!((stats.accuracy - 2.0 / 3.0).abs() < 1e-12);
+/// assert!((stats.f1_score - 2.0 / 3.0).abs() < 1e-12);
+/// assert!((stats.mcc - 1.0 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BinaryClassificationReport {
+    stat_scores: BinaryStatScores,
+}
+
+impl BinaryClassificationReport {
+    pub fn new(threshold: f64) -> Self {
+        let stat_scores = BinaryStatScores::new(threshold);
+        Self { stat_scores }
+    }
+
+    /// Skip samples whose target equals `ignore_index`, see [`BinaryStatScores::with_ignore_index`].
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.stat_scores = self.stat_scores.with_ignore_index(ignore_index);
+        self
+    }
+
+    /// Map raw scores to probabilities, see [`BinaryStatScores::with_input_transform`].
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        self.stat_scores = self.stat_scores.with_input_transform(input_transform);
+        self
+    }
+
+    /// Score already thresholded `0`/`1` predictions, see [`BinaryStatScores::update_labels`].
+    pub fn update_labels(
+        &mut self,
+        predictions: &[usize],
+        targets: &[usize],
+    ) -> Result<(), MetricError> {
+        self.stat_scores.update_labels(predictions, targets)
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryClassificationReport {
+    type Output = ClassificationStats;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.update((predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let counts = self.stat_scores.compute()?;
+        let tp = counts.true_positive as f64;
+        let fp = counts.false_positive as f64;
+        let fn_ = counts.false_negative as f64;
+        let tn = counts.true_negative as f64;
+        let mcc_denominator = ((tp + fp) * (tp + fn_) * (tn + fp) * (tn + fn_)).sqrt();
+        Some(ClassificationStats {
+            accuracy: (tp + tn) / (tp + fp + fn_ + tn),
+            precision: ratio(tp, tp + fp),
+            recall: ratio(tp, tp + fn_),
+            f1_score: ratio(2.0 * tp, 2.0 * tp + fp + fn_),
+            specificity: ratio(tn, tn + fp),
+            mcc: ratio(tp * tn - fp * fn_, mcc_denominator),
+            counts,
+        })
+    }
+}
+
+impl Revertible<(&[f64], &[usize])> for BinaryClassificationReport {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.stat_scores.revert(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryClassificationReport;
+    use crate::core::Metric;
+    use crate::{BinaryPrecision, BinaryRecall};
+
+    #[test]
+    fn report_matches_single_metrics() {
+        let preds = [0.9, 0.8, 0.3, 0.6, 0.2, 0.1, 0.7];
+        let target = [1_usize, 1, 1, 0, 0, 0, 0];
+
+        let mut report = BinaryClassificationReport::default();
+        let mut precision = BinaryPrecision::default();
+        let mut recall = BinaryRecall::default();
+        report.update((&preds, &target)).unwrap();
+        precision.update((&preds, &target)).unwrap();
+        recall.update((&preds, &target)).unwrap();
+
+        let stats = report.compute().unwrap();
+        assert_eq!(Some(stats.precision), precision.compute());
+        assert_eq!(Some(stats.recall), recall.compute());
+        assert_eq!(stats.specificity, 0.5);
+        // (2 * 2 - 2 * 1) / sqrt(4 * 3 * 4 * 3)
+        assert!((stats.mcc - 2.0 / 12.0).abs() < 1e-12);
+
+        report.reset();
+        report.update((&[0.1, 0.2], &[0, 0])).unwrap();
+        let stats = report.compute().unwrap();
+        assert_eq!(
+            (stats.precision, stats.mcc, stats.accuracy),
+            (0.0, 0.0, 1.0)
+        );
+
+        report.reset();
+        assert_eq!(report.compute(), None);
+    }
+}
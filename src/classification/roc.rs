@@ -0,0 +1,208 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::verify_range;
+
+use super::curve::BinaryScores;
+
+/// ROC curve for binary classification, returned as `(fpr, tpr, thresholds)`.
+///
+/// Points are ordered by decreasing threshold, and a sample is predicted positive when its score
+/// is at least the threshold. The curve starts at `(0, 0)` with an infinite threshold. Passing
+/// `0` to [`BinaryRoc::new`] keeps every sample (one point per distinct score); any value `> 1`
+/// buckets scores into that many bins, like [`BinaryAuroc`](crate::BinaryAuroc).
+///
+/// ```
+/// use rust_metrics::{BinaryRoc, Metric};
+///
+/// let preds = [0.1, 0.4, 0.35, 0.8];
+/// let target = [0_usize, 0, 1, 1];
+///
+/// let mut roc = BinaryRoc::new(0);
+/// roc.update((&preds, &target)).unwrap();
+/// let (fpr, tpr, thresholds) = roc.compute().unwrap();
+/// assert_eq!(fpr, vec![0.0, 0.0, 0.5, 0.5, 1.0]);
+/// assert_eq!(tpr, vec![0.0, 0.5, 0.5, 1.0, 1.0]);
+/// assert_eq!(thresholds[1..], [0.8, 0.4, 0.35, 0.1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryRoc {
+    scores: BinaryScores,
+}
+
+impl Default for BinaryRoc {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl BinaryRoc {
+    pub fn new(bins: usize) -> Self {
+        Self {
+            scores: BinaryScores::new(bins),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinaryRoc {
+    type Output = (Vec<f64>, Vec<f64>, Vec<f64>);
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.scores.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.scores.roc()
+    }
+}
+
+/// Highest sensitivity (TPR) reachable while keeping specificity at least `min_specificity`,
+/// returned as `(sensitivity, threshold)`.
+///
+/// Reads the same ROC accumulation as [`BinaryRoc`]; ties on sensitivity go to the highest
+/// threshold. Scores are kept exactly unless [`with_bins`](Self::with_bins) is used.
+///
+/// The threshold follows the ROC convention: samples scoring at least `threshold` are
+/// predicted positive. [`BinaryStatScores`](crate::BinaryStatScores),
+/// [`ThresholdSweep`](crate::ThresholdSweep), and the other thresholded metrics predict positive
+/// only when `score > threshold`, so they reproduce the reported sensitivity with a threshold just
+/// below this one. When no threshold gives a positive sensitivity at the required specificity, the
+/// result is `(0.0, f64::INFINITY)`: only predicting every sample negative qualifies.
+///
+/// ```
+/// use rust_metrics::{BinarySensitivityAtSpecificity, Metric};
+///
+/// let preds = [0.1, 0.4, 0.35, 0.8];
+/// let target = [0_usize, 0, 1, 1];
+///
+/// let mut metric = BinarySensitivityAtSpecificity::new(0.5);
+/// metric.update((&preds, &target)).unwrap();
+/// // 0.35 itself counts as positive
+/// assert_eq!(metric.compute(), Some((1.0, 0.35)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinarySensitivityAtSpecificity {
+    min_specificity: f64,
+    scores: BinaryScores,
+}
+
+impl BinarySensitivityAtSpecificity {
+    pub fn new(min_specificity: f64) -> Self {
+        verify_range(min_specificity, 0.0, 1.0).unwrap();
+        Self {
+            min_specificity,
+            scores: BinaryScores::new(0),
+        }
+    }
+
+    /// Bucket scores into `bins` evenly spaced bins instead of keeping every sample.
+    pub fn with_bins(mut self, bins: usize) -> Self {
+        assert!(bins > 1, "bins must be greater than 1");
+        self.scores = BinaryScores::new(bins);
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for BinarySensitivityAtSpecificity {
+    type Output = (f64, f64);
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        self.scores.update(predictions, targets)
+    }
+
+    fn reset(&mut self) {
+        self.scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let (fpr, tpr, thresholds) = self.scores.roc()?;
+        let mut best = (0.0, f64::INFINITY);
+        for ((fpr, tpr), threshold) in fpr.into_iter().zip(tpr).zip(thresholds) {
+            if 1.0 - fpr >= self.min_specificity && tpr > best.0 {
+                best = (tpr, threshold);
+            }
+        }
+        Some(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryRoc, BinarySensitivityAtSpecificity};
+    use crate::BinaryAuroc;
+    use crate::core::Metric;
+
+    #[test]
+    fn binned_roc_matches_auroc() {
+        let preds = [0.0, 0.5, 0.7, 0.8, 0.3, 0.9];
+        let target = [0_usize, 1, 1, 0, 0, 1];
+
+        let mut roc = BinaryRoc::new(11);
+        roc.update((&preds[..3], &target[..3])).unwrap();
+        roc.update((&preds[3..], &target[3..])).unwrap();
+        let (fpr, tpr, thresholds) = roc.compute().unwrap();
+        assert_eq!(fpr.len(), 12);
+        assert_eq!(thresholds[1], 1.0);
+        assert_eq!((fpr[11], tpr[11]), (1.0, 1.0));
+
+        let area: f64 = fpr
+            .windows(2)
+            .zip(tpr.windows(2))
+            .map(|(x, y)| (x[1] - x[0]) * (y[1] + y[0]) / 2.0)
+            .sum();
+        let mut auroc = BinaryAuroc::new(11);
+        auroc.update((&preds, &target)).unwrap();
+        assert!((area - auroc.compute().unwrap()).abs() < 1e-12);
+
+        roc.reset();
+        assert_eq!(roc.compute(), None);
+    }
+
+    #[test]
+    fn sensitivity_at_strict_specificity() {
+        let preds = [0.1, 0.4, 0.35, 0.8];
+        let target = [0_usize, 0, 1, 1];
+
+        let mut metric = BinarySensitivityAtSpecificity::new(0.9);
+        metric.update((&preds[..2], &target[..2])).unwrap();
+        assert_eq!(metric.compute(), None);
+        metric.update((&preds[2..], &target[2..])).unwrap();
+        assert_eq!(metric.compute(), Some((0.5, 0.8)));
+
+        let mut binned = BinarySensitivityAtSpecificity::new(0.9).with_bins(11);
+        binned.update((&preds, &target)).unwrap();
+        assert_eq!(binned.compute(), Some((0.5, 0.8)));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn threshold_predicts_scores_at_least_as_high() {
+        let preds = [0.1, 0.4, 0.35, 0.8, 0.6];
+        let target = [0_usize, 0, 1, 1, 0];
+
+        let mut metric = BinarySensitivityAtSpecificity::new(0.3);
+        metric.update((&preds, &target)).unwrap();
+        let (sensitivity, threshold) = metric.compute().unwrap();
+        assert_eq!((sensitivity, threshold), (1.0, 0.35));
+
+        let recalled = |positive: fn(f64, f64) -> bool| {
+            let hits = preds
+                .iter()
+                .zip(&target)
+                .filter(|&(&p, &t)| t == 1 && positive(p, threshold))
+                .count();
+            hits as f64 / 2.0
+        };
+        assert_eq!(recalled(|p, t| p >= t), sensitivity);
+        assert_eq!(recalled(|p, t| p > t), 0.5);
+
+        // the highest score is negative, so any positive prediction costs specificity
+        let mut unreachable = BinarySensitivityAtSpecificity::new(1.0);
+        unreachable.update((&preds, &[0, 1, 1, 0, 1])).unwrap();
+        assert_eq!(unreachable.compute(), Some((0.0, f64::INFINITY)));
+    }
+}
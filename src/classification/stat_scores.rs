@@ -1,6 +1,54 @@
-use crate::core::MetricError;
-use crate::utils::{verify_binary_label, verify_label, verify_range};
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{
+    AverageMethod, InputTransform, verify_binary_label, verify_label, verify_range,
+    verify_reverted_count,
+};
 
+/// Confusion counts of one binary decision (a class or a label), as returned by the stat scores
+/// metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatCounts {
+    pub true_positive: usize,
+    pub false_positive: usize,
+    pub false_negative: usize,
+    pub true_negative: usize,
+    /// Number of samples whose target is this class (`TP + FN`).
+    pub support: usize,
+}
+
+impl StatCounts {
+    pub(crate) fn new(
+        true_positive: usize,
+        false_positive: usize,
+        false_negative: usize,
+        true_negative: usize,
+    ) -> Self {
+        Self {
+            true_positive,
+            false_positive,
+            false_negative,
+            true_negative,
+            support: true_positive + false_negative,
+        }
+    }
+}
+
+/// Thresholded TP/FP/FN/TN counts for binary classification, the state behind the binary
+/// metrics. Use it directly to derive metrics the crate does not provide.
+///
+/// ```
+/// use rust_metrics::{BinaryStatScores, Metric};
+///
+/// let target = [0_usize, 1, 0, 1, 0, 1];
+/// let preds = [0.11, 0.22, 0.84, 0.73, 0.33, 0.92];
+///
+/// let mut metric = BinaryStatScores::default();
+/// metric.update((&preds, &target)).unwrap();
+/// let counts = metric.compute().unwrap();
+/// assert_eq!((counts.true_positive, counts.false_positive), (2, 1));
+/// assert_eq!((counts.false_negative, counts.true_negative), (1, 2));
+/// assert_eq!(counts.support, 3);
+/// ```
 #[derive(Debug, Clone)]
 pub struct BinaryStatScores {
     pub true_positive: usize,
@@ -9,6 +57,8 @@ pub struct BinaryStatScores {
     pub true_negative: usize,
     pub total: usize,
     threshold: f64,
+    ignore_index: Option<usize>,
+    input_transform: InputTransform,
 }
 impl Default for BinaryStatScores {
     fn default() -> Self {
@@ -25,12 +75,34 @@ impl BinaryStatScores {
             true_negative: 0,
             total: 0,
             threshold,
+            ignore_index: None,
+            input_transform: InputTransform::None,
         }
     }
 
-    pub fn update(
+    /// Skip samples whose target equals `ignore_index` (e.g. padding) during updates.
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.ignore_index = Some(ignore_index);
+        self
+    }
+
+    /// Map raw scores (e.g. logits with [`InputTransform::Sigmoid`]) to probabilities before
+    /// thresholding.
+    pub fn with_input_transform(mut self, input_transform: InputTransform) -> Self {
+        assert_ne!(
+            input_transform,
+            InputTransform::Softmax,
+            "softmax needs a row of class scores"
+        );
+        self.input_transform = input_transform;
+        self
+    }
+
+    /// Accumulate already thresholded `0`/`1` predictions; the threshold is not applied.
+    pub fn update_labels(
         &mut self,
-        (predictions, targets): (&[f64], &[usize]),
+        predictions: &[usize],
+        targets: &[usize],
     ) -> Result<(), MetricError> {
         if predictions.len() != targets.len() {
             return Err(MetricError::LengthMismatch {
@@ -39,32 +111,107 @@ impl BinaryStatScores {
             });
         }
         for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
-            verify_range(prediction, 0.0, 1.0)?;
+            if Some(target) == self.ignore_index {
+                continue;
+            }
+            verify_binary_label(prediction)?;
             verify_binary_label(target)?;
+            self.record(prediction == 1, target == 1);
+        }
+        Ok(())
+    }
 
-            let prediction: bool = prediction > self.threshold;
-            let actual: bool = target == 1;
+    fn record(&mut self, predicted: bool, actual: bool) {
+        match (predicted, actual) {
+            (true, true) => self.true_positive += 1,
+            (true, false) => self.false_positive += 1,
+            (false, true) => self.false_negative += 1,
+            (false, false) => self.true_negative += 1,
+        }
+        self.total += 1;
+    }
+}
 
-            match (prediction, actual) {
-                (true, true) => self.true_positive += 1,
-                (true, false) => self.false_positive += 1,
-                (false, true) => self.false_negative += 1,
-                (false, false) => self.true_negative += 1,
-            }
+impl Metric<(&[f64], &[usize])> for BinaryStatScores {
+    type Output = StatCounts;
 
-            self.total += 1;
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            if Some(target) == self.ignore_index {
+                continue;
+            }
+            let prediction = self.input_transform.probability(prediction)?;
+            verify_binary_label(target)?;
+            self.record(prediction > self.threshold, target == 1);
         }
         Ok(())
     }
-    pub fn reset(&mut self) {
+
+    fn reset(&mut self) {
         self.true_positive = 0;
         self.false_positive = 0;
         self.false_negative = 0;
         self.true_negative = 0;
         self.total = 0;
     }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(StatCounts::new(
+            self.true_positive,
+            self.false_positive,
+            self.false_negative,
+            self.true_negative,
+        ))
+    }
 }
 
+impl Revertible<(&[f64], &[usize])> for BinaryStatScores {
+    fn revert(&mut self, input: (&[f64], &[usize])) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(input)?;
+        verify_reverted_count(batch.true_positive, self.true_positive)?;
+        verify_reverted_count(batch.false_positive, self.false_positive)?;
+        verify_reverted_count(batch.false_negative, self.false_negative)?;
+        verify_reverted_count(batch.true_negative, self.true_negative)?;
+
+        self.true_positive -= batch.true_positive;
+        self.false_positive -= batch.false_positive;
+        self.false_negative -= batch.false_negative;
+        self.true_negative -= batch.true_negative;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+/// One-vs-rest TP/FP/FN/TN counts of every class after taking the argmax prediction.
+///
+/// ```
+/// use rust_metrics::{Metric, MulticlassStatScores};
+///
+/// let targets = [2, 1, 0, 0];
+/// let preds: [&[f64]; 4] = [
+///     &[0.16, 0.26, 0.58],
+///     &[0.22, 0.61, 0.17],
+///     &[0.71, 0.09, 0.20],
+///     &[0.05, 0.82, 0.13],
+/// ];
+///
+/// let mut metric = MulticlassStatScores::new(3);
+/// metric.update((&preds, &targets)).unwrap();
+/// let counts = metric.compute().unwrap();
+/// assert_eq!(counts[1].false_positive, 1);
+/// assert_eq!(counts[0].support, 2);
+/// ```
 #[derive(Debug, Clone)]
 pub struct MulticlassStatScores {
     pub true_positive: Vec<usize>,
@@ -74,6 +221,7 @@ pub struct MulticlassStatScores {
     pub total_per_class: Vec<usize>,
     pub total: usize,
     pub num_classes: usize,
+    ignore_index: Option<usize>,
 }
 impl MulticlassStatScores {
     pub fn new(num_classes: usize) -> Self {
@@ -86,13 +234,48 @@ impl MulticlassStatScores {
             total_per_class: vec![0; num_classes],
             total: 0,
             num_classes,
+            ignore_index: None,
         }
     }
 
-    pub fn update(
-        &mut self,
-        (predictions, targets): (&[&[f64]], &[usize]),
-    ) -> Result<(), MetricError> {
+    /// Skip samples whose target equals `ignore_index` (e.g. padding) during updates.
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.ignore_index = Some(ignore_index);
+        self
+    }
+
+    /// Per-class `numerator / denominator` built from `(tp, fp, fn, tn)`, with `0.0` for classes
+    /// whose denominator is zero; `None` before any update.
+    pub(crate) fn per_class(
+        &self,
+        ratio: impl Fn(usize, usize, usize, usize) -> (usize, usize),
+    ) -> Option<Vec<f64>> {
+        if self.total == 0 {
+            return None;
+        }
+        let values = (0..self.num_classes)
+            .map(|i| {
+                let (numerator, denominator) = ratio(
+                    self.true_positive[i],
+                    self.false_positive[i],
+                    self.false_negative[i],
+                    self.true_negative[i],
+                );
+                if denominator == 0 {
+                    0.0
+                } else {
+                    numerator as f64 / denominator as f64
+                }
+            })
+            .collect();
+        Some(values)
+    }
+}
+
+impl Metric<(&[&[f64]], &[usize])> for MulticlassStatScores {
+    type Output = Vec<StatCounts>;
+
+    fn update(&mut self, (predictions, targets): (&[&[f64]], &[usize])) -> Result<(), MetricError> {
         if predictions.len() != targets.len() {
             return Err(MetricError::LengthMismatch {
                 predictions: predictions.len(),
@@ -101,6 +284,9 @@ impl MulticlassStatScores {
         }
 
         for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            if Some(target) == self.ignore_index {
+                continue;
+            }
             verify_label(target, self.num_classes)?;
 
             if prediction.len() != self.num_classes {
@@ -137,7 +323,8 @@ impl MulticlassStatScores {
         }
         Ok(())
     }
-    pub fn reset(&mut self) {
+
+    fn reset(&mut self) {
         self.true_positive = vec![0; self.num_classes];
         self.false_positive = vec![0; self.num_classes];
         self.false_negative = vec![0; self.num_classes];
@@ -145,4 +332,378 @@ impl MulticlassStatScores {
         self.total_per_class = vec![0; self.num_classes];
         self.total = 0;
     }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        let counts = (0..self.num_classes)
+            .map(|i| {
+                StatCounts::new(
+                    self.true_positive[i],
+                    self.false_positive[i],
+                    self.false_negative[i],
+                    self.true_negative[i],
+                )
+            })
+            .collect();
+        Some(counts)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[usize])> for MulticlassStatScores {
+    fn revert(&mut self, input: (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(input)?;
+        for class_idx in 0..self.num_classes {
+            verify_reverted_count(
+                batch.true_positive[class_idx],
+                self.true_positive[class_idx],
+            )?;
+            verify_reverted_count(
+                batch.false_positive[class_idx],
+                self.false_positive[class_idx],
+            )?;
+            verify_reverted_count(
+                batch.false_negative[class_idx],
+                self.false_negative[class_idx],
+            )?;
+            verify_reverted_count(
+                batch.true_negative[class_idx],
+                self.true_negative[class_idx],
+            )?;
+        }
+
+        for class_idx in 0..self.num_classes {
+            self.true_positive[class_idx] -= batch.true_positive[class_idx];
+            self.false_positive[class_idx] -= batch.false_positive[class_idx];
+            self.false_negative[class_idx] -= batch.false_negative[class_idx];
+            self.true_negative[class_idx] -= batch.true_negative[class_idx];
+            self.total_per_class[class_idx] -= batch.total_per_class[class_idx];
+        }
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+/// Per-label confusion counts for multilabel classification, where every label is an independent
+/// binary decision over its own probability column.
+///
+/// Precision, recall and F1 can all be read from one accumulation:
+///
+/// ```
+/// use rust_metrics::{Metric, MultilabelStatScores};
+/// use rust_metrics::utils::AverageMethod;
+///
+/// let preds: [&[f64]; 2] = [&[0.9, 0.2], &[0.7, 0.6]];
+/// let targets: [&[usize]; 2] = [&[1, 1], &[0, 1]];
+///
+/// let mut stat_scores = MultilabelStatScores::new(2, 0.5);
+/// stat_scores.update((&preds, &targets)).unwrap();
+/// assert_eq!(stat_scores.precision(&AverageMethod::Micro), Some(2.0 / 3.0));
+/// assert_eq!(stat_scores.recall(&AverageMethod::Micro), Some(2.0 / 3.0));
+/// assert_eq!(stat_scores.f1_score(&AverageMethod::Macro), Some((2.0 / 3.0 + 2.0 / 3.0) / 2.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultilabelStatScores {
+    pub true_positive: Vec<usize>,
+    pub false_positive: Vec<usize>,
+    pub false_negative: Vec<usize>,
+    pub true_negative: Vec<usize>,
+    pub total: usize,
+    pub num_labels: usize,
+    thresholds: Vec<f64>,
+}
+impl MultilabelStatScores {
+    pub fn new(num_labels: usize, threshold: f64) -> Self {
+        assert!(num_labels >= 1, "num_labels must be at least 1");
+        verify_range(threshold, 0.0, 1.0).unwrap();
+        Self {
+            true_positive: vec![0; num_labels],
+            false_positive: vec![0; num_labels],
+            false_negative: vec![0; num_labels],
+            true_negative: vec![0; num_labels],
+            total: 0,
+            num_labels,
+            thresholds: vec![threshold; num_labels],
+        }
+    }
+
+    /// Use a separate decision threshold for each label.
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        assert_eq!(
+            thresholds.len(),
+            self.num_labels,
+            "expected one threshold per label"
+        );
+        for &threshold in &thresholds {
+            verify_range(threshold, 0.0, 1.0).unwrap();
+        }
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Number of samples carrying each label (`TP + FN`).
+    pub fn support(&self) -> Vec<usize> {
+        self.true_positive
+            .iter()
+            .zip(&self.false_negative)
+            .map(|(tp, fn_)| tp + fn_)
+            .collect()
+    }
+
+    pub fn precision(&self, average_method: &AverageMethod) -> Option<f64> {
+        self.average_ratio(average_method, |tp, fp, _| (tp, tp + fp))
+    }
+
+    pub fn recall(&self, average_method: &AverageMethod) -> Option<f64> {
+        self.average_ratio(average_method, |tp, _, fn_| (tp, tp + fn_))
+    }
+
+    pub fn f1_score(&self, average_method: &AverageMethod) -> Option<f64> {
+        self.average_ratio(average_method, |tp, fp, fn_| (2 * tp, 2 * tp + fp + fn_))
+    }
+
+    /// Average a per-label `numerator / denominator` ratio built from `(tp, fp, fn)`.
+    ///
+    /// `Micro` pools the counts before dividing; `Macro` and `Weighted` skip labels whose
+    /// denominator is zero, `Weighted` weighting the rest by support.
+    fn average_ratio(
+        &self,
+        average_method: &AverageMethod,
+        ratio: impl Fn(usize, usize, usize) -> (usize, usize),
+    ) -> Option<f64> {
+        if self.total == 0 {
+            return None;
+        }
+        let parts: Vec<(usize, usize)> = (0..self.num_labels)
+            .map(|i| {
+                ratio(
+                    self.true_positive[i],
+                    self.false_positive[i],
+                    self.false_negative[i],
+                )
+            })
+            .collect();
+
+        match average_method {
+            AverageMethod::Micro => {
+                let numerator: usize = parts.iter().map(|(num, _)| num).sum();
+                let denominator: usize = parts.iter().map(|(_, den)| den).sum();
+                if denominator == 0 {
+                    return None;
+                }
+                Some(numerator as f64 / denominator as f64)
+            }
+            AverageMethod::Macro | AverageMethod::Weighted => {
+                let weights: Vec<usize> = match average_method {
+                    AverageMethod::Weighted => self.support(),
+                    _ => vec![1; self.num_labels],
+                };
+                let mut weighted_sum = 0.0;
+                let mut weight_total = 0usize;
+                for (&(num, den), &weight) in parts.iter().zip(&weights) {
+                    if den > 0 {
+                        weighted_sum += weight as f64 * num as f64 / den as f64;
+                        weight_total += weight;
+                    }
+                }
+                if weight_total == 0 {
+                    None
+                } else {
+                    Some(weighted_sum / weight_total as f64)
+                }
+            }
+        }
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[usize]])> for MultilabelStatScores {
+    type Output = Vec<StatCounts>;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[f64]], &[&[usize]]),
+    ) -> Result<(), MetricError> {
+        verify_multilabel_batch(predictions, targets, self.num_labels)?;
+
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            for (label_idx, (&score, &label)) in prediction.iter().zip(target.iter()).enumerate() {
+                match (score > self.thresholds[label_idx], label == 1) {
+                    (true, true) => self.true_positive[label_idx] += 1,
+                    (true, false) => self.false_positive[label_idx] += 1,
+                    (false, true) => self.false_negative[label_idx] += 1,
+                    (false, false) => self.true_negative[label_idx] += 1,
+                }
+            }
+            self.total += 1;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.true_positive = vec![0; self.num_labels];
+        self.false_positive = vec![0; self.num_labels];
+        self.false_negative = vec![0; self.num_labels];
+        self.true_negative = vec![0; self.num_labels];
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        let counts = (0..self.num_labels)
+            .map(|i| {
+                StatCounts::new(
+                    self.true_positive[i],
+                    self.false_positive[i],
+                    self.false_negative[i],
+                    self.true_negative[i],
+                )
+            })
+            .collect();
+        Some(counts)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[usize]])> for MultilabelStatScores {
+    fn revert(&mut self, input: (&[&[f64]], &[&[usize]])) -> Result<(), MetricError> {
+        let mut batch = self.clone();
+        batch.reset();
+        batch.update(input)?;
+        for label_idx in 0..self.num_labels {
+            verify_reverted_count(
+                batch.true_positive[label_idx],
+                self.true_positive[label_idx],
+            )?;
+            verify_reverted_count(
+                batch.false_positive[label_idx],
+                self.false_positive[label_idx],
+            )?;
+            verify_reverted_count(
+                batch.false_negative[label_idx],
+                self.false_negative[label_idx],
+            )?;
+            verify_reverted_count(
+                batch.true_negative[label_idx],
+                self.true_negative[label_idx],
+            )?;
+        }
+
+        for label_idx in 0..self.num_labels {
+            self.true_positive[label_idx] -= batch.true_positive[label_idx];
+            self.false_positive[label_idx] -= batch.false_positive[label_idx];
+            self.false_negative[label_idx] -= batch.false_negative[label_idx];
+            self.true_negative[label_idx] -= batch.true_negative[label_idx];
+        }
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+/// Panic unless `weights` holds one finite, non-negative weight per class with a positive sum.
+pub(crate) fn verify_class_weights(weights: &[f64], num_classes: usize) {
+    assert_eq!(weights.len(), num_classes, "expected one weight per class");
+    assert!(
+        weights.iter().all(|w| w.is_finite() && *w >= 0.0),
+        "class weights must be finite and non-negative"
+    );
+    assert!(
+        weights.iter().sum::<f64>() > 0.0,
+        "class weights must not all be zero"
+    );
+}
+
+/// `sum(w_i * v_i) / sum(w_i)` over per-class values.
+pub(crate) fn weighted_class_mean(values: &[f64], weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().sum();
+    values.iter().zip(weights).map(|(v, w)| v * w).sum::<f64>() / total
+}
+
+/// Check that every prediction and multi-hot target row has `num_labels` valid entries.
+pub(crate) fn verify_multilabel_batch(
+    predictions: &[&[f64]],
+    targets: &[&[usize]],
+    num_labels: usize,
+) -> Result<(), MetricError> {
+    if predictions.len() != targets.len() {
+        return Err(MetricError::LengthMismatch {
+            predictions: predictions.len(),
+            targets: targets.len(),
+        });
+    }
+
+    for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+        if prediction.len() != num_labels || target.len() != num_labels {
+            return Err(MetricError::IncompatibleInput {
+                expected: format!(
+                    "predictions and targets of length equal to number of labels: {}",
+                    num_labels
+                ),
+                got: format!("got {} and {}", prediction.len(), target.len()),
+            });
+        }
+        for (&score, &label) in prediction.iter().zip(target.iter()) {
+            verify_range(score, 0.0, 1.0)?;
+            verify_binary_label(label)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryStatScores, MulticlassStatScores, MultilabelStatScores, StatCounts};
+    use crate::core::{Metric, Revertible};
+
+    #[test]
+    fn multilabel_stat_scores_per_label_counts() {
+        let preds: [&[f64]; 2] = [&[0.9, 0.2][..], &[0.7, 0.6][..]];
+        let targets: [&[usize]; 2] = [&[1, 1][..], &[0, 1][..]];
+
+        let mut metric = MultilabelStatScores::new(2, 0.5);
+        metric.update((&preds, &targets)).unwrap();
+        let counts = metric.compute().unwrap();
+        assert_eq!(
+            counts[0],
+            StatCounts {
+                true_positive: 1,
+                false_positive: 1,
+                false_negative: 0,
+                true_negative: 0,
+                support: 1,
+            }
+        );
+        assert_eq!(counts[1].support, 2);
+
+        metric.revert((&preds, &targets)).unwrap();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn ignore_index_skips_padding() {
+        let preds: [&[f64]; 3] = [
+            &[0.1, 0.2, 0.7][..],
+            &[0.6, 0.3, 0.1][..],
+            &[0.8, 0.1, 0.1][..],
+        ];
+        let mut multiclass = MulticlassStatScores::new(3).with_ignore_index(usize::MAX);
+        multiclass.update((&preds, &[2, usize::MAX, 0])).unwrap();
+        assert_eq!(multiclass.total, 2);
+        assert_eq!(multiclass.compute().unwrap()[0].false_positive, 0);
+        assert!(
+            MulticlassStatScores::new(3)
+                .update((&preds, &[2, usize::MAX, 0]))
+                .is_err()
+        );
+
+        let mut binary = BinaryStatScores::default().with_ignore_index(2);
+        binary.update((&[0.9, 0.4, 0.8], &[1, 2, 0])).unwrap();
+        binary.revert((&[0.4], &[2])).unwrap();
+        let counts = binary.compute().unwrap();
+        assert_eq!((counts.true_positive, counts.false_positive), (1, 1));
+        assert_eq!(binary.total, 2);
+    }
 }
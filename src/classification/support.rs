@@ -0,0 +1,123 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{verify_label, verify_reverted_count};
+
+/// Number and share of samples of one class, as reported by [`ClassSupport`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassCount {
+    pub support: usize,
+    pub prevalence: f64,
+}
+
+/// Per-class target counts and prevalence, for showing `n` next to per-class scores.
+///
+/// ```
+/// use rust_metrics::{ClassSupport, Metric};
+///
+/// let targets = [2, 1, 0, 0];
+///
+/// let mut metric = ClassSupport::new(3);
+/// metric.update(&targets).unwrap();
+/// let counts = metric.compute().unwrap();
+/// assert_eq!(counts[0].support, 2);
+/// assert_eq!(counts[0].prevalence, 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClassSupport {
+    counts: Vec<usize>,
+    total: usize,
+}
+
+impl ClassSupport {
+    pub fn new(num_classes: usize) -> Self {
+        assert!(num_classes >= 2, "num_classes must be at least 2");
+        Self {
+            counts: vec![0; num_classes],
+            total: 0,
+        }
+    }
+
+    fn batch_counts(&self, targets: &[usize]) -> Result<Vec<usize>, MetricError> {
+        let mut counts = vec![0; self.counts.len()];
+        for &target in targets {
+            verify_label(target, self.counts.len())?;
+            counts[target] += 1;
+        }
+        Ok(counts)
+    }
+}
+
+impl Metric<&[usize]> for ClassSupport {
+    type Output = Vec<ClassCount>;
+
+    fn update(&mut self, targets: &[usize]) -> Result<(), MetricError> {
+        let batch = self.batch_counts(targets)?;
+        for (count, batch_count) in self.counts.iter_mut().zip(batch) {
+            *count += batch_count;
+        }
+        self.total += targets.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.counts.fill(0);
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        let counts = self
+            .counts
+            .iter()
+            .map(|&support| ClassCount {
+                support,
+                prevalence: support as f64 / self.total as f64,
+            })
+            .collect();
+        Some(counts)
+    }
+}
+
+impl Revertible<&[usize]> for ClassSupport {
+    fn revert(&mut self, targets: &[usize]) -> Result<(), MetricError> {
+        let batch = self.batch_counts(targets)?;
+        for (&count, &batch_count) in self.counts.iter().zip(&batch) {
+            verify_reverted_count(batch_count, count)?;
+        }
+        for (count, batch_count) in self.counts.iter_mut().zip(batch) {
+            *count -= batch_count;
+        }
+        self.total -= targets.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClassSupport;
+    use crate::core::{Metric, Revertible};
+
+    #[test]
+    fn class_support_over_batches() {
+        let mut metric = ClassSupport::new(3);
+        metric.update(&[0, 1]).unwrap();
+        metric.update(&[1, 1]).unwrap();
+        let supports: Vec<usize> = metric
+            .compute()
+            .unwrap()
+            .iter()
+            .map(|c| c.support)
+            .collect();
+        assert_eq!(supports, vec![1, 3, 0]);
+        assert_eq!(metric.compute().unwrap()[1].prevalence, 0.75);
+
+        assert!(metric.update(&[3]).is_err());
+        assert!(metric.revert(&[2]).is_err());
+        metric.revert(&[0, 1]).unwrap();
+        assert_eq!(metric.compute().unwrap()[1].prevalence, 1.0);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
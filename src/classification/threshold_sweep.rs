@@ -0,0 +1,194 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{verify_binary_label, verify_range};
+
+use super::stat_scores::StatCounts;
+
+/// Quantity maximized by [`ThresholdSweep::best`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdObjective {
+    /// `2 TP / (2 TP + FP + FN)`.
+    F1,
+    /// Youden's J statistic, `sensitivity + specificity - 1`.
+    Youden,
+    /// `(TP + TN) / N`.
+    Accuracy,
+}
+
+impl ThresholdObjective {
+    fn evaluate(&self, counts: &StatCounts) -> Option<f64> {
+        let tp = counts.true_positive as f64;
+        let fp = counts.false_positive as f64;
+        let fn_ = counts.false_negative as f64;
+        let tn = counts.true_negative as f64;
+        match self {
+            ThresholdObjective::F1 => {
+                let denominator = 2.0 * tp + fp + fn_;
+                (denominator > 0.0).then(|| 2.0 * tp / denominator)
+            }
+            ThresholdObjective::Youden => {
+                (tp + fn_ > 0.0 && tn + fp > 0.0).then(|| tp / (tp + fn_) + tn / (tn + fp) - 1.0)
+            }
+            ThresholdObjective::Accuracy => Some((tp + tn) / (tp + fp + fn_ + tn)),
+        }
+    }
+}
+
+/// Threshold picked by [`ThresholdSweep::best`] and the objective value it reaches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdChoice {
+    pub threshold: f64,
+    pub value: f64,
+}
+
+/// Confusion counts of a binary classifier at every threshold of a fixed grid, accumulated in
+/// one pass.
+///
+/// Like the other binary metrics, a sample is predicted positive when its score is strictly
+/// greater than the threshold. Only one counter per grid cell is stored, so memory does not grow
+/// with the number of samples. `compute` returns the `(threshold, counts)` table in increasing
+/// threshold order; [`best`](Self::best) picks the threshold maximizing a [`ThresholdObjective`]
+/// (ties go to the lowest threshold).
+///
+/// ```
+/// use rust_metrics::{Metric, ThresholdObjective, ThresholdSweep};
+///
+/// let preds = [0.1, 0.4, 0.35, 0.8];
+/// let target = [0_usize, 0, 1, 1];
+///
+/// let mut sweep = ThresholdSweep::new(vec![0.2, 0.3, 0.5]);
+/// sweep.update((&preds, &target)).unwrap();
+/// let best = sweep.best(ThresholdObjective::F1).unwrap();
+/// assert_eq!(best.threshold, 0.2);
+/// assert!((best.value - 0.8).abs() < 1e-12);
+/// assert_eq!(sweep.best(ThresholdObjective::Accuracy).unwrap().threshold, 0.2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThresholdSweep {
+    thresholds: Vec<f64>,
+    /// `positives[j]` / `negatives[j]` count samples above exactly `j` grid thresholds.
+    positives: Vec<usize>,
+    negatives: Vec<usize>,
+    total: usize,
+}
+
+impl Default for ThresholdSweep {
+    /// Thresholds `0.00, 0.01, ..., 1.00`.
+    fn default() -> Self {
+        Self::new((0..=100).map(|i| i as f64 / 100.0).collect())
+    }
+}
+
+impl ThresholdSweep {
+    pub fn new(mut thresholds: Vec<f64>) -> Self {
+        assert!(!thresholds.is_empty(), "thresholds must not be empty");
+        for &threshold in &thresholds {
+            verify_range(threshold, 0.0, 1.0).unwrap();
+        }
+        thresholds.sort_by(f64::total_cmp);
+        thresholds.dedup();
+        let cells = thresholds.len() + 1;
+        Self {
+            thresholds,
+            positives: vec![0; cells],
+            negatives: vec![0; cells],
+            total: 0,
+        }
+    }
+
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+
+    pub fn best(&self, objective: ThresholdObjective) -> Option<ThresholdChoice> {
+        let mut best: Option<ThresholdChoice> = None;
+        for (threshold, counts) in self.compute()? {
+            if let Some(value) = objective.evaluate(&counts)
+                && best.is_none_or(|choice| value > choice.value)
+            {
+                best = Some(ThresholdChoice { threshold, value });
+            }
+        }
+        best
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for ThresholdSweep {
+    type Output = Vec<(f64, StatCounts)>;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            verify_range(prediction, 0.0, 1.0)?;
+            verify_binary_label(target)?;
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            let cell = self.thresholds.partition_point(|&t| t < prediction);
+            if target == 1 {
+                self.positives[cell] += 1;
+            } else {
+                self.negatives[cell] += 1;
+            }
+        }
+        self.total += predictions.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.positives.fill(0);
+        self.negatives.fill(0);
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        let total_pos: usize = self.positives.iter().sum();
+        let total_neg: usize = self.negatives.iter().sum();
+        let mut tp = total_pos;
+        let mut fp = total_neg;
+        let mut table = Vec::with_capacity(self.thresholds.len());
+        for (idx, &threshold) in self.thresholds.iter().enumerate() {
+            // samples in cell `idx` are not above threshold `idx`
+            tp -= self.positives[idx];
+            fp -= self.negatives[idx];
+            let counts = StatCounts::new(tp, fp, total_pos - tp, total_neg - fp);
+            table.push((threshold, counts));
+        }
+        Some(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ThresholdObjective, ThresholdSweep};
+    use crate::BinaryStatScores;
+    use crate::core::Metric;
+
+    #[test]
+    fn sweep_matches_fixed_threshold_counts() {
+        let preds = [0.12, 0.5, 0.33, 0.81, 0.67, 0.05, 0.92, 0.4];
+        let target = [0_usize, 1, 0, 1, 0, 0, 1, 1];
+
+        let mut sweep = ThresholdSweep::default();
+        sweep.update((&preds[..4], &target[..4])).unwrap();
+        sweep.update((&preds[4..], &target[4..])).unwrap();
+        for (threshold, counts) in sweep.compute().unwrap() {
+            let mut fixed = BinaryStatScores::new(threshold);
+            fixed.update((&preds, &target)).unwrap();
+            assert_eq!(fixed.compute().unwrap(), counts);
+        }
+
+        let youden = sweep.best(ThresholdObjective::Youden).unwrap();
+        assert_eq!(youden.threshold, 0.33);
+        assert_eq!(youden.value, 0.75);
+
+        sweep.reset();
+        assert_eq!(sweep.best(ThresholdObjective::F1), None);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::ContingencyTable;
+
+/// Dense label-by-cluster table returned by [`ContingencyMatrix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContingencyCounts {
+    /// Observed target labels in ascending order, one per row.
+    pub classes: Vec<usize>,
+    /// Observed predicted clusters in ascending order, one per column.
+    pub clusters: Vec<usize>,
+    /// `counts[i][j]` samples with target `classes[i]` assigned to cluster `clusters[j]`.
+    pub counts: Vec<Vec<usize>>,
+}
+
+/// Contingency matrix of target labels against predicted clusters, the table behind
+/// [`MutualInfoScore`](crate::MutualInfoScore) and the other pair-counting scores.
+///
+/// Arbitrary label ids are accepted; rows and columns only cover observed values and are sorted
+/// ascending, so the layout does not depend on the order samples arrive in.
+///
+/// ```
+/// use rust_metrics::{ContingencyMatrix, Metric};
+///
+/// let preds = [1, 1, 7, 7, 7];
+/// let target = [0, 0, 0, 4, 4];
+///
+/// let mut metric = ContingencyMatrix::default();
+/// metric.update((&preds, &target)).unwrap();
+/// let table = metric.compute().unwrap();
+/// assert_eq!(table.classes, vec![0, 4]);
+/// assert_eq!(table.clusters, vec![1, 7]);
+/// assert_eq!(table.counts, vec![vec![2, 1], vec![0, 2]]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContingencyMatrix {
+    contingency: ContingencyTable,
+}
+
+impl ContingencyMatrix {
+    pub fn new() -> Self {
+        Self {
+            contingency: ContingencyTable::new(),
+        }
+    }
+
+    /// Sparse accumulated counts.
+    pub fn table(&self) -> &ContingencyTable {
+        &self.contingency
+    }
+}
+
+impl Metric<(&[usize], &[usize])> for ContingencyMatrix {
+    type Output = ContingencyCounts;
+
+    fn update(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        self.contingency.update(targets, predictions)
+    }
+
+    fn reset(&mut self) {
+        self.contingency.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.contingency.is_empty() {
+            return None;
+        }
+        let classes: Vec<usize> = self.contingency.row_totals().keys().copied().collect();
+        let clusters: Vec<usize> = self.contingency.column_totals().keys().copied().collect();
+        let counts = classes
+            .iter()
+            .map(|&class| {
+                clusters
+                    .iter()
+                    .map(|&cluster| self.contingency.count(class, cluster))
+                    .collect()
+            })
+            .collect();
+        Some(ContingencyCounts {
+            classes,
+            clusters,
+            counts,
+        })
+    }
+}
+
+impl Revertible<(&[usize], &[usize])> for ContingencyMatrix {
+    fn revert(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        let mut batch = ContingencyTable::new();
+        batch.update(targets, predictions)?;
+        self.contingency.subtract(&batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContingencyMatrix;
+    use crate::core::{Metric, Revertible};
+
+    #[test]
+    fn contingency_matrix_ignores_arrival_order() {
+        let mut forward = ContingencyMatrix::default();
+        forward.update((&[3, 0, 3], &[1, 1, 2])).unwrap();
+        let mut backward = ContingencyMatrix::default();
+        backward.update((&[3], &[2])).unwrap();
+        backward.update((&[0, 3], &[1, 1])).unwrap();
+        assert_eq!(forward.compute(), backward.compute());
+        assert_eq!(
+            forward.compute().unwrap().counts,
+            vec![vec![1, 1], vec![0, 1]]
+        );
+
+        forward.revert((&[3], &[2])).unwrap();
+        let table = forward.compute().unwrap();
+        assert_eq!(table.classes, vec![1]);
+
+        forward.reset();
+        assert_eq!(forward.compute(), None);
+    }
+}
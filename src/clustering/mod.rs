@@ -3,6 +3,14 @@
 //! Every struct in this module implements [`Metric`](crate::core::Metric) and therefore supports
 //! batched updates plus `reset`/`compute` semantics.
 
+pub mod contingency_matrix;
 pub mod mutual_info_score;
+pub mod pair_confusion;
+pub mod purity;
+pub mod silhouette;
 
+pub use contingency_matrix::{ContingencyCounts, ContingencyMatrix};
 pub use mutual_info_score::MutualInfoScore;
+pub use pair_confusion::PairConfusionMatrix;
+pub use purity::ClusterPurity;
+pub use silhouette::{Distance, SilhouetteScore};
@@ -1,5 +1,5 @@
-use crate::core::{Metric, MetricError};
-use std::collections::HashMap;
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::ContingencyTable;
 
 /// Mutual information between predicted and target cluster assignments.
 ///
@@ -15,15 +15,13 @@ use std::collections::HashMap;
 /// ```
 #[derive(Debug, Clone, Default)]
 pub struct MutualInfoScore {
-    preds: Vec<usize>,
-    targets: Vec<usize>,
+    contingency: ContingencyTable,
 }
 
 impl MutualInfoScore {
     pub fn new() -> Self {
         Self {
-            preds: Vec::new(),
-            targets: Vec::new(),
+            contingency: ContingencyTable::new(),
         }
     }
 }
@@ -38,48 +36,26 @@ impl Metric<(&[usize], &[usize])> for MutualInfoScore {
                 targets: targets.len(),
             });
         }
-        self.preds.extend(predictions);
-        self.targets.extend(targets);
-
-        Ok(())
+        self.contingency.update(targets, predictions)
     }
 
     fn reset(&mut self) {
-        self.preds.clear();
-        self.targets.clear();
+        self.contingency.clear();
     }
 
     fn compute(&self) -> Option<Self::Output> {
-        if self.preds.is_empty() {
+        if self.contingency.is_empty() {
             return None;
         }
-        let total = self.preds.len() as f64;
-
-        let mut joint_counts: HashMap<(usize, usize), usize> = HashMap::new();
-        for (&target, &pred) in self.targets.iter().zip(self.preds.iter()) {
-            *joint_counts.entry((target, pred)).or_insert(0) += 1;
-        }
-
-        let mut target_counts: HashMap<usize, usize> = HashMap::new();
-        let mut pred_counts: HashMap<usize, usize> = HashMap::new();
-        for &t in self.targets.iter() {
-            *target_counts.entry(t).or_insert(0) += 1;
-        }
-        for &p in self.preds.iter() {
-            *pred_counts.entry(p).or_insert(0) += 1;
-        }
-
-        let mut mi = 0.0;
-        for ((target, pred), &count) in joint_counts.iter() {
-            let count = count as f64;
-            let target_count = *target_counts.get(target)? as f64;
-            let pred_count = *pred_counts.get(pred)? as f64;
-
-            let term = (count / total) * ((total * count) / (target_count * pred_count)).ln();
-            mi += term;
-        }
+        Some(self.contingency.mutual_information())
+    }
+}
 
-        Some(mi)
+impl Revertible<(&[usize], &[usize])> for MutualInfoScore {
+    fn revert(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        let mut batch = ContingencyTable::new();
+        batch.update(targets, predictions)?;
+        self.contingency.subtract(&batch)
     }
 }
 
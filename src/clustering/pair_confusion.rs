@@ -0,0 +1,104 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::ContingencyTable;
+
+/// Pair confusion matrix between target labels and predicted clusters, the counts behind the
+/// Rand-family indices.
+///
+/// Every ordered pair of distinct samples is classified by whether it shares a target label (row)
+/// and whether it shares a predicted cluster (column): `[0][0]` pairs are split in both,
+/// `[0][1]` are merged only by the clustering, `[1][0]` are split only by the clustering, and
+/// `[1][1]` are together in both. Like scikit-learn, counts are over ordered pairs, so the matrix
+/// sums to `n * (n - 1)`.
+///
+/// ```
+/// use rust_metrics::{Metric, PairConfusionMatrix};
+///
+/// let preds = [0, 0, 1, 1];
+/// let target = [0, 0, 1, 2];
+///
+/// let mut metric = PairConfusionMatrix::default();
+/// metric.update((&preds, &target)).unwrap();
+/// assert_eq!(metric.compute(), Some([[8, 2], [0, 2]]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PairConfusionMatrix {
+    contingency: ContingencyTable,
+}
+
+impl PairConfusionMatrix {
+    pub fn new() -> Self {
+        Self {
+            contingency: ContingencyTable::new(),
+        }
+    }
+}
+
+impl Metric<(&[usize], &[usize])> for PairConfusionMatrix {
+    type Output = [[usize; 2]; 2];
+
+    fn update(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        self.contingency.update(targets, predictions)
+    }
+
+    fn reset(&mut self) {
+        self.contingency.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.contingency.is_empty() {
+            return None;
+        }
+        let n = self.contingency.total();
+        let sum_squares: usize = self.contingency.cells().map(|(_, c)| c * c).sum();
+        let same_class: usize = self.contingency.row_totals().values().map(|c| c * c).sum();
+        let same_cluster: usize = self
+            .contingency
+            .column_totals()
+            .values()
+            .map(|c| c * c)
+            .sum();
+
+        let together = sum_squares - n;
+        let merged_only = same_cluster - sum_squares;
+        let split_only = same_class - sum_squares;
+        let apart = n * n - same_cluster - same_class + sum_squares;
+        Some([[apart, merged_only], [split_only, together]])
+    }
+}
+
+impl Revertible<(&[usize], &[usize])> for PairConfusionMatrix {
+    fn revert(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        let mut batch = ContingencyTable::new();
+        batch.update(targets, predictions)?;
+        self.contingency.subtract(&batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PairConfusionMatrix;
+    use crate::core::Metric;
+
+    #[test]
+    fn pair_confusion_over_batches() {
+        let mut metric = PairConfusionMatrix::default();
+        metric.update((&[0, 0], &[0, 0])).unwrap();
+        metric.update((&[1, 1], &[1, 2])).unwrap();
+        let matrix = metric.compute().unwrap();
+        assert_eq!(matrix, [[8, 2], [0, 2]]);
+        assert_eq!(matrix.iter().flatten().sum::<usize>(), 4 * 3);
+
+        metric.reset();
+        metric.update((&[5, 5, 9, 9], &[1, 1, 0, 0])).unwrap();
+        assert_eq!(metric.compute(), Some([[8, 0], [0, 4]]));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::ContingencyTable;
+
+/// Cluster purity: the fraction of samples that belong to the majority target class of their
+/// predicted cluster.
+///
+/// Purity rewards many small clusters (one cluster per sample scores `1.0`), so read it next to
+/// the number of clusters or a score such as [`MutualInfoScore`](crate::MutualInfoScore).
+///
+/// ```
+/// use rust_metrics::{ClusterPurity, Metric};
+///
+/// let preds = [0, 0, 0, 1, 1, 1];
+/// let target = [0, 0, 1, 1, 1, 2];
+///
+/// let mut metric = ClusterPurity::default();
+/// metric.update((&preds, &target)).unwrap();
+/// assert!((metric.compute().unwrap() - 4.0 / 6.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClusterPurity {
+    contingency: ContingencyTable,
+}
+
+impl ClusterPurity {
+    pub fn new() -> Self {
+        Self {
+            contingency: ContingencyTable::new(),
+        }
+    }
+}
+
+impl Metric<(&[usize], &[usize])> for ClusterPurity {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        self.contingency.update(targets, predictions)
+    }
+
+    fn reset(&mut self) {
+        self.contingency.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.contingency.is_empty() {
+            return None;
+        }
+        let mut majority: BTreeMap<usize, usize> = BTreeMap::new();
+        for ((_, cluster), count) in self.contingency.cells() {
+            let best = majority.entry(cluster).or_insert(0);
+            *best = (*best).max(count);
+        }
+        let majority_total: usize = majority.values().sum();
+        Some(majority_total as f64 / self.contingency.total() as f64)
+    }
+}
+
+impl Revertible<(&[usize], &[usize])> for ClusterPurity {
+    fn revert(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        let mut batch = ContingencyTable::new();
+        batch.update(targets, predictions)?;
+        self.contingency.subtract(&batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClusterPurity;
+    use crate::core::{Metric, Revertible};
+
+    #[test]
+    fn purity_over_batches() {
+        let mut metric = ClusterPurity::default();
+        metric.update((&[0, 0, 0], &[0, 0, 1])).unwrap();
+        metric.update((&[1, 1, 1], &[1, 1, 2])).unwrap();
+        assert!((metric.compute().unwrap() - 4.0 / 6.0).abs() < f64::EPSILON);
+
+        metric.revert((&[0, 1], &[1, 2])).unwrap();
+        assert_eq!(metric.compute(), Some(1.0));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::{SampleBuffer, verify_range};
+
+/// Distance between feature vectors used by [`SilhouetteScore`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Distance {
+    #[default]
+    Euclidean,
+    /// `1 - cosine similarity`; zero vectors are treated as orthogonal to everything.
+    Cosine,
+}
+
+impl Distance {
+    fn between(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            Distance::Euclidean => a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f64>()
+                .sqrt(),
+            Distance::Cosine => {
+                let dot: f64 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                let norm_a = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+                let norm_b = b.iter().map(|x| x * x).sum::<f64>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 {
+                    1.0
+                } else {
+                    1.0 - dot / (norm_a * norm_b)
+                }
+            }
+        }
+    }
+}
+
+/// Mean silhouette coefficient of clustered feature vectors, an internal validation score that
+/// needs no ground-truth classes.
+///
+/// For each point, `a` is its mean distance to the rest of its cluster and `b` the smallest mean
+/// distance to another cluster; its silhouette is `(b - a) / max(a, b)`, or `0` for singleton
+/// clusters. Points are kept until `compute`, which is quadratic in their number;
+/// [`SilhouetteScore::with_max_samples`] caps the cost by scoring an evenly strided subsample.
+/// `None` unless there are between 2 and `n - 1` clusters.
+///
+/// ```
+/// use rust_metrics::{Metric, SilhouetteScore};
+///
+/// let points: [&[f64]; 4] = [&[0.0, 0.0], &[0.0, 1.0], &[5.0, 0.0], &[5.0, 1.0]];
+/// let labels = [0, 0, 1, 1];
+///
+/// let mut metric = SilhouetteScore::default();
+/// metric.update((&points, &labels)).unwrap();
+/// let b = (5.0 + 26.0_f64.sqrt()) / 2.0;
+/// assert!((metric.compute().unwrap() - (b - 1.0) / b).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SilhouetteScore {
+    distance: Distance,
+    max_samples: Option<usize>,
+    dimension: Option<usize>,
+    points: SampleBuffer<(Vec<f64>, usize)>,
+}
+
+impl SilhouetteScore {
+    pub fn new(distance: Distance) -> Self {
+        Self {
+            distance,
+            max_samples: None,
+            dimension: None,
+            points: SampleBuffer::new(),
+        }
+    }
+
+    /// Score at most `max_samples` points, taken at evenly spaced positions of the stream.
+    pub fn with_max_samples(mut self, max_samples: usize) -> Self {
+        assert!(max_samples >= 2, "max_samples must be at least 2");
+        self.max_samples = Some(max_samples);
+        self
+    }
+}
+
+impl Metric<(&[&[f64]], &[usize])> for SilhouetteScore {
+    type Output = f64;
+
+    fn update(&mut self, (features, labels): (&[&[f64]], &[usize])) -> Result<(), MetricError> {
+        if features.len() != labels.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: features.len(),
+                targets: labels.len(),
+            });
+        }
+        let mut dimension = self.dimension;
+        for &point in features {
+            let expected = *dimension.get_or_insert(point.len());
+            if point.len() != expected || expected == 0 {
+                return Err(MetricError::IncompatibleInput {
+                    expected: format!("non-empty feature vectors of length {}", expected),
+                    got: format!("length {}", point.len()),
+                });
+            }
+            for &value in point {
+                verify_range(value, f64::MIN, f64::MAX)?;
+            }
+        }
+        self.dimension = dimension;
+        self.points.push_batch(
+            features
+                .iter()
+                .zip(labels)
+                .map(|(point, &label)| (point.to_vec(), label))
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.points.clear();
+        self.dimension = None;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let mut points = self.points.to_vec();
+        if let Some(max_samples) = self.max_samples
+            && points.len() > max_samples
+        {
+            let stride = points.len() as f64 / max_samples as f64;
+            points = (0..max_samples)
+                .map(|i| points[(i as f64 * stride) as usize].clone())
+                .collect();
+        }
+
+        let mut cluster_sizes: BTreeMap<usize, usize> = BTreeMap::new();
+        for (_, label) in &points {
+            *cluster_sizes.entry(*label).or_insert(0) += 1;
+        }
+        if cluster_sizes.len() < 2 || cluster_sizes.len() >= points.len() {
+            return None;
+        }
+
+        let mut total = 0.0;
+        for (i, (point, label)) in points.iter().enumerate() {
+            let mut distance_sums: BTreeMap<usize, f64> = BTreeMap::new();
+            for (j, (other, other_label)) in points.iter().enumerate() {
+                if i != j {
+                    *distance_sums.entry(*other_label).or_insert(0.0) +=
+                        self.distance.between(point, other);
+                }
+            }
+            let own_size = cluster_sizes[label];
+            if own_size == 1 {
+                continue;
+            }
+            let a = distance_sums.get(label).copied().unwrap_or(0.0) / (own_size - 1) as f64;
+            let b = distance_sums
+                .iter()
+                .filter(|(other_label, _)| *other_label != label)
+                .map(|(other_label, sum)| sum / cluster_sizes[other_label] as f64)
+                .fold(f64::INFINITY, f64::min);
+            let denominator = a.max(b);
+            if denominator > 0.0 {
+                total += (b - a) / denominator;
+            }
+        }
+        Some(total / points.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Distance, SilhouetteScore};
+    use crate::core::Metric;
+
+    #[test]
+    fn silhouette_over_batches() {
+        let points: [&[f64]; 5] = [
+            &[1.0, 0.0][..],
+            &[2.0, 0.1][..],
+            &[0.0, 1.0][..],
+            &[0.1, 3.0][..],
+            &[-1.0, -1.0][..],
+        ];
+        let labels = [0, 0, 1, 1, 2];
+
+        let mut cosine = SilhouetteScore::new(Distance::Cosine);
+        cosine.update((&points[..2], &labels[..2])).unwrap();
+        assert_eq!(cosine.compute(), None);
+        cosine.update((&points[2..], &labels[2..])).unwrap();
+        let score = cosine.compute().unwrap();
+        // both real clusters point in nearly one direction; the singleton scores 0
+        assert!(score > 0.5 && score < 4.0 / 5.0);
+
+        let mut capped = SilhouetteScore::default().with_max_samples(4);
+        capped.update((&points, &labels)).unwrap();
+        assert!(capped.compute().is_some());
+        assert!(capped.update((&[&[1.0][..]], &[0])).is_err());
+
+        cosine.reset();
+        assert_eq!(cosine.compute(), None);
+    }
+}
@@ -5,6 +5,8 @@ pub enum MetricError {
     LengthMismatch { predictions: usize, targets: usize },
     /// Inputs fail additional validation (value ranges, binary labels, etc.).
     IncompatibleInput { expected: String, got: String },
+    /// An external backend, such as an embedding model, failed to load or run.
+    Backend(String),
 }
 
 /// Common interface implemented by every streaming metric.
@@ -24,3 +26,50 @@ pub trait Metric<Input> {
     /// Compute the final value; returns `None` until at least one batch was seen.
     fn compute(&self) -> Option<Self::Output>;
 }
+
+/// Metrics whose state is a sum of per-sample contributions, so a previously observed batch can be
+/// removed again without replaying the whole stream.
+///
+/// ```
+/// use rust_metrics::{BinaryAccuracy, Metric, Revertible};
+///
+/// let mut metric = BinaryAccuracy::default();
+/// metric.update((&[0.9, 0.1], &[1, 0])).unwrap();
+/// metric.update((&[0.9, 0.9], &[0, 0])).unwrap();
+/// assert_eq!(metric.compute(), Some(0.5));
+///
+/// metric.revert((&[0.9, 0.9], &[0, 0])).unwrap();
+/// assert_eq!(metric.compute(), Some(1.0));
+/// ```
+pub trait Revertible<Input>: Metric<Input> {
+    /// Remove a batch previously passed to [`update`](Metric::update).
+    ///
+    /// Fails without modifying the state if the batch could not have been observed, e.g. when it
+    /// holds more samples of some kind than were accumulated. Float-valued state is subtracted, so
+    /// the result may differ from a fresh replay by rounding error.
+    fn revert(&mut self, input: Input) -> Result<(), MetricError>;
+}
+
+/// Metrics that can freeze their accumulated state into a cheap, immutable snapshot.
+///
+/// Snapshots are `Send + Sync`, so an expensive final computation can run on another thread
+/// while the original metric keeps receiving updates.
+///
+/// ```
+/// use rust_metrics::{BinaryAuroc, Metric, Snapshot};
+///
+/// let mut auroc = BinaryAuroc::new(0);
+/// auroc.update((&[0.0, 0.5, 0.7, 0.8], &[0, 1, 1, 0])).unwrap();
+///
+/// let snapshot = auroc.snapshot();
+/// let handle = std::thread::spawn(move || snapshot.compute());
+/// auroc.update((&[0.9], &[1])).unwrap();
+///
+/// assert_eq!(handle.join().unwrap(), Some(0.5));
+/// ```
+pub trait Snapshot {
+    type Snapshot: Send + Sync + 'static;
+
+    /// Capture the current state without copying stored samples.
+    fn snapshot(&self) -> Self::Snapshot;
+}
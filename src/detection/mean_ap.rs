@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::verify_range;
+
+/// Recall points of the COCO 101-point interpolated precision-recall curve.
+const RECALL_POINTS: usize = 101;
+
+/// Predicted boxes of one image as `[x_min, y_min, x_max, y_max]`, with a confidence score and a
+/// class label per box.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageDetections<'a> {
+    pub boxes: &'a [[f64; 4]],
+    pub scores: &'a [f64],
+    pub labels: &'a [usize],
+}
+
+/// Ground-truth boxes of one image as `[x_min, y_min, x_max, y_max]`, with a class label per box.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageTargets<'a> {
+    pub boxes: &'a [[f64; 4]],
+    pub labels: &'a [usize],
+}
+
+/// Value of a [`MeanAveragePrecision`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectionScores {
+    /// AP averaged over classes and all IoU thresholds.
+    pub map: f64,
+    /// AP at IoU `0.5`; `None` unless it is one of the thresholds.
+    pub map_50: Option<f64>,
+    /// AP at IoU `0.75`; `None` unless it is one of the thresholds.
+    pub map_75: Option<f64>,
+    /// AP of every class with ground truth, averaged over the IoU thresholds.
+    pub per_class: BTreeMap<usize, f64>,
+}
+
+/// COCO-style mean average precision for object detection.
+///
+/// Each update takes the detections and ground truths of a batch of images. Within an image and
+/// class, detections are matched greedily by descending score to the unmatched ground truth they
+/// overlap most, counting as true positives when the IoU reaches the threshold; only the
+/// `max_detections` (default `100`) highest-scored detections per image and class are kept. AP
+/// is the 101-point interpolated area under each class's precision-recall curve, averaged over
+/// classes with ground truth and over the IoU thresholds (default `0.50:0.05:0.95`).
+///
+/// Unlike pycocotools, there are no crowd annotations or per-area breakdowns. Matching results
+/// are stored per detection, so memory grows with the number of detections.
+///
+/// ```
+/// use rust_metrics::{ImageDetections, ImageTargets, MeanAveragePrecision, Metric};
+///
+/// let detections = ImageDetections {
+///     boxes: &[[258.0, 41.0, 606.0, 285.0]],
+///     scores: &[0.536],
+///     labels: &[0],
+/// };
+/// let targets = ImageTargets {
+///     boxes: &[[214.0, 41.0, 562.0, 285.0]],
+///     labels: &[0],
+/// };
+///
+/// let mut metric = MeanAveragePrecision::default();
+/// metric.update((&[detections], &[targets])).unwrap();
+/// let scores = metric.compute().unwrap();
+/// assert!((scores.map - 0.6).abs() < 1e-12);
+/// assert_eq!(scores.map_50, Some(1.0));
+/// assert_eq!(scores.map_75, Some(1.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeanAveragePrecision {
+    iou_thresholds: Vec<f64>,
+    max_detections: usize,
+    classes: BTreeMap<usize, ClassRecords>,
+}
+
+/// Matching results of one class: the ground-truth count and, per detection, its score and
+/// whether it was a true positive at each IoU threshold.
+#[derive(Debug, Clone, Default)]
+struct ClassRecords {
+    ground_truths: usize,
+    detections: Vec<(f64, Vec<bool>)>,
+}
+
+impl Default for MeanAveragePrecision {
+    fn default() -> Self {
+        Self::new((0..10).map(|step| 0.5 + 0.05 * step as f64).collect())
+    }
+}
+
+impl MeanAveragePrecision {
+    pub fn new(iou_thresholds: Vec<f64>) -> Self {
+        assert!(
+            !iou_thresholds.is_empty(),
+            "at least one IoU threshold is required"
+        );
+        assert!(
+            iou_thresholds
+                .iter()
+                .all(|threshold| (0.0..=1.0).contains(threshold)),
+            "IoU thresholds must lie in [0, 1]"
+        );
+        Self {
+            iou_thresholds,
+            max_detections: 100,
+            classes: BTreeMap::new(),
+        }
+    }
+
+    /// Keep at most `max_detections` detections per image and class.
+    pub fn with_max_detections(mut self, max_detections: usize) -> Self {
+        assert!(max_detections > 0, "max_detections must be positive");
+        self.max_detections = max_detections;
+        self
+    }
+
+    fn match_image(&mut self, detections: &ImageDetections, targets: &ImageTargets) {
+        let mut labels: Vec<usize> = detections
+            .labels
+            .iter()
+            .chain(targets.labels)
+            .copied()
+            .collect();
+        labels.sort_unstable();
+        labels.dedup();
+
+        for label in labels {
+            let ground_truths: Vec<[f64; 4]> = targets
+                .boxes
+                .iter()
+                .zip(targets.labels)
+                .filter(|&(_, &l)| l == label)
+                .map(|(&bbox, _)| bbox)
+                .collect();
+            let mut predicted: Vec<(f64, [f64; 4])> = detections
+                .boxes
+                .iter()
+                .zip(detections.scores)
+                .zip(detections.labels)
+                .filter(|&(_, &l)| l == label)
+                .map(|((&bbox, &score), _)| (score, bbox))
+                .collect();
+            predicted.sort_by(|a, b| b.0.total_cmp(&a.0));
+            predicted.truncate(self.max_detections);
+
+            let records = self.classes.entry(label).or_default();
+            records.ground_truths += ground_truths.len();
+            let mut matched = vec![vec![false; ground_truths.len()]; self.iou_thresholds.len()];
+            for (score, bbox) in predicted {
+                let ious: Vec<f64> = ground_truths.iter().map(|gt| box_iou(&bbox, gt)).collect();
+                let hits = self
+                    .iou_thresholds
+                    .iter()
+                    .zip(matched.iter_mut())
+                    .map(|(&threshold, matched)| {
+                        let best = (0..ious.len())
+                            .filter(|&gt| !matched[gt] && ious[gt] >= threshold)
+                            .max_by(|&a, &b| ious[a].total_cmp(&ious[b]).then(b.cmp(&a)));
+                        if let Some(gt) = best {
+                            matched[gt] = true;
+                        }
+                        best.is_some()
+                    })
+                    .collect();
+                records.detections.push((score, hits));
+            }
+        }
+    }
+}
+
+impl Metric<(&[ImageDetections<'_>], &[ImageTargets<'_>])> for MeanAveragePrecision {
+    type Output = DetectionScores;
+
+    fn update(
+        &mut self,
+        (detections, targets): (&[ImageDetections<'_>], &[ImageTargets<'_>]),
+    ) -> Result<(), MetricError> {
+        if detections.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: detections.len(),
+                targets: targets.len(),
+            });
+        }
+        for (image_detections, image_targets) in detections.iter().zip(targets) {
+            verify_image(
+                image_detections.boxes,
+                image_detections.labels,
+                Some(image_detections.scores),
+            )?;
+            verify_image(image_targets.boxes, image_targets.labels, None)?;
+        }
+        for (image_detections, image_targets) in detections.iter().zip(targets) {
+            self.match_image(image_detections, image_targets);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.classes.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        // per_threshold[t][c]: AP of the c-th class with ground truth at threshold t
+        let mut per_threshold = vec![Vec::new(); self.iou_thresholds.len()];
+        let mut per_class = BTreeMap::new();
+        for (&label, records) in &self.classes {
+            if records.ground_truths == 0 {
+                continue;
+            }
+            let mut order: Vec<usize> = (0..records.detections.len()).collect();
+            order.sort_by(|&a, &b| records.detections[b].0.total_cmp(&records.detections[a].0));
+
+            let mut class_sum = 0.0;
+            for (threshold, aps) in per_threshold.iter_mut().enumerate() {
+                let hits: Vec<bool> = order
+                    .iter()
+                    .map(|&idx| records.detections[idx].1[threshold])
+                    .collect();
+                let ap = interpolated_average_precision(&hits, records.ground_truths);
+                aps.push(ap);
+                class_sum += ap;
+            }
+            per_class.insert(label, class_sum / self.iou_thresholds.len() as f64);
+        }
+        if per_class.is_empty() {
+            return None;
+        }
+
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+        let map_at = |iou: f64| {
+            self.iou_thresholds
+                .iter()
+                .position(|&threshold| (threshold - iou).abs() < 1e-9)
+                .map(|threshold| mean(&per_threshold[threshold]))
+        };
+        Some(DetectionScores {
+            map: mean(&per_class.values().copied().collect::<Vec<f64>>()),
+            map_50: map_at(0.5),
+            map_75: map_at(0.75),
+            per_class,
+        })
+    }
+}
+
+fn verify_image(
+    boxes: &[[f64; 4]],
+    labels: &[usize],
+    scores: Option<&[f64]>,
+) -> Result<(), MetricError> {
+    if labels.len() != boxes.len() {
+        return Err(MetricError::LengthMismatch {
+            predictions: boxes.len(),
+            targets: labels.len(),
+        });
+    }
+    if let Some(scores) = scores {
+        if scores.len() != boxes.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: boxes.len(),
+                targets: scores.len(),
+            });
+        }
+        for &score in scores {
+            verify_range(score, f64::MIN, f64::MAX)?;
+        }
+    }
+    for bbox in boxes {
+        for &coordinate in bbox {
+            verify_range(coordinate, f64::MIN, f64::MAX)?;
+        }
+        if bbox[2] < bbox[0] || bbox[3] < bbox[1] {
+            return Err(MetricError::IncompatibleInput {
+                expected: "boxes as [x_min, y_min, x_max, y_max]".to_string(),
+                got: format!("{:?}", bbox),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Intersection over union of two `[x_min, y_min, x_max, y_max]` boxes.
+fn box_iou(a: &[f64; 4], b: &[f64; 4]) -> f64 {
+    let width = (a[2].min(b[2]) - a[0].max(b[0])).max(0.0);
+    let height = (a[3].min(b[3]) - a[1].max(b[1])).max(0.0);
+    let intersection = width * height;
+    let area = |bbox: &[f64; 4]| (bbox[2] - bbox[0]) * (bbox[3] - bbox[1]);
+    let union = area(a) + area(b) - intersection;
+    if union <= 0.0 {
+        return 0.0;
+    }
+    intersection / union
+}
+
+/// COCO 101-point interpolated AP of detections ranked by descending score.
+fn interpolated_average_precision(hits: &[bool], ground_truths: usize) -> f64 {
+    let mut recall = Vec::with_capacity(hits.len());
+    let mut precision = Vec::with_capacity(hits.len());
+    let mut true_positives = 0usize;
+    for (rank, &hit) in hits.iter().enumerate() {
+        true_positives += usize::from(hit);
+        recall.push(true_positives as f64 / ground_truths as f64);
+        precision.push(true_positives as f64 / (rank + 1) as f64);
+    }
+    // precision envelope: the best precision at any higher recall
+    for idx in (1..precision.len()).rev() {
+        precision[idx - 1] = precision[idx - 1].max(precision[idx]);
+    }
+
+    let mut sum = 0.0;
+    for point in 0..RECALL_POINTS {
+        let recall_threshold = point as f64 / (RECALL_POINTS - 1) as f64;
+        let idx = recall.partition_point(|&r| r < recall_threshold);
+        if idx < precision.len() {
+            sum += precision[idx];
+        }
+    }
+    sum / RECALL_POINTS as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageDetections, ImageTargets, MeanAveragePrecision, box_iou};
+    use crate::core::Metric;
+
+    #[test]
+    fn iou_of_overlapping_boxes() {
+        let a = [0.0, 0.0, 2.0, 2.0];
+        assert_eq!(box_iou(&a, &[1.0, 0.0, 3.0, 2.0]), 1.0 / 3.0);
+        assert_eq!(box_iou(&a, &[5.0, 5.0, 6.0, 6.0]), 0.0);
+    }
+
+    #[test]
+    fn duplicates_and_misses_lower_precision_and_recall() {
+        let detections = [
+            ImageDetections {
+                boxes: &[[0.0, 0.0, 10.0, 10.0], [0.0, 0.0, 10.0, 10.0]],
+                scores: &[0.9, 0.8],
+                labels: &[0, 0],
+            },
+            ImageDetections {
+                boxes: &[],
+                scores: &[],
+                labels: &[],
+            },
+        ];
+        let targets = [
+            ImageTargets {
+                boxes: &[[0.0, 0.0, 10.0, 10.0]],
+                labels: &[0],
+            },
+            ImageTargets {
+                boxes: &[[0.0, 0.0, 5.0, 5.0]],
+                labels: &[0],
+            },
+        ];
+
+        let mut metric = MeanAveragePrecision::new(vec![0.5]);
+        metric.update((&detections, &targets)).unwrap();
+        // the duplicate is a false positive and half the ground truths are found: precision 1
+        // up to recall 0.5, so 51 of the 101 recall points score 1
+        let scores = metric.compute().unwrap();
+        assert!((scores.map - 51.0 / 101.0).abs() < 1e-12);
+        assert_eq!(scores.map_75, None);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
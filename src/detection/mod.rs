@@ -0,0 +1,8 @@
+//! Object detection metrics over per-image bounding boxes.
+//!
+//! All types implement [`Metric`](crate::core::Metric) for batched updates, taking the
+//! detections and ground truths of each image in a batch.
+
+pub mod mean_ap;
+
+pub use mean_ap::{DetectionScores, ImageDetections, ImageTargets, MeanAveragePrecision};
@@ -0,0 +1,10 @@
+//! Distribution drift metrics comparing streamed production data with a fixed reference sample.
+//!
+//! All types implement [`Metric`](crate::core::Metric): the reference is captured at
+//! construction and every update streams more production values.
+
+pub mod psi;
+pub mod statistical;
+
+pub use psi::PopulationStabilityIndex;
+pub use statistical::{ChiSquaredDrift, DriftTestResult, KolmogorovSmirnovDrift};
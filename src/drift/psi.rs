@@ -0,0 +1,169 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::stats::interpolated_quantile;
+use crate::utils::verify_range;
+
+/// Floor applied to bin proportions so empty bins do not produce infinite terms.
+const MIN_PROPORTION: f64 = 1e-4;
+
+/// Population stability index between a reference sample and streamed production values.
+///
+/// Bins are fixed at construction, either at the reference quantiles
+/// ([`from_reference`](PopulationStabilityIndex::from_reference)) or at explicit interior edges
+/// ([`from_edges`](PopulationStabilityIndex::from_edges)); the outermost bins are open-ended.
+/// The index is `sum_i (a_i - e_i) ln(a_i / e_i)` over the production (`a`) and reference (`e`)
+/// bin proportions, each floored at `1e-4`. Values below `0.1` are commonly read as stable and
+/// above `0.25` as a significant shift.
+///
+/// ```
+/// use rust_metrics::{Metric, PopulationStabilityIndex};
+///
+/// let reference: Vec<f64> = (0..100).map(f64::from).collect();
+/// let mut psi = PopulationStabilityIndex::from_reference(&reference, 4).unwrap();
+/// assert_eq!(psi.edges(), &[24.75, 49.5, 74.25]);
+///
+/// psi.update(&reference).unwrap();
+/// assert_eq!(psi.compute(), Some(0.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PopulationStabilityIndex {
+    edges: Vec<f64>,
+    expected: Vec<f64>,
+    counts: Vec<usize>,
+    total: usize,
+}
+
+impl PopulationStabilityIndex {
+    /// Bin at `bins` equal-frequency quantiles of `reference`; duplicate edges caused by ties
+    /// are merged, so fewer bins may result.
+    pub fn from_reference(reference: &[f64], bins: usize) -> Result<Self, MetricError> {
+        assert!(bins >= 2, "bins must be at least 2");
+        verify_reference(reference)?;
+        let mut sorted = reference.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let mut edges: Vec<f64> = (1..bins)
+            .map(|bin| interpolated_quantile(&sorted, bin as f64 / bins as f64))
+            .collect();
+        edges.dedup();
+        Self::from_edges(edges, reference)
+    }
+
+    /// Bin at the given strictly increasing interior `edges`; a value `v` falls in the first bin
+    /// whose upper edge is greater than `v`.
+    pub fn from_edges(edges: Vec<f64>, reference: &[f64]) -> Result<Self, MetricError> {
+        verify_reference(reference)?;
+        for &edge in &edges {
+            verify_range(edge, f64::MIN, f64::MAX)?;
+        }
+        if edges.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(MetricError::IncompatibleInput {
+                expected: "strictly increasing bin edges".to_string(),
+                got: format!("{:?}", edges),
+            });
+        }
+
+        let mut reference_counts = vec![0; edges.len() + 1];
+        for &value in reference {
+            reference_counts[bin_index(&edges, value)] += 1;
+        }
+        let expected = reference_counts
+            .iter()
+            .map(|&count| count as f64 / reference.len() as f64)
+            .collect();
+        Ok(Self {
+            counts: vec![0; edges.len() + 1],
+            edges,
+            expected,
+            total: 0,
+        })
+    }
+
+    /// Interior bin edges.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+}
+
+impl Metric<&[f64]> for PopulationStabilityIndex {
+    type Output = f64;
+
+    fn update(&mut self, values: &[f64]) -> Result<(), MetricError> {
+        for &value in values {
+            verify_range(value, f64::MIN, f64::MAX)?;
+        }
+        for &value in values {
+            self.counts[bin_index(&self.edges, value)] += 1;
+        }
+        self.total += values.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.counts.fill(0);
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        let psi = self
+            .counts
+            .iter()
+            .zip(&self.expected)
+            .map(|(&count, &expected)| {
+                let actual = (count as f64 / self.total as f64).max(MIN_PROPORTION);
+                let expected = expected.max(MIN_PROPORTION);
+                (actual - expected) * (actual / expected).ln()
+            })
+            .sum();
+        Some(psi)
+    }
+}
+
+fn verify_reference(reference: &[f64]) -> Result<(), MetricError> {
+    if reference.is_empty() {
+        return Err(MetricError::IncompatibleInput {
+            expected: "a non-empty reference sample".to_string(),
+            got: "0 values".to_string(),
+        });
+    }
+    for &value in reference {
+        verify_range(value, f64::MIN, f64::MAX)?;
+    }
+    Ok(())
+}
+
+fn bin_index(edges: &[f64], value: f64) -> usize {
+    edges.partition_point(|&edge| edge <= value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PopulationStabilityIndex;
+    use crate::core::Metric;
+
+    #[test]
+    fn psi_detects_shift() {
+        let reference = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let mut psi = PopulationStabilityIndex::from_edges(vec![4.5], &reference).unwrap();
+        psi.update(&[1.0, 2.0, 3.0]).unwrap();
+        psi.update(&[9.0]).unwrap();
+
+        // actual [0.75, 0.25] vs expected [0.5, 0.5]
+        let expected = 0.25 * 1.5_f64.ln() - 0.25 * 0.5_f64.ln();
+        assert!((psi.compute().unwrap() - expected).abs() < 1e-12);
+
+        psi.reset();
+        assert_eq!(psi.compute(), None);
+    }
+
+    #[test]
+    fn psi_validates_configuration() {
+        assert!(PopulationStabilityIndex::from_edges(vec![2.0, 1.0], &[1.0]).is_err());
+        assert!(PopulationStabilityIndex::from_reference(&[], 10).is_err());
+
+        let psi = PopulationStabilityIndex::from_reference(&[1.0, 1.0, 1.0, 2.0], 4).unwrap();
+        assert_eq!(psi.edges(), &[1.0, 1.25]);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{chi_squared_sf, kolmogorov_sf, verify_label, verify_range};
+
+/// Test statistic and p-value of a two-sample drift test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DriftTestResult {
+    pub statistic: f64,
+    /// Probability of a statistic at least this extreme if both samples share one distribution.
+    pub p_value: f64,
+}
+
+/// Pearson chi-squared test of homogeneity between reference category counts and streamed
+/// categorical values.
+///
+/// Categories are indices `0..reference_counts.len()`. Categories unseen in both samples are
+/// dropped, leaving `k - 1` degrees of freedom for `k` observed categories.
+///
+/// ```
+/// use rust_metrics::{ChiSquaredDrift, Metric};
+///
+/// let mut drift = ChiSquaredDrift::from_reference_counts(vec![50, 50]).unwrap();
+/// let production: Vec<usize> = (0..100).map(|i| usize::from(i >= 30)).collect();
+/// drift.update(&production).unwrap();
+///
+/// let result = drift.compute().unwrap();
+/// assert!((result.statistic - 25.0 / 3.0).abs() < 1e-12);
+/// assert!(result.p_value < 0.01);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChiSquaredDrift {
+    reference_counts: Vec<usize>,
+    counts: Vec<usize>,
+    total: usize,
+}
+
+impl ChiSquaredDrift {
+    pub fn from_reference_counts(reference_counts: Vec<usize>) -> Result<Self, MetricError> {
+        if reference_counts.iter().sum::<usize>() == 0 {
+            return Err(MetricError::IncompatibleInput {
+                expected: "at least one reference observation".to_string(),
+                got: format!("{:?}", reference_counts),
+            });
+        }
+        Ok(Self {
+            counts: vec![0; reference_counts.len()],
+            reference_counts,
+            total: 0,
+        })
+    }
+}
+
+impl Metric<&[usize]> for ChiSquaredDrift {
+    type Output = DriftTestResult;
+
+    fn update(&mut self, categories: &[usize]) -> Result<(), MetricError> {
+        for &category in categories {
+            verify_label(category, self.counts.len())?;
+        }
+        for &category in categories {
+            self.counts[category] += 1;
+        }
+        self.total += categories.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.counts.fill(0);
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        let reference_total = self.reference_counts.iter().sum::<usize>() as f64;
+        let production_total = self.total as f64;
+        let grand_total = reference_total + production_total;
+
+        let mut statistic = 0.0;
+        let mut observed_categories = 0;
+        for (&reference, &production) in self.reference_counts.iter().zip(&self.counts) {
+            let category_total = (reference + production) as f64;
+            if category_total == 0.0 {
+                continue;
+            }
+            observed_categories += 1;
+            for (observed, row_total) in [
+                (reference as f64, reference_total),
+                (production as f64, production_total),
+            ] {
+                let expected = row_total * category_total / grand_total;
+                statistic += (observed - expected).powi(2) / expected;
+            }
+        }
+
+        if observed_categories < 2 {
+            return Some(DriftTestResult {
+                statistic: 0.0,
+                p_value: 1.0,
+            });
+        }
+        Some(DriftTestResult {
+            statistic,
+            p_value: chi_squared_sf(statistic, observed_categories - 1),
+        })
+    }
+}
+
+/// Two-sample Kolmogorov-Smirnov test between a reference sample and streamed continuous values.
+///
+/// The statistic is the largest gap between the two empirical CDFs; the p-value uses the
+/// asymptotic Kolmogorov distribution with Stephens' small-sample correction. Every production
+/// value is kept until [`reset`](Metric::reset).
+///
+/// ```
+/// use rust_metrics::{KolmogorovSmirnovDrift, Metric};
+///
+/// let mut drift = KolmogorovSmirnovDrift::from_reference(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+/// drift.update(&[3.0, 4.0, 5.0, 6.0, 7.0]).unwrap();
+///
+/// let result = drift.compute().unwrap();
+/// assert!((result.statistic - 0.4).abs() < 1e-12);
+/// assert!((result.p_value - 0.697405).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KolmogorovSmirnovDrift {
+    reference: Vec<f64>,
+    production: Vec<f64>,
+}
+
+impl KolmogorovSmirnovDrift {
+    pub fn from_reference(reference: &[f64]) -> Result<Self, MetricError> {
+        if reference.is_empty() {
+            return Err(MetricError::IncompatibleInput {
+                expected: "a non-empty reference sample".to_string(),
+                got: "0 values".to_string(),
+            });
+        }
+        for &value in reference {
+            verify_range(value, f64::MIN, f64::MAX)?;
+        }
+        let mut reference = reference.to_vec();
+        reference.sort_by(f64::total_cmp);
+        Ok(Self {
+            reference,
+            production: Vec::new(),
+        })
+    }
+}
+
+impl Metric<&[f64]> for KolmogorovSmirnovDrift {
+    type Output = DriftTestResult;
+
+    fn update(&mut self, values: &[f64]) -> Result<(), MetricError> {
+        for &value in values {
+            verify_range(value, f64::MIN, f64::MAX)?;
+        }
+        self.production.extend_from_slice(values);
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.production.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.production.is_empty() {
+            return None;
+        }
+        let mut production = self.production.clone();
+        production.sort_by(f64::total_cmp);
+
+        let n = self.reference.len();
+        let m = production.len();
+        let (mut i, mut j) = (0, 0);
+        let mut statistic: f64 = 0.0;
+        while i < n && j < m {
+            let value = self.reference[i].min(production[j]);
+            while i < n && self.reference[i] <= value {
+                i += 1;
+            }
+            while j < m && production[j] <= value {
+                j += 1;
+            }
+            statistic = statistic.max((i as f64 / n as f64 - j as f64 / m as f64).abs());
+        }
+
+        let effective = ((n * m) as f64 / (n + m) as f64).sqrt();
+        let p_value = kolmogorov_sf((effective + 0.12 + 0.11 / effective) * statistic);
+        Some(DriftTestResult { statistic, p_value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChiSquaredDrift, KolmogorovSmirnovDrift};
+    use crate::core::Metric;
+
+    #[test]
+    fn chi_squared_drift_over_batches() {
+        let mut drift = ChiSquaredDrift::from_reference_counts(vec![30, 30, 0, 40]).unwrap();
+        drift.update(&[0, 1, 3]).unwrap();
+        drift.update(&[0, 1, 3, 3]).unwrap();
+        let result = drift.compute().unwrap();
+        assert!(result.p_value > 0.9);
+
+        assert!(drift.update(&[4]).is_err());
+        assert!(ChiSquaredDrift::from_reference_counts(vec![0, 0]).is_err());
+
+        drift.reset();
+        assert_eq!(drift.compute(), None);
+    }
+
+    #[test]
+    fn ks_drift_identical_samples() {
+        let reference = [0.3, 0.1, 0.7, 0.5];
+        let mut drift = KolmogorovSmirnovDrift::from_reference(&reference).unwrap();
+        drift.update(&reference[..2]).unwrap();
+        drift.update(&reference[2..]).unwrap();
+        let result = drift.compute().unwrap();
+        assert_eq!(result.statistic, 0.0);
+        assert_eq!(result.p_value, 1.0);
+
+        drift.reset();
+        assert_eq!(drift.compute(), None);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::{verify_binary_label, verify_range};
+
+/// Output of [`DemographicParity`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParityScores {
+    /// Share of positive predictions in every group seen so far.
+    pub positive_rates: BTreeMap<usize, f64>,
+    /// Lowest positive rate over the highest (the disparate impact ratio); `1.0` at parity or when
+    /// no group receives positive predictions.
+    pub ratio: f64,
+    /// Highest positive rate minus the lowest; `0.0` at parity.
+    pub difference: f64,
+}
+
+/// Demographic parity of a binary classifier: how much the rate of positive predictions differs
+/// between groups, regardless of the targets.
+///
+/// Inputs are `(predictions, groups)`: probabilities thresholded like
+/// [`BinaryStatScores`](crate::BinaryStatScores) and one group id per sample.
+/// [`update_labels`](DemographicParity::update_labels) takes already thresholded `0`/`1`
+/// predictions instead. A ratio below `0.8` is the usual "four-fifths rule" warning sign.
+///
+/// ```
+/// use rust_metrics::{DemographicParity, Metric};
+///
+/// let preds = [0.11, 0.84, 0.22, 0.73, 0.33, 0.92];
+/// let groups = [0_usize, 1, 0, 1, 0, 1];
+///
+/// let mut metric = DemographicParity::default();
+/// metric.update((&preds, &groups)).unwrap();
+/// let scores = metric.compute().unwrap();
+/// assert_eq!(scores.positive_rates[&0], 0.0);
+/// assert_eq!(scores.positive_rates[&1], 1.0);
+/// assert_eq!((scores.ratio, scores.difference), (0.0, 1.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DemographicParity {
+    threshold: f64,
+    /// `(positive predictions, samples)` per group.
+    groups: BTreeMap<usize, (usize, usize)>,
+}
+
+impl Default for DemographicParity {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl DemographicParity {
+    pub fn new(threshold: f64) -> Self {
+        verify_range(threshold, 0.0, 1.0).unwrap();
+        Self {
+            threshold,
+            groups: BTreeMap::new(),
+        }
+    }
+
+    /// Accumulate already thresholded `0`/`1` predictions; the threshold is not applied.
+    pub fn update_labels(
+        &mut self,
+        predictions: &[usize],
+        groups: &[usize],
+    ) -> Result<(), MetricError> {
+        verify_lengths(predictions.len(), groups.len())?;
+        for &prediction in predictions {
+            verify_binary_label(prediction)?;
+        }
+        for (&prediction, &group) in predictions.iter().zip(groups) {
+            self.record(prediction == 1, group);
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, predicted: bool, group: usize) {
+        let (positive, total) = self.groups.entry(group).or_insert((0, 0));
+        if predicted {
+            *positive += 1;
+        }
+        *total += 1;
+    }
+}
+
+impl Metric<(&[f64], &[usize])> for DemographicParity {
+    type Output = ParityScores;
+
+    fn update(&mut self, (predictions, groups): (&[f64], &[usize])) -> Result<(), MetricError> {
+        verify_lengths(predictions.len(), groups.len())?;
+        for &prediction in predictions {
+            verify_range(prediction, 0.0, 1.0)?;
+        }
+        for (&prediction, &group) in predictions.iter().zip(groups) {
+            self.record(prediction > self.threshold, group);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.groups.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let positive_rates: BTreeMap<usize, f64> = self
+            .groups
+            .iter()
+            .map(|(&group, &(positive, total))| (group, positive as f64 / total as f64))
+            .collect();
+        let (min, max) = extremes(positive_rates.values().copied())?;
+        Some(ParityScores {
+            ratio: if max == 0.0 { 1.0 } else { min / max },
+            difference: max - min,
+            positive_rates,
+        })
+    }
+}
+
+pub(crate) fn verify_lengths(predictions: usize, groups: usize) -> Result<(), MetricError> {
+    if predictions != groups {
+        return Err(MetricError::LengthMismatch {
+            predictions,
+            targets: groups,
+        });
+    }
+    Ok(())
+}
+
+/// Lowest and highest of `rates`; `None` if empty.
+pub(crate) fn extremes(rates: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    rates.fold(None, |extremes, rate| match extremes {
+        None => Some((rate, rate)),
+        Some((min, max)) => Some((f64::min(min, rate), f64::max(max, rate))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DemographicParity;
+    use crate::core::Metric;
+
+    #[test]
+    fn positive_rates_accumulate_across_batches() {
+        let mut metric = DemographicParity::default();
+        assert_eq!(metric.compute(), None);
+
+        metric.update((&[0.9, 0.1, 0.8], &[0, 0, 1])).unwrap();
+        metric.update_labels(&[0, 1, 1], &[1, 1, 2]).unwrap();
+        let scores = metric.compute().unwrap();
+        assert_eq!(scores.positive_rates[&0], 0.5);
+        assert!((scores.positive_rates[&1] - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(scores.positive_rates[&2], 1.0);
+        assert_eq!((scores.ratio, scores.difference), (0.5, 0.5));
+
+        assert!(metric.update((&[0.5], &[0, 1])).is_err());
+        assert!(metric.update_labels(&[2], &[0]).is_err());
+
+        metric.reset();
+        metric.update_labels(&[0, 0], &[0, 1]).unwrap();
+        let scores = metric.compute().unwrap();
+        assert_eq!((scores.ratio, scores.difference), (1.0, 0.0));
+    }
+}
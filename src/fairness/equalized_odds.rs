@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use crate::classification::{BinaryStatScores, StatCounts};
+use crate::core::{Metric, MetricError};
+use crate::utils::GroupedMetric;
+
+use super::demographic_parity::{extremes, verify_lengths};
+
+/// Error rates of one group, as reported by [`EqualizedOdds`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupRates {
+    pub counts: StatCounts,
+    /// `TP / (TP + FN)`; `None` without positive targets in the group.
+    pub true_positive_rate: Option<f64>,
+    /// `FP / (FP + TN)`; `None` without negative targets in the group.
+    pub false_positive_rate: Option<f64>,
+}
+
+/// Output of [`EqualizedOdds`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OddsScores {
+    pub groups: BTreeMap<usize, GroupRates>,
+    /// Highest minus lowest true positive rate over the groups where it is defined.
+    pub true_positive_rate_gap: f64,
+    /// Highest minus lowest false positive rate over the groups where it is defined.
+    pub false_positive_rate_gap: f64,
+    /// Larger of the two gaps; `0.0` when the classifier satisfies equalized odds.
+    pub max_gap: f64,
+}
+
+/// Equalized odds of a binary classifier: how much the true and false positive rates differ
+/// between groups.
+///
+/// Inputs are `(predictions, targets, groups)`. Predictions are thresholded and counted per group
+/// by a [`GroupedMetric`] of [`BinaryStatScores`]. A group without positive (or negative) targets
+/// has no true (or false) positive rate and does not count towards that gap; a gap over fewer than
+/// two groups is `0.0`.
+///
+/// ```
+/// use rust_metrics::{EqualizedOdds, Metric};
+///
+/// let preds = [0.9, 0.2, 0.8, 0.7, 0.1, 0.1];
+/// let target = [1_usize, 1, 0, 1, 0, 0];
+/// let groups = [0_usize, 0, 0, 1, 1, 1];
+///
+/// let mut metric = EqualizedOdds::default();
+/// metric.update((&preds, &target, &groups)).unwrap();
+/// let scores = metric.compute().unwrap();
+/// assert_eq!(scores.groups[&0].true_positive_rate, Some(0.5));
+/// assert_eq!(scores.groups[&1].false_positive_rate, Some(0.0));
+/// assert_eq!(scores.true_positive_rate_gap, 0.5);
+/// assert_eq!(scores.false_positive_rate_gap, 1.0);
+/// assert_eq!(scores.max_gap, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EqualizedOdds {
+    stat_scores: GroupedMetric<usize, BinaryStatScores>,
+}
+
+impl Default for EqualizedOdds {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl EqualizedOdds {
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            stat_scores: GroupedMetric::new(BinaryStatScores::new(threshold)),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[usize], &[usize])> for EqualizedOdds {
+    type Output = OddsScores;
+
+    fn update(
+        &mut self,
+        (predictions, targets, groups): (&[f64], &[usize], &[usize]),
+    ) -> Result<(), MetricError> {
+        verify_lengths(predictions.len(), groups.len())?;
+        self.stat_scores.update((groups, predictions, targets))
+    }
+
+    fn reset(&mut self) {
+        self.stat_scores.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let groups: BTreeMap<usize, GroupRates> = self
+            .stat_scores
+            .compute()?
+            .groups
+            .into_iter()
+            .map(|(group, counts)| (group, group_rates(counts)))
+            .collect();
+        let gap = |rates: Vec<Option<f64>>| {
+            extremes(rates.into_iter().flatten()).map_or(0.0, |(min, max)| max - min)
+        };
+        let true_positive_rate_gap = gap(groups.values().map(|r| r.true_positive_rate).collect());
+        let false_positive_rate_gap = gap(groups.values().map(|r| r.false_positive_rate).collect());
+        Some(OddsScores {
+            groups,
+            true_positive_rate_gap,
+            false_positive_rate_gap,
+            max_gap: true_positive_rate_gap.max(false_positive_rate_gap),
+        })
+    }
+}
+
+fn group_rates(counts: StatCounts) -> GroupRates {
+    let rate = |hits: usize, misses: usize| {
+        (hits + misses > 0).then(|| hits as f64 / (hits + misses) as f64)
+    };
+    GroupRates {
+        counts,
+        true_positive_rate: rate(counts.true_positive, counts.false_negative),
+        false_positive_rate: rate(counts.false_positive, counts.true_negative),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EqualizedOdds;
+    use crate::core::Metric;
+
+    #[test]
+    fn undefined_rates_are_left_out_of_the_gaps() {
+        let mut metric = EqualizedOdds::default();
+        assert_eq!(metric.compute(), None);
+
+        // group 2 only has negatives, so it has no true positive rate
+        metric.update((&[0.9, 0.3], &[1, 0], &[1, 1])).unwrap();
+        metric.update((&[0.6, 0.7], &[0, 0], &[2, 2])).unwrap();
+        let scores = metric.compute().unwrap();
+        assert_eq!(scores.groups[&2].true_positive_rate, None);
+        assert_eq!(scores.groups[&2].counts.false_positive, 2);
+        assert_eq!(scores.true_positive_rate_gap, 0.0);
+        assert_eq!(scores.false_positive_rate_gap, 1.0);
+        assert_eq!(scores.max_gap, 1.0);
+
+        assert!(metric.update((&[0.5], &[1], &[0, 1])).is_err());
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
@@ -0,0 +1,11 @@
+//! Group fairness metrics for binary classifiers.
+//!
+//! All types implement [`Metric`](crate::core::Metric) for batched updates; alongside the
+//! predictions (and targets), pass one integer group id (e.g. an encoded protected attribute)
+//! per sample.
+
+pub mod demographic_parity;
+pub mod equalized_odds;
+
+pub use demographic_parity::{DemographicParity, ParityScores};
+pub use equalized_odds::{EqualizedOdds, GroupRates, OddsScores};
@@ -0,0 +1,159 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use fastembed::{
+    EmbeddingModel, ImageEmbedding, ImageEmbeddingModel, ImageInitOptions, TextEmbedding,
+    TextInitOptions,
+};
+
+use crate::{
+    core::{Metric, MetricError},
+    utils::{MetricAggregator, Reduction, cosine_similarity},
+};
+
+/// CLIPScore: `100 * max(cos(image, caption), 0)` averaged over image-caption pairs.
+///
+/// Images are read from disk and embedded with the CLIP vision tower, captions with the matching
+/// CLIP text tower, both through `fastembed`. Requires the `clip` feature. [`Default`] loads
+/// CLIP ViT-B/32 and panics if it cannot be loaded; [`with_models`](ClipScore::with_models)
+/// reports load failures as [`MetricError::Backend`], as `update` does for unreadable images and
+/// embedding failures.
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use rust_metrics::{ClipScore, Metric};
+///
+/// let mut metric = ClipScore::default();
+/// metric
+///     .update((&[Path::new("cat.jpg")], &["a photo of a cat"]))
+///     .unwrap();
+/// assert!(metric.compute().unwrap() > 20.0);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "clip")))]
+pub struct ClipScore {
+    image_model: Arc<Mutex<ImageEmbedding>>,
+    text_model: Arc<Mutex<TextEmbedding>>,
+    metric_aggregator: MetricAggregator,
+}
+
+impl Default for ClipScore {
+    fn default() -> Self {
+        Self::with_models(
+            ImageInitOptions::new(ImageEmbeddingModel::ClipVitB32),
+            TextInitOptions::new(EmbeddingModel::ClipVitB32),
+        )
+        .expect("Failed to initialize CLIP models")
+    }
+}
+
+impl ClipScore {
+    /// Both models must embed into the same space, e.g. the two towers of one CLIP checkpoint.
+    pub fn new(
+        image_model: Arc<Mutex<ImageEmbedding>>,
+        text_model: Arc<Mutex<TextEmbedding>>,
+    ) -> Self {
+        Self {
+            image_model,
+            text_model,
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+
+    /// Load the image and text towers from `image_options` and `text_options`, which must name
+    /// the two halves of one CLIP checkpoint.
+    ///
+    /// Download and initialization failures are returned as [`MetricError::Backend`].
+    ///
+    /// ```rust,ignore
+    /// use fastembed::{EmbeddingModel, ImageEmbeddingModel, ImageInitOptions, TextInitOptions};
+    /// use rust_metrics::ClipScore;
+    ///
+    /// let metric = ClipScore::with_models(
+    ///     ImageInitOptions::new(ImageEmbeddingModel::ClipVitB32).with_show_download_progress(false),
+    ///     TextInitOptions::new(EmbeddingModel::ClipVitB32).with_show_download_progress(false),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn with_models(
+        image_options: ImageInitOptions,
+        text_options: TextInitOptions,
+    ) -> Result<Self, MetricError> {
+        let image_model = ImageEmbedding::try_new(image_options)
+            .map_err(|err| MetricError::Backend(err.to_string()))?;
+        let text_model = TextEmbedding::try_new(text_options)
+            .map_err(|err| MetricError::Backend(err.to_string()))?;
+        Ok(Self::new(
+            Arc::new(Mutex::new(image_model)),
+            Arc::new(Mutex::new(text_model)),
+        ))
+    }
+
+    fn embed_images(&self, images: &[&Path]) -> Result<Vec<Vec<f32>>, MetricError> {
+        let mut model = self
+            .image_model
+            .lock()
+            .map_err(|_| MetricError::Backend("ImageEmbedding lock poisoned".to_string()))?;
+        model
+            .embed(images, None)
+            .map_err(|err| MetricError::Backend(err.to_string()))
+    }
+
+    fn embed_captions(&self, captions: &[&str]) -> Result<Vec<Vec<f32>>, MetricError> {
+        let inputs: Vec<String> = captions.iter().map(|s| (*s).to_string()).collect();
+        let mut model = self
+            .text_model
+            .lock()
+            .map_err(|_| MetricError::Backend("TextEmbedding lock poisoned".to_string()))?;
+        model
+            .embed(inputs, None)
+            .map_err(|err| MetricError::Backend(err.to_string()))
+    }
+}
+
+impl Metric<(&[&Path], &[&str])> for ClipScore {
+    type Output = f64;
+
+    fn update(&mut self, (images, captions): (&[&Path], &[&str])) -> Result<(), MetricError> {
+        if images.len() != captions.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: images.len(),
+                targets: captions.len(),
+            });
+        }
+        if images.is_empty() {
+            return Ok(());
+        }
+
+        let image_embeddings = self.embed_images(images)?;
+        let caption_embeddings = self.embed_captions(captions)?;
+        for (image, caption) in image_embeddings.iter().zip(caption_embeddings.iter()) {
+            self.metric_aggregator
+                .update(100.0 * cosine_similarity(image, caption).max(0.0));
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::ClipScore;
+    use crate::core::{Metric, MetricError};
+
+    #[test]
+    fn missing_image_is_a_backend_error() {
+        let mut metric = ClipScore::default();
+        let result = metric.update((&[Path::new("does/not/exist.jpg")], &["a photo of a cat"]));
+        assert!(matches!(result, Err(MetricError::Backend(_))));
+        assert_eq!(metric.compute(), None);
+    }
+}
@@ -0,0 +1,250 @@
+#[cfg(feature = "clip")]
+use std::path::Path;
+#[cfg(feature = "clip")]
+use std::sync::Mutex;
+
+#[cfg(feature = "clip")]
+use fastembed::ImageEmbedding;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::CovarianceAccumulator;
+
+/// Fréchet distance between Gaussians fitted to real and generated embeddings (as in FID).
+///
+/// `d^2 = |mu_r - mu_g|^2 + tr(S_r + S_g - 2 (S_r S_g)^(1/2))`. Only running means and
+/// covariances are stored, so memory is `O(dim^2)` regardless of how many images are seen.
+/// Updates take `(real, generated)` embedding batches, which may differ in size; `compute`
+/// needs at least two embeddings on each side. With the `clip` feature,
+/// [`update_images`](FrechetDistance::update_images) embeds image files itself.
+///
+/// ```
+/// use rust_metrics::{FrechetDistance, Metric};
+///
+/// let real: [&[f32]; 2] = [&[0.0], &[2.0]];
+/// let generated: [&[f32]; 2] = [&[1.0], &[5.0]];
+///
+/// let mut fid = FrechetDistance::new(1);
+/// fid.update((&real, &generated)).unwrap();
+/// // (1 - 3)^2 + 2 + 8 - 2 * sqrt(2 * 8)
+/// assert!((fid.compute().unwrap() - 6.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrechetDistance {
+    real: CovarianceAccumulator,
+    generated: CovarianceAccumulator,
+}
+
+impl FrechetDistance {
+    pub fn new(dim: usize) -> Self {
+        Self {
+            real: CovarianceAccumulator::new(dim),
+            generated: CovarianceAccumulator::new(dim),
+        }
+    }
+
+    /// Embed `real` and `generated` image files with `model` and add them, as `update` does for
+    /// precomputed embeddings; `dim` must match the model's output (`512` for CLIP ViT-B/32).
+    /// Model failures are reported as [`MetricError::Backend`].
+    ///
+    /// ```rust,ignore
+    /// use std::path::Path;
+    /// use std::sync::Mutex;
+    ///
+    /// use fastembed::{ImageEmbedding, ImageEmbeddingModel, ImageInitOptions};
+    /// use rust_metrics::{FrechetDistance, Metric};
+    ///
+    /// let model = Mutex::new(
+    ///     ImageEmbedding::try_new(ImageInitOptions::new(ImageEmbeddingModel::ClipVitB32)).unwrap(),
+    /// );
+    /// let mut fid = FrechetDistance::new(512);
+    /// fid.update_images(&model, &[Path::new("real.jpg")], &[Path::new("fake.jpg")])
+    ///     .unwrap();
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "clip")))]
+    #[cfg(feature = "clip")]
+    pub fn update_images(
+        &mut self,
+        model: &Mutex<ImageEmbedding>,
+        real: &[&Path],
+        generated: &[&Path],
+    ) -> Result<(), MetricError> {
+        let mut model = model
+            .lock()
+            .map_err(|_| MetricError::Backend("ImageEmbedding lock poisoned".to_string()))?;
+        let mut embed = |images: &[&Path]| {
+            if images.is_empty() {
+                return Ok(Vec::new());
+            }
+            model
+                .embed(images, None)
+                .map_err(|err| MetricError::Backend(err.to_string()))
+        };
+        let real = embed(real)?;
+        let generated = embed(generated)?;
+        drop(model);
+
+        let real: Vec<&[f32]> = real.iter().map(|e| &e[..]).collect();
+        let generated: Vec<&[f32]> = generated.iter().map(|e| &e[..]).collect();
+        self.update((&real, &generated))
+    }
+}
+
+impl Metric<(&[&[f32]], &[&[f32]])> for FrechetDistance {
+    type Output = f64;
+
+    fn update(&mut self, (real, generated): (&[&[f32]], &[&[f32]])) -> Result<(), MetricError> {
+        let dim = self.real.dim();
+        for embedding in real.iter().chain(generated.iter()) {
+            if embedding.len() != dim {
+                return Err(MetricError::IncompatibleInput {
+                    expected: format!("embedding of dimension {}", dim),
+                    got: format!("dimension {}", embedding.len()),
+                });
+            }
+        }
+        for embedding in real {
+            let embedding: Vec<f64> = embedding.iter().map(|&x| x as f64).collect();
+            self.real.update(&embedding)?;
+        }
+        for embedding in generated {
+            let embedding: Vec<f64> = embedding.iter().map(|&x| x as f64).collect();
+            self.generated.update(&embedding)?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.real.reset();
+        self.generated.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let real_cov = self.real.covariance()?;
+        let generated_cov = self.generated.covariance()?;
+        let n = self.real.dim();
+
+        let mean_term: f64 = self
+            .real
+            .mean()
+            .iter()
+            .zip(self.generated.mean())
+            .map(|(r, g)| (r - g).powi(2))
+            .sum();
+        let trace_term: f64 = (0..n)
+            .map(|i| real_cov[i * n + i] + generated_cov[i * n + i])
+            .sum();
+
+        // tr((S_r S_g)^(1/2)) equals tr((S_r^(1/2) S_g S_r^(1/2))^(1/2)), whose argument is
+        // symmetric positive semi-definite.
+        let real_sqrt = symmetric_sqrt(&real_cov, n);
+        let product = mat_mul(&mat_mul(&real_sqrt, &generated_cov, n), &real_sqrt, n);
+        let (eigenvalues, _) = symmetric_eigen(product, n);
+        let cross_term: f64 = eigenvalues.iter().map(|&l| l.max(0.0).sqrt()).sum();
+
+        Some((mean_term + trace_term - 2.0 * cross_term).max(0.0))
+    }
+}
+
+fn mat_mul(a: &[f64], b: &[f64], n: usize) -> Vec<f64> {
+    let mut out = vec![0.0; n * n];
+    for i in 0..n {
+        for k in 0..n {
+            let a_ik = a[i * n + k];
+            for j in 0..n {
+                out[i * n + j] += a_ik * b[k * n + j];
+            }
+        }
+    }
+    out
+}
+
+/// Principal square root of a symmetric positive semi-definite matrix.
+fn symmetric_sqrt(matrix: &[f64], n: usize) -> Vec<f64> {
+    let (eigenvalues, eigenvectors) = symmetric_eigen(matrix.to_vec(), n);
+    let roots: Vec<f64> = eigenvalues.iter().map(|&l| l.max(0.0).sqrt()).collect();
+    let mut out = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            out[i * n + j] = (0..n)
+                .map(|k| eigenvectors[i * n + k] * roots[k] * eigenvectors[j * n + k])
+                .sum();
+        }
+    }
+    out
+}
+
+/// Cyclic Jacobi eigen-decomposition of a symmetric row-major matrix.
+///
+/// Returns the eigenvalues and a matrix whose columns are the matching eigenvectors.
+fn symmetric_eigen(mut a: Vec<f64>, n: usize) -> (Vec<f64>, Vec<f64>) {
+    const MAX_SWEEPS: usize = 100;
+    let mut v = vec![0.0; n * n];
+    for i in 0..n {
+        v[i * n + i] = 1.0;
+    }
+
+    let total: f64 = a.iter().map(|x| x * x).sum();
+    for _ in 0..MAX_SWEEPS {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i * n + j].powi(2))
+            .sum();
+        if off_diagonal <= f64::EPSILON * f64::EPSILON * total {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let a_pq = a[p * n + q];
+                if a_pq == 0.0 {
+                    continue;
+                }
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * a_pq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (a_kp, a_kq) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = c * a_kp - s * a_kq;
+                    a[k * n + q] = s * a_kp + c * a_kq;
+                }
+                for k in 0..n {
+                    let (a_pk, a_qk) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = c * a_pk - s * a_qk;
+                    a[q * n + k] = s * a_pk + c * a_qk;
+                }
+                for k in 0..n {
+                    let (v_kp, v_kq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * v_kp - s * v_kq;
+                    v[k * n + q] = s * v_kp + c * v_kq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i * n + i]).collect(), v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrechetDistance;
+    use crate::core::Metric;
+
+    #[test]
+    fn frechet_distance_full_covariance() {
+        let real: [&[f32]; 4] = [&[1.0, 2.0], &[2.0, 1.0], &[3.0, 5.0], &[0.0, 0.0]];
+        let mut fid = FrechetDistance::new(2);
+        fid.update((&real[..2], &real[..1])).unwrap();
+        fid.update((&real[2..], &real[1..])).unwrap();
+        assert!(fid.compute().unwrap().abs() < 1e-9);
+
+        // shifting the generated set only changes the mean term
+        let shifted: Vec<Vec<f32>> = real.iter().map(|e| vec![e[0] + 1.0, e[1] - 2.0]).collect();
+        let shifted: Vec<&[f32]> = shifted.iter().map(|e| &e[..]).collect();
+        let mut fid = FrechetDistance::new(2);
+        fid.update((&real, &shifted)).unwrap();
+        assert!((fid.compute().unwrap() - 5.0).abs() < 1e-9);
+
+        assert!(fid.update((&[&[1.0][..]], &[])).is_err());
+        fid.reset();
+        assert_eq!(fid.compute(), None);
+    }
+}
@@ -0,0 +1,13 @@
+//! Generative image metrics over embeddings (requires the `image-embed` feature).
+//!
+//! Feature extraction is left to the caller: pass one embedding vector per image, e.g. the pooled
+//! activations of an Inception or CLIP backbone. The `clip` feature adds [`ClipScore`], which
+//! embeds images and captions itself through `fastembed`.
+
+#[cfg(feature = "clip")]
+pub mod clip;
+pub mod frechet;
+
+#[cfg(feature = "clip")]
+pub use clip::ClipScore;
+pub use frechet::FrechetDistance;
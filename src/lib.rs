@@ -1,27 +1,84 @@
 #![doc = include_str!("../README.md")]
 
+pub mod audio;
 pub mod classification;
 pub mod clustering;
 pub mod core;
+pub mod detection;
+pub mod drift;
+pub mod fairness;
+#[cfg(feature = "image-embed")]
+pub mod image;
 pub mod regression;
+pub mod retrieval;
+pub mod segmentation;
+pub mod sequence;
 pub mod text;
 pub mod utils;
 
+pub use audio::{
+    ScaleInvariantSignalDistortionRatio, ScaleInvariantSignalNoiseRatio, SignalNoiseRatio,
+};
 pub use classification::{
-    BinaryAccuracy, BinaryAuroc, BinaryConfusionMatrix, BinaryF1Score, BinaryHingeLoss,
-    BinaryJaccardIndex, BinaryPrecision, BinaryRecall, MulticlassAccuracy, MulticlassF1Score,
-    MulticlassHingeLoss, MulticlassJaccardIndex, MulticlassPrecision,
+    BinaryAccuracy, BinaryAuprg, BinaryAuroc, BinaryAveragePrecision, BinaryClassificationReport,
+    BinaryCohenKappa, BinaryConfusionMatrix, BinaryF1Score, BinaryFalseNegativeRate,
+    BinaryFalsePositiveRate, BinaryGini, BinaryHingeLoss, BinaryJaccardIndex, BinaryLiftCurve,
+    BinaryLogLoss, BinaryNegativePredictiveValue, BinaryPrecision, BinaryRecall, BinaryRoc,
+    BinarySensitivityAtSpecificity, BinaryStatScores, ClassCount, ClassSupport,
+    ClassificationStats, DeLong, HierarchicalF1Score, HierarchicalPrecision, HierarchicalRecall,
+    KappaWeighting, LiftBucket, MulticlassAccuracy, MulticlassAveragePrecision,
+    MulticlassCohenKappa, MulticlassConfusionMatrix, MulticlassF1Score, MulticlassHingeLoss,
+    MulticlassHingeMode, MulticlassJaccardIndex, MulticlassLogLoss, MulticlassPrecision,
+    MulticlassStatScores, MultilabelAccuracy, MultilabelCoverageError, MultilabelExactMatch,
+    MultilabelF1Score, MultilabelPrecision, MultilabelRankingAveragePrecision,
+    MultilabelRankingLoss, MultilabelRecall, MultilabelStatScores, Normalization, ScoreKind,
+    StatCounts, ThresholdChoice, ThresholdObjective, ThresholdSweep,
+};
+pub use clustering::{
+    ClusterPurity, ContingencyCounts, ContingencyMatrix, Distance, MutualInfoScore,
+    PairConfusionMatrix, SilhouetteScore,
 };
-pub use clustering::MutualInfoScore;
-pub use core::{Metric, MetricError};
+pub use core::{Metric, MetricError, Revertible, Snapshot};
+pub use detection::{DetectionScores, ImageDetections, ImageTargets, MeanAveragePrecision};
+pub use drift::{ChiSquaredDrift, KolmogorovSmirnovDrift, PopulationStabilityIndex};
+pub use fairness::{DemographicParity, EqualizedOdds, GroupRates, OddsScores, ParityScores};
 pub use regression::{
-    MeanAbsoluteError, MeanAbsolutePercentageError, MeanSquaredError,
-    NormalizedRootMeanSquaredError, R2Score,
+    CoverageSummary, D2AbsoluteErrorScore, D2PinballScore, DirectionalAccuracy,
+    EarthMoversDistance, IntervalCoverage, JensenShannonDivergence, KlDivergence,
+    MeanAbsoluteError, MeanAbsolutePercentageError, MeanSquaredError, MedianAbsoluteError,
+    MinkowskiError, NormalizedRootMeanSquaredError, R2Score, WeightedMeanAbsolutePercentageError,
+};
+pub use retrieval::{
+    CatalogCoverage, EmptyTargetAction, IntraListDiversity, Novelty, Personalization,
+    RetrievalAUROC, RetrievalHitRate, RetrievalMAP, RetrievalMRR, Serendipity,
 };
+pub use segmentation::MeanIoU;
+pub use sequence::{ChunkPrecision, ChunkRecall, EntityF1};
 
-pub use text::{Bleu, EditDistance, RougeScore};
-pub use utils::Reduction;
+pub use text::{
+    Bleu, BleuSmoothing, CharErrorRate, ChrF, CodeBleu, DistinctNGrams, EditDistance, EntityRecall,
+    ExtractiveFragmentCoverage, ExtractiveFragmentDensity, HallucinatedEntityRate,
+    MaxConsecutiveRepeat, MultiReferenceRouge, NovelNGramRatio, Perplexity, RepetitionRate,
+    RougeScore, SelfBleu, SquadScore, SquadScores, WordErrorRate,
+};
+pub use utils::{
+    CompositeMetric, ContingencyTable, EditOperations, GroupedMetric, Reduction, SimilarityFunction,
+};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "text-bert")))]
 #[cfg(feature = "text-bert")]
-pub use text::SentenceEmbeddingSimilarity;
+pub use text::{
+    EmbeddingModel, InitOptionsUserDefined, Pooling, SentenceEmbeddingSimilarity, TextInitOptions,
+};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "nli")))]
+#[cfg(feature = "nli")]
+pub use text::FactualConsistency;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "image-embed")))]
+#[cfg(feature = "image-embed")]
+pub use image::FrechetDistance;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "clip")))]
+#[cfg(feature = "clip")]
+pub use image::ClipScore;
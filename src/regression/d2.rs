@@ -0,0 +1,163 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::SampleBuffer;
+use crate::utils::stats::interpolated_quantile;
+
+fn pinball_loss(prediction: f64, target: f64, alpha: f64) -> f64 {
+    let diff = target - prediction;
+    if diff >= 0.0 {
+        alpha * diff
+    } else {
+        (alpha - 1.0) * diff
+    }
+}
+
+/// D² pinball score: the fraction of pinball loss explained relative to always predicting the
+/// empirical `alpha`-quantile of the targets, as in scikit-learn's `d2_pinball_score`.
+///
+/// The baseline quantile depends on every target, so targets are kept until `compute`. `1.0` is
+/// a perfect score and values can be negative; `None` until at least two samples are seen.
+///
+/// ```
+/// use rust_metrics::{D2PinballScore, Metric};
+///
+/// let preds = [1.0, 3.0, 3.0];
+/// let target = [1.0, 2.0, 3.0];
+///
+/// let mut metric = D2PinballScore::new(0.9);
+/// metric.update((&preds, &target)).unwrap();
+/// // loss 0.1 against 0.1 * 1.8 + 0.1 * 0.8 + 0.9 * 0.2 = 0.44 for the 0.9-quantile 2.8
+/// assert!((metric.compute().unwrap() - (1.0 - 0.1 / 0.44)).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct D2PinballScore {
+    alpha: f64,
+    sum_loss: f64,
+    targets: SampleBuffer<f64>,
+}
+
+impl Default for D2PinballScore {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl D2PinballScore {
+    pub fn new(alpha: f64) -> Self {
+        assert!(alpha > 0.0 && alpha < 1.0, "alpha must lie in (0, 1)");
+        Self {
+            alpha,
+            sum_loss: 0.0,
+            targets: SampleBuffer::new(),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[f64])> for D2PinballScore {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[f64])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            self.sum_loss += pinball_loss(prediction, target, self.alpha);
+        }
+        self.targets.push_batch(targets.to_vec());
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum_loss = 0.0;
+        self.targets.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.targets.len() < 2 {
+            return None;
+        }
+        let mut sorted = self.targets.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let baseline = interpolated_quantile(&sorted, self.alpha);
+        let baseline_loss: f64 = sorted
+            .iter()
+            .map(|&target| pinball_loss(baseline, target, self.alpha))
+            .sum();
+        if baseline_loss == 0.0 {
+            return Some(if self.sum_loss == 0.0 { 1.0 } else { 0.0 });
+        }
+        Some(1.0 - self.sum_loss / baseline_loss)
+    }
+}
+
+/// D² absolute error score, the [`D2PinballScore`] at `alpha = 0.5` (skill against always
+/// predicting the target median), as in scikit-learn's `d2_absolute_error_score`.
+///
+/// ```
+/// use rust_metrics::{D2AbsoluteErrorScore, Metric};
+///
+/// let preds = [2.5, 0.0, 2.0, 8.0];
+/// let target = [3.0, -0.5, 2.0, 7.0];
+///
+/// let mut metric = D2AbsoluteErrorScore::default();
+/// metric.update((&preds, &target)).unwrap();
+/// assert!((metric.compute().unwrap() - 0.7647058823529411).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct D2AbsoluteErrorScore {
+    inner: D2PinballScore,
+}
+
+impl D2AbsoluteErrorScore {
+    pub fn new() -> Self {
+        Self {
+            inner: D2PinballScore::new(0.5),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[f64])> for D2AbsoluteErrorScore {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[f64], &[f64])) -> Result<(), MetricError> {
+        self.inner.update(input)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.inner.compute()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{D2AbsoluteErrorScore, D2PinballScore};
+    use crate::core::Metric;
+
+    #[test]
+    fn d2_scores_over_batches() {
+        let mut metric = D2AbsoluteErrorScore::default();
+        metric.update((&[2.5, 0.0], &[3.0, -0.5])).unwrap();
+        metric.update((&[2.0, 8.0], &[2.0, 7.0])).unwrap();
+        assert!((metric.compute().unwrap() - 0.7647058823529411).abs() < 1e-12);
+
+        // predicting the baseline quantile scores zero; constant targets score 1 only if exact
+        let mut baseline = D2PinballScore::new(0.5);
+        baseline
+            .update((&[2.0, 2.0, 2.0], &[1.0, 2.0, 3.0]))
+            .unwrap();
+        assert!(baseline.compute().unwrap().abs() < 1e-12);
+        let mut constant = D2PinballScore::new(0.5);
+        constant.update((&[4.0, 4.0], &[4.0, 4.0])).unwrap();
+        assert_eq!(constant.compute(), Some(1.0));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
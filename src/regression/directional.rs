@@ -0,0 +1,117 @@
+use std::cmp::Ordering;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::verify_range;
+
+/// Share of forecasts that move in the same direction as the actual series.
+///
+/// For consecutive samples the predicted change `prediction[t] - target[t - 1]` is compared with
+/// the actual change `target[t] - target[t - 1]`; a flat change only matches a flat change. The
+/// last target of each batch is carried over, so splitting a series across batches gives the
+/// same result. The first sample of a stream has no previous actual and is skipped unless one is
+/// supplied through [`DirectionalAccuracy::set_previous_actual`].
+///
+/// ```
+/// use rust_metrics::{DirectionalAccuracy, Metric};
+///
+/// let target = [10.0, 12.0, 11.0, 11.5];
+/// let preds = [10.0, 11.0, 12.5, 12.0];
+///
+/// let mut metric = DirectionalAccuracy::default();
+/// metric.update((&preds[..2], &target[..2])).unwrap();
+/// metric.update((&preds[2..], &target[2..])).unwrap();
+/// // up/up, up/down, up/up
+/// assert!((metric.compute().unwrap() - 2.0 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DirectionalAccuracy {
+    previous_actual: Option<f64>,
+    matches: usize,
+    total: usize,
+}
+
+impl DirectionalAccuracy {
+    pub fn new() -> Self {
+        Self {
+            previous_actual: None,
+            matches: 0,
+            total: 0,
+        }
+    }
+
+    /// Use `actual` as the value preceding the next update, e.g. the last observation before the
+    /// evaluation window or of a batch processed elsewhere.
+    pub fn set_previous_actual(&mut self, actual: f64) {
+        self.previous_actual = Some(actual);
+    }
+
+    pub fn previous_actual(&self) -> Option<f64> {
+        self.previous_actual
+    }
+}
+
+fn direction(delta: f64) -> Ordering {
+    delta.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+}
+
+impl Metric<(&[f64], &[f64])> for DirectionalAccuracy {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[f64])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for &value in predictions.iter().chain(targets) {
+            verify_range(value, f64::MIN, f64::MAX)?;
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            if let Some(previous) = self.previous_actual {
+                if direction(prediction - previous) == direction(target - previous) {
+                    self.matches += 1;
+                }
+                self.total += 1;
+            }
+            self.previous_actual = Some(target);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.previous_actual = None;
+        self.matches = 0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.matches as f64 / self.total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DirectionalAccuracy;
+    use crate::core::Metric;
+
+    #[test]
+    fn directional_accuracy_with_seeded_history() {
+        let mut metric = DirectionalAccuracy::default();
+        metric.update((&[5.0], &[5.0])).unwrap();
+        assert_eq!(metric.compute(), None);
+
+        metric.reset();
+        metric.set_previous_actual(4.0);
+        // up/up, flat/flat, down/up
+        metric.update((&[5.0, 6.0, 5.5], &[6.0, 6.0, 7.0])).unwrap();
+        assert!((metric.compute().unwrap() - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(metric.previous_actual(), Some(7.0));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
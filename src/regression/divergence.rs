@@ -0,0 +1,224 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{verify_range, verify_reverted_count};
+
+/// Validate one `(p, q)` pair of non-negative weights and normalize both to sum to one.
+fn normalized_pair(p: &[f64], q: &[f64]) -> Result<(Vec<f64>, Vec<f64>), MetricError> {
+    if p.len() != q.len() || p.is_empty() {
+        return Err(MetricError::IncompatibleInput {
+            expected: "non-empty distributions of equal length".to_string(),
+            got: format!("lengths {} and {}", p.len(), q.len()),
+        });
+    }
+    let normalize = |weights: &[f64]| -> Result<Vec<f64>, MetricError> {
+        for &weight in weights {
+            verify_range(weight, 0.0, f64::MAX)?;
+        }
+        let sum: f64 = weights.iter().sum();
+        if sum == 0.0 {
+            return Err(MetricError::IncompatibleInput {
+                expected: "a distribution with positive mass".to_string(),
+                got: "all zero weights".to_string(),
+            });
+        }
+        Ok(weights.iter().map(|weight| weight / sum).collect())
+    };
+    Ok((normalize(p)?, normalize(q)?))
+}
+
+/// `KL(p || q)` in nats; infinite when `q` misses mass that `p` has.
+fn kl(p: &[f64], q: &[f64]) -> f64 {
+    p.iter()
+        .zip(q)
+        .filter(|(pi, _)| **pi > 0.0)
+        .map(|(pi, qi)| pi * (pi / qi).ln())
+        .sum()
+}
+
+fn js(p: &[f64], q: &[f64]) -> f64 {
+    let m: Vec<f64> = p.iter().zip(q).map(|(pi, qi)| (pi + qi) / 2.0).collect();
+    (kl(p, &m) + kl(q, &m)) / 2.0
+}
+
+/// Mean Kullback-Leibler divergence `KL(p || q)` between paired discrete distributions.
+///
+/// Each update takes rows of `p` and `q`; every row is normalized to sum to one, so raw counts
+/// are accepted. The divergence is in nats and is infinite when `q` assigns zero mass to a
+/// category that `p` does not.
+///
+/// ```
+/// use rust_metrics::{KlDivergence, Metric};
+///
+/// let p: [&[f64]; 1] = [&[0.36, 0.48, 0.16]];
+/// let q: [&[f64]; 1] = [&[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]];
+///
+/// let mut metric = KlDivergence::default();
+/// metric.update((&p, &q)).unwrap();
+/// assert!((metric.compute().unwrap() - 0.0852996).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KlDivergence {
+    sum_divergence: f64,
+    total: usize,
+}
+
+impl KlDivergence {
+    pub fn new() -> Self {
+        Self {
+            sum_divergence: 0.0,
+            total: 0,
+        }
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[f64]])> for KlDivergence {
+    type Output = f64;
+
+    fn update(&mut self, (p, q): (&[&[f64]], &[&[f64]])) -> Result<(), MetricError> {
+        if p.len() != q.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: p.len(),
+                targets: q.len(),
+            });
+        }
+        let pairs = p
+            .iter()
+            .zip(q)
+            .map(|(p_row, q_row)| normalized_pair(p_row, q_row))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (p_row, q_row) in pairs {
+            self.sum_divergence += kl(&p_row, &q_row);
+        }
+        self.total += p.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum_divergence = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.sum_divergence / self.total as f64)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[f64]])> for KlDivergence {
+    fn revert(&mut self, input: (&[&[f64]], &[&[f64]])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_divergence -= batch.sum_divergence;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+/// Mean Jensen-Shannon divergence between paired discrete distributions.
+///
+/// The symmetric, always finite counterpart of [`KlDivergence`]:
+/// `JS(p, q) = (KL(p || m) + KL(q || m)) / 2` with `m = (p + q) / 2`. Rows are normalized like
+/// for [`KlDivergence`]; the value is in nats and bounded by `ln 2`.
+///
+/// ```
+/// use rust_metrics::{JensenShannonDivergence, Metric};
+///
+/// let p: [&[f64]; 2] = [&[1.0, 0.0], &[0.5, 0.5]];
+/// let q: [&[f64]; 2] = [&[0.0, 1.0], &[0.5, 0.5]];
+///
+/// let mut metric = JensenShannonDivergence::default();
+/// metric.update((&p, &q)).unwrap();
+/// assert!((metric.compute().unwrap() - 2.0_f64.ln() / 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JensenShannonDivergence {
+    sum_divergence: f64,
+    total: usize,
+}
+
+impl JensenShannonDivergence {
+    pub fn new() -> Self {
+        Self {
+            sum_divergence: 0.0,
+            total: 0,
+        }
+    }
+}
+
+impl Metric<(&[&[f64]], &[&[f64]])> for JensenShannonDivergence {
+    type Output = f64;
+
+    fn update(&mut self, (p, q): (&[&[f64]], &[&[f64]])) -> Result<(), MetricError> {
+        if p.len() != q.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: p.len(),
+                targets: q.len(),
+            });
+        }
+        let pairs = p
+            .iter()
+            .zip(q)
+            .map(|(p_row, q_row)| normalized_pair(p_row, q_row))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (p_row, q_row) in pairs {
+            self.sum_divergence += js(&p_row, &q_row);
+        }
+        self.total += p.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum_divergence = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.sum_divergence / self.total as f64)
+    }
+}
+
+impl Revertible<(&[&[f64]], &[&[f64]])> for JensenShannonDivergence {
+    fn revert(&mut self, input: (&[&[f64]], &[&[f64]])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_divergence -= batch.sum_divergence;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JensenShannonDivergence, KlDivergence};
+    use crate::core::Metric;
+
+    #[test]
+    fn divergences_normalize_rows() {
+        let p: [&[f64]; 2] = [&[2.0, 6.0, 2.0][..], &[1.0, 1.0, 0.0][..]];
+        let q: [&[f64]; 2] = [&[1.0, 1.0, 1.0][..], &[1.0, 1.0, 0.0][..]];
+
+        let mut kl = KlDivergence::default();
+        let mut js = JensenShannonDivergence::default();
+        kl.update((&p, &q)).unwrap();
+        js.update((&p, &q)).unwrap();
+        let expected_kl = (0.2 * 0.6_f64.ln() * 2.0 + 0.6 * 1.8_f64.ln()) / 2.0;
+        assert!((kl.compute().unwrap() - expected_kl).abs() < 1e-12);
+        assert!(js.compute().unwrap() > 0.0 && js.compute().unwrap() < expected_kl);
+
+        let missing: [&[f64]; 1] = [&[0.0, 1.0][..]];
+        let full: [&[f64]; 1] = [&[0.5, 0.5][..]];
+        kl.reset();
+        kl.update((&full, &missing)).unwrap();
+        assert_eq!(kl.compute(), Some(f64::INFINITY));
+        assert!(kl.update((&[&[-1.0, 2.0][..]], &full)).is_err());
+
+        js.reset();
+        assert_eq!(js.compute(), None);
+    }
+}
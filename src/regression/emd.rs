@@ -0,0 +1,185 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{P2Quantile, SampleBuffer, verify_range};
+
+#[derive(Debug, Clone)]
+enum DistributionSamples {
+    Exact {
+        predictions: SampleBuffer<f64>,
+        targets: SampleBuffer<f64>,
+    },
+    Sketched {
+        predictions: Vec<P2Quantile>,
+        targets: Vec<P2Quantile>,
+    },
+}
+
+/// Wasserstein-1 (earth mover's) distance between the empirical distributions of predictions and
+/// targets.
+///
+/// Predictions and targets are treated as two independent 1-D samples, so batches of different
+/// lengths are accepted. By default every value is kept and the distance is exact;
+/// [`EarthMoversDistance::with_quantiles`] instead tracks a fixed grid of streaming
+/// [`P2Quantile`] sketches per side and integrates the gap between the quantile functions, which
+/// keeps memory constant.
+///
+/// ```
+/// use rust_metrics::{EarthMoversDistance, Metric};
+///
+/// let preds = [0.0, 1.0, 3.0];
+/// let target = [5.0, 6.0, 8.0];
+///
+/// let mut emd = EarthMoversDistance::default();
+/// emd.update((&preds, &target)).unwrap();
+/// assert!((emd.compute().unwrap() - 5.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EarthMoversDistance {
+    samples: DistributionSamples,
+}
+
+impl Default for EarthMoversDistance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EarthMoversDistance {
+    pub fn new() -> Self {
+        Self {
+            samples: DistributionSamples::Exact {
+                predictions: SampleBuffer::new(),
+                targets: SampleBuffer::new(),
+            },
+        }
+    }
+
+    /// Approximate the distance from `quantiles` evenly spaced quantile sketches per side.
+    pub fn with_quantiles(mut self, quantiles: usize) -> Self {
+        assert!(quantiles >= 1, "quantiles must be at least 1");
+        let sketches: Vec<P2Quantile> = (0..quantiles)
+            .map(|i| P2Quantile::new((i as f64 + 0.5) / quantiles as f64))
+            .collect();
+        self.samples = DistributionSamples::Sketched {
+            predictions: sketches.clone(),
+            targets: sketches,
+        };
+        self
+    }
+}
+
+impl Metric<(&[f64], &[f64])> for EarthMoversDistance {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[f64])) -> Result<(), MetricError> {
+        for &value in predictions.iter().chain(targets) {
+            verify_range(value, f64::MIN, f64::MAX)?;
+        }
+        match &mut self.samples {
+            DistributionSamples::Exact {
+                predictions: stored_predictions,
+                targets: stored_targets,
+            } => {
+                stored_predictions.push_batch(predictions.to_vec());
+                stored_targets.push_batch(targets.to_vec());
+            }
+            DistributionSamples::Sketched {
+                predictions: prediction_sketches,
+                targets: target_sketches,
+            } => {
+                for sketch in prediction_sketches.iter_mut() {
+                    predictions.iter().for_each(|&value| sketch.observe(value));
+                }
+                for sketch in target_sketches.iter_mut() {
+                    targets.iter().for_each(|&value| sketch.observe(value));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        match &mut self.samples {
+            DistributionSamples::Exact {
+                predictions,
+                targets,
+            } => {
+                predictions.clear();
+                targets.clear();
+            }
+            DistributionSamples::Sketched {
+                predictions,
+                targets,
+            } => {
+                predictions.iter_mut().for_each(P2Quantile::reset);
+                targets.iter_mut().for_each(P2Quantile::reset);
+            }
+        }
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        match &self.samples {
+            DistributionSamples::Exact {
+                predictions,
+                targets,
+            } => {
+                if predictions.is_empty() || targets.is_empty() {
+                    return None;
+                }
+                Some(exact_wasserstein(predictions.to_vec(), targets.to_vec()))
+            }
+            DistributionSamples::Sketched {
+                predictions,
+                targets,
+            } => {
+                let mut gap = 0.0;
+                for (prediction, target) in predictions.iter().zip(targets) {
+                    gap += (prediction.estimate()? - target.estimate()?).abs();
+                }
+                Some(gap / predictions.len() as f64)
+            }
+        }
+    }
+}
+
+/// `integral |F_a(x) - F_b(x)| dx` between the empirical CDFs of two non-empty samples.
+fn exact_wasserstein(mut a: Vec<f64>, mut b: Vec<f64>) -> f64 {
+    a.sort_by(f64::total_cmp);
+    b.sort_by(f64::total_cmp);
+    let mut all: Vec<f64> = a.iter().chain(&b).copied().collect();
+    all.sort_by(f64::total_cmp);
+
+    let cdf = |sorted: &[f64], x: f64| {
+        sorted.partition_point(|&value| value <= x) as f64 / sorted.len() as f64
+    };
+    all.windows(2)
+        .map(|pair| (cdf(&a, pair[0]) - cdf(&b, pair[0])).abs() * (pair[1] - pair[0]))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EarthMoversDistance;
+    use crate::core::Metric;
+
+    #[test]
+    fn emd_exact_and_sketched() {
+        let mut exact = EarthMoversDistance::default();
+        exact.update((&[3.4, 3.9, 7.5], &[4.5, 1.4])).unwrap();
+        exact.update((&[7.8], &[])).unwrap();
+        // scipy.stats.wasserstein_distance([3.4, 3.9, 7.5, 7.8], [4.5, 1.4])
+        assert!((exact.compute().unwrap() - 2.7).abs() < 1e-12);
+
+        let preds: Vec<f64> = (0..2000)
+            .map(|i| ((i * 7919) % 2000) as f64 / 100.0)
+            .collect();
+        let target: Vec<f64> = preds.iter().map(|p| p + 2.5).collect();
+        let mut sketched = EarthMoversDistance::new().with_quantiles(20);
+        sketched.update((&preds, &target)).unwrap();
+        assert!((sketched.compute().unwrap() - 2.5).abs() < 0.1);
+
+        exact.reset();
+        assert_eq!(exact.compute(), None);
+        sketched.reset();
+        assert_eq!(sketched.compute(), None);
+    }
+}
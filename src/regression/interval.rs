@@ -0,0 +1,131 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::{verify_range, verify_reverted_count};
+
+/// Value of [`IntervalCoverage`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoverageSummary {
+    /// Fraction of targets inside their `[lower, upper]` interval.
+    pub coverage: f64,
+    /// Mean `upper - lower` (sharpness); narrower is better at equal coverage.
+    pub mean_width: f64,
+}
+
+/// Empirical coverage and mean width of prediction intervals, e.g. from conformal or quantile
+/// regressors.
+///
+/// Each update takes `(lower, upper, targets)`; bounds are inclusive and `lower` must not exceed
+/// `upper`.
+///
+/// ```
+/// use rust_metrics::{IntervalCoverage, Metric};
+///
+/// let lower = [0.0, 1.0, 2.0, 3.0];
+/// let upper = [1.0, 2.0, 4.0, 3.5];
+/// let target = [0.5, 2.0, 5.0, 3.2];
+///
+/// let mut metric = IntervalCoverage::default();
+/// metric.update((&lower, &upper, &target)).unwrap();
+/// let summary = metric.compute().unwrap();
+/// assert_eq!(summary.coverage, 0.75);
+/// assert_eq!(summary.mean_width, 1.125);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IntervalCoverage {
+    covered: usize,
+    sum_width: f64,
+    total: usize,
+}
+
+impl IntervalCoverage {
+    pub fn new() -> Self {
+        Self {
+            covered: 0,
+            sum_width: 0.0,
+            total: 0,
+        }
+    }
+}
+
+impl Metric<(&[f64], &[f64], &[f64])> for IntervalCoverage {
+    type Output = CoverageSummary;
+
+    fn update(
+        &mut self,
+        (lower, upper, targets): (&[f64], &[f64], &[f64]),
+    ) -> Result<(), MetricError> {
+        for bounds in [lower, upper] {
+            if bounds.len() != targets.len() {
+                return Err(MetricError::LengthMismatch {
+                    predictions: bounds.len(),
+                    targets: targets.len(),
+                });
+            }
+        }
+        for ((&low, &high), &target) in lower.iter().zip(upper).zip(targets) {
+            verify_range(low, f64::MIN, f64::MAX)?;
+            verify_range(high, low, f64::MAX)?;
+            verify_range(target, f64::MIN, f64::MAX)?;
+        }
+        for ((&low, &high), &target) in lower.iter().zip(upper).zip(targets) {
+            if (low..=high).contains(&target) {
+                self.covered += 1;
+            }
+            self.sum_width += high - low;
+        }
+        self.total += targets.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.covered = 0;
+        self.sum_width = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(CoverageSummary {
+            coverage: self.covered as f64 / self.total as f64,
+            mean_width: self.sum_width / self.total as f64,
+        })
+    }
+}
+
+impl Revertible<(&[f64], &[f64], &[f64])> for IntervalCoverage {
+    fn revert(&mut self, input: (&[f64], &[f64], &[f64])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.covered -= batch.covered;
+        self.sum_width -= batch.sum_width;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalCoverage;
+    use crate::core::{Metric, Revertible};
+
+    #[test]
+    fn interval_coverage_over_batches() {
+        let mut metric = IntervalCoverage::default();
+        metric
+            .update((&[0.0, 1.0], &[2.0, 1.0], &[2.0, 1.5]))
+            .unwrap();
+        metric.update((&[-1.0], &[1.0], &[0.0])).unwrap();
+        let summary = metric.compute().unwrap();
+        assert!((summary.coverage - 2.0 / 3.0).abs() < 1e-12);
+        assert!((summary.mean_width - 4.0 / 3.0).abs() < 1e-12);
+
+        metric.revert((&[-1.0], &[1.0], &[0.0])).unwrap();
+        assert_eq!(metric.compute().unwrap().coverage, 0.5);
+        assert!(metric.update((&[1.0], &[0.0], &[0.5])).is_err());
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::core::{Metric, MetricError};
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::verify_reverted_count;
 
 /// Online MeanAbsoluteError
 ///
@@ -59,6 +60,17 @@ impl Metric<(&[f64], &[f64])> for MeanAbsoluteError {
     }
 }
 
+impl Revertible<(&[f64], &[f64])> for MeanAbsoluteError {
+    fn revert(&mut self, input: (&[f64], &[f64])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_abs_error -= batch.sum_abs_error;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{MeanAbsoluteError, Metric};
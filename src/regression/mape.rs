@@ -1,4 +1,5 @@
-use crate::core::{Metric, MetricError};
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::verify_reverted_count;
 
 /// Online MeanAbsolutePercentageError
 ///
@@ -61,9 +62,99 @@ impl Metric<(&[f64], &[f64])> for MeanAbsolutePercentageError {
     }
 }
 
+impl Revertible<(&[f64], &[f64])> for MeanAbsolutePercentageError {
+    fn revert(&mut self, input: (&[f64], &[f64])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_abs_per_error -= batch.sum_abs_per_error;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+/// Online weighted MAPE (WAPE): the sum of absolute errors divided by the sum of absolute
+/// targets.
+///
+/// Unlike [`MeanAbsolutePercentageError`], errors are weighted by target magnitude, so near-zero
+/// targets do not blow up the score. `None` while every target seen so far is zero.
+///
+/// ```
+/// use rust_metrics::{Metric, WeightedMeanAbsolutePercentageError};
+///
+/// let preds = [0.9, 15.0, 1_200_000.0];
+/// let target = [1.0, 10.0, 1_000_000.0];
+///
+/// let mut wape = WeightedMeanAbsolutePercentageError::default();
+/// wape.update((&preds, &target)).unwrap();
+/// let expected = (0.1 + 5.0 + 200_000.0) / (1.0 + 10.0 + 1_000_000.0);
+/// assert!((wape.compute().unwrap() - expected).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WeightedMeanAbsolutePercentageError {
+    sum_abs_error: f64,
+    sum_abs_target: f64,
+    total: usize,
+}
+
+impl WeightedMeanAbsolutePercentageError {
+    pub fn new() -> Self {
+        Self {
+            sum_abs_error: 0.0,
+            sum_abs_target: 0.0,
+            total: 0,
+        }
+    }
+}
+
+impl Metric<(&[f64], &[f64])> for WeightedMeanAbsolutePercentageError {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[f64])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        self.total += predictions.len();
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            self.sum_abs_error += (prediction - target).abs();
+            self.sum_abs_target += target.abs();
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum_abs_error = 0.0;
+        self.sum_abs_target = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 || self.sum_abs_target == 0.0 {
+            return None;
+        }
+        Some(self.sum_abs_error / self.sum_abs_target)
+    }
+}
+
+impl Revertible<(&[f64], &[f64])> for WeightedMeanAbsolutePercentageError {
+    fn revert(&mut self, input: (&[f64], &[f64])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_abs_error -= batch.sum_abs_error;
+        self.sum_abs_target -= batch.sum_abs_target;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{MeanAbsolutePercentageError, Metric};
+    use super::{MeanAbsolutePercentageError, Metric, WeightedMeanAbsolutePercentageError};
 
     #[test]
     fn mape_computes_over_batches() {
@@ -72,4 +163,17 @@ mod tests {
             .unwrap();
         assert!((mape.compute().unwrap() - 0.26666666666666666).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn wape_handles_zero_targets() {
+        let mut wape = WeightedMeanAbsolutePercentageError::default();
+        wape.update((&[0.5], &[0.0])).unwrap();
+        assert_eq!(wape.compute(), None);
+
+        wape.update((&[3.0, 1.0], &[2.0, 2.0])).unwrap();
+        assert!((wape.compute().unwrap() - 2.5 / 4.0).abs() < 1e-12);
+
+        wape.reset();
+        assert_eq!(wape.compute(), None);
+    }
 }
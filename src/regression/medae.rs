@@ -0,0 +1,99 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{P2Quantile, verify_range};
+
+/// Online median absolute error, estimated with a [`P2Quantile`] sketch.
+///
+/// Memory stays constant over unbounded streams; the value is exact for up to five samples and
+/// an approximation afterwards. The sketch cannot forget samples, so this metric does not
+/// implement [`Revertible`](crate::core::Revertible). Non-finite predictions and targets are
+/// rejected.
+///
+/// ```
+/// use rust_metrics::{MedianAbsoluteError, Metric};
+///
+/// let preds = [2.5, 0.0, 2.0, 8.0];
+/// let target = [3.0, -0.5, 2.0, 7.0];
+///
+/// let mut medae = MedianAbsoluteError::default();
+/// medae.update((&preds, &target)).unwrap();
+/// assert!((medae.compute().unwrap() - 0.5).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MedianAbsoluteError {
+    sketch: P2Quantile,
+}
+
+impl Default for MedianAbsoluteError {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MedianAbsoluteError {
+    pub fn new() -> Self {
+        Self {
+            sketch: P2Quantile::new(0.5),
+        }
+    }
+}
+
+impl Metric<(&[f64], &[f64])> for MedianAbsoluteError {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[f64])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for &value in predictions.iter().chain(targets) {
+            verify_range(value, f64::MIN, f64::MAX)?;
+        }
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            self.sketch.observe((prediction - target).abs());
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sketch.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.sketch.estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MedianAbsoluteError, Metric};
+
+    #[test]
+    fn medae_is_robust_to_outliers() {
+        let mut medae = MedianAbsoluteError::default();
+        for batch in 0..50 {
+            let preds: Vec<f64> = (0..20).map(|i| (batch * 20 + i) as f64).collect();
+            // every error is 1.0 except one large outlier per batch
+            let mut target: Vec<f64> = preds.iter().map(|p| p + 1.0).collect();
+            target[0] += 1_000.0;
+            medae.update((&preds, &target)).unwrap();
+        }
+        assert!((medae.compute().unwrap() - 1.0).abs() < 1e-2);
+
+        medae.reset();
+        assert_eq!(medae.compute(), None);
+    }
+
+    #[test]
+    fn medae_rejects_non_finite_values() {
+        let mut medae = MedianAbsoluteError::default();
+        medae
+            .update((&[1.0, 2.0, 3.0, 4.0, 5.0], &[0.0; 5]))
+            .unwrap();
+        assert!(medae.update((&[f64::NAN], &[0.0])).is_err());
+        assert!(medae.update((&[0.0], &[f64::INFINITY])).is_err());
+        assert_eq!(medae.compute(), Some(3.0));
+    }
+}
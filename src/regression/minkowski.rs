@@ -0,0 +1,108 @@
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::verify_reverted_count;
+
+/// Online mean `p`-th power error, `mean(|prediction - target|^p)`.
+///
+/// `p = 1` matches [`MeanAbsoluteError`](crate::MeanAbsoluteError) and `p = 2`
+/// [`MeanSquaredError`](crate::MeanSquaredError); take the `1 / p` power of the result for a
+/// norm in the units of the target.
+///
+/// ```
+/// use rust_metrics::{Metric, MinkowskiError};
+///
+/// let preds = [3.0, 5.0, 2.5, 7.0];
+/// let target = [2.5, 5.0, 4.0, 8.0];
+///
+/// let mut metric = MinkowskiError::new(3.0);
+/// metric.update((&preds, &target)).unwrap();
+/// let expected = (0.125 + 0.0 + 3.375 + 1.0) / 4.0;
+/// assert!((metric.compute().unwrap() - expected).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinkowskiError {
+    p: f64,
+    sum_error: f64,
+    total: usize,
+}
+
+impl MinkowskiError {
+    pub fn new(p: f64) -> Self {
+        assert!(
+            p.is_finite() && p > 0.0,
+            "p must be a positive finite number"
+        );
+        Self {
+            p,
+            sum_error: 0.0,
+            total: 0,
+        }
+    }
+}
+
+impl Metric<(&[f64], &[f64])> for MinkowskiError {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[f64], &[f64])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        self.total += predictions.len();
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            self.sum_error += (prediction - target).abs().powf(self.p);
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum_error = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.sum_error / self.total as f64)
+    }
+}
+
+impl Revertible<(&[f64], &[f64])> for MinkowskiError {
+    fn revert(&mut self, input: (&[f64], &[f64])) -> Result<(), MetricError> {
+        let mut batch = Self::new(self.p);
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_error -= batch.sum_error;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Metric, MinkowskiError};
+    use crate::{MeanAbsoluteError, MeanSquaredError};
+
+    #[test]
+    fn minkowski_generalizes_mae_and_mse() {
+        let preds = [2.5, 0.0, 2.0, 8.0];
+        let target = [3.0, -0.5, 2.0, 7.0];
+
+        let mut mae = MeanAbsoluteError::default();
+        let mut mse = MeanSquaredError::default();
+        mae.update((&preds, &target)).unwrap();
+        mse.update((&preds, &target)).unwrap();
+        for (p, expected) in [(1.0, mae.compute()), (2.0, mse.compute())] {
+            let mut metric = MinkowskiError::new(p);
+            metric.update((&preds[..2], &target[..2])).unwrap();
+            metric.update((&preds[2..], &target[2..])).unwrap();
+            assert!((metric.compute().unwrap() - expected.unwrap()).abs() < 1e-12);
+
+            metric.reset();
+            assert_eq!(metric.compute(), None);
+        }
+    }
+}
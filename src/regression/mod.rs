@@ -2,14 +2,28 @@
 //!
 //! All types implement [`Metric`](crate::core::Metric) for batched updates.
 
+pub mod d2;
+pub mod directional;
+pub mod divergence;
+pub mod emd;
+pub mod interval;
 pub mod mae;
 pub mod mape;
+pub mod medae;
+pub mod minkowski;
 pub mod mse;
 pub mod nrmse;
 pub mod r2;
 
+pub use d2::{D2AbsoluteErrorScore, D2PinballScore};
+pub use directional::DirectionalAccuracy;
+pub use divergence::{JensenShannonDivergence, KlDivergence};
+pub use emd::EarthMoversDistance;
+pub use interval::{CoverageSummary, IntervalCoverage};
 pub use mae::MeanAbsoluteError;
-pub use mape::MeanAbsolutePercentageError;
+pub use mape::{MeanAbsolutePercentageError, WeightedMeanAbsolutePercentageError};
+pub use medae::MedianAbsoluteError;
+pub use minkowski::MinkowskiError;
 pub use mse::MeanSquaredError;
 pub use nrmse::{NormalizationType, NormalizedRootMeanSquaredError};
 pub use r2::R2Score;
@@ -1,4 +1,5 @@
-use crate::core::{Metric, MetricError};
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::verify_reverted_count;
 
 /// Online MeanSquaredError
 ///
@@ -59,9 +60,20 @@ impl Metric<(&[f64], &[f64])> for MeanSquaredError {
     }
 }
 
+impl Revertible<(&[f64], &[f64])> for MeanSquaredError {
+    fn revert(&mut self, input: (&[f64], &[f64])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_squared_error -= batch.sum_squared_error;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{MeanSquaredError, Metric};
+    use super::{MeanSquaredError, Metric, Revertible};
 
     #[test]
     fn mse_computes_over_batches() {
@@ -70,4 +82,15 @@ mod tests {
             .unwrap();
         assert_eq!(mse.compute().unwrap(), 0.8750);
     }
+
+    #[test]
+    fn mse_revert() {
+        let mut mse = MeanSquaredError::default();
+        mse.update((&[3.0, 5.0], &[2.5, 5.0])).unwrap();
+        mse.update((&[2.5, 7.0], &[4.0, 8.0])).unwrap();
+        mse.revert((&[2.5, 7.0], &[4.0, 8.0])).unwrap();
+        assert_eq!(mse.compute().unwrap(), 0.125);
+
+        assert!(mse.revert((&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0])).is_err());
+    }
 }
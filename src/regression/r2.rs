@@ -1,4 +1,5 @@
-use crate::core::{Metric, MetricError};
+use crate::core::{Metric, MetricError, Revertible};
+use crate::utils::verify_reverted_count;
 
 /// Online R2Score
 ///
@@ -70,6 +71,19 @@ impl Metric<(&[f64], &[f64])> for R2Score {
     }
 }
 
+impl Revertible<(&[f64], &[f64])> for R2Score {
+    fn revert(&mut self, input: (&[f64], &[f64])) -> Result<(), MetricError> {
+        let mut batch = Self::new();
+        batch.update(input)?;
+        verify_reverted_count(batch.total, self.total)?;
+        self.sum_squared_error -= batch.sum_squared_error;
+        self.sum_error -= batch.sum_error;
+        self.residual -= batch.residual;
+        self.total -= batch.total;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Metric, R2Score};
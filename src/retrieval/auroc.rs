@@ -0,0 +1,89 @@
+use crate::classification::curve::BinaryScores;
+use crate::core::{Metric, MetricError};
+
+use super::queries::{EmptyTargetAction, RetrievalQueries};
+
+/// Mean over queries of the exact ROC AUC separating each query's relevant documents from its
+/// irrelevant ones.
+///
+/// Inputs are `(scores, relevance, query_ids)` as for [`RetrievalMRR`](crate::RetrievalMRR).
+/// Each query is scored like [`BinaryAuroc`](crate::BinaryAuroc) in exact mode, except that
+/// scores need not lie in `[0, 1]`. Queries without any relevant document score according to
+/// [`EmptyTargetAction`]; queries whose documents are all relevant have no AUROC and are left out.
+///
+/// ```
+/// use rust_metrics::{Metric, RetrievalAUROC};
+///
+/// let query_ids = [0, 0, 0, 1, 1, 1, 1];
+/// let scores = [0.2, 0.3, 0.5, 0.1, 0.3, 0.5, 0.2];
+/// let relevance = [0, 0, 1, 0, 1, 0, 1];
+///
+/// let mut auroc = RetrievalAUROC::default();
+/// auroc.update((&scores, &relevance, &query_ids)).unwrap();
+/// assert_eq!(auroc.compute(), Some(0.75));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RetrievalAUROC {
+    queries: RetrievalQueries,
+    empty_target_action: EmptyTargetAction,
+}
+
+impl RetrievalAUROC {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_empty_target_action(mut self, empty_target_action: EmptyTargetAction) -> Self {
+        self.empty_target_action = empty_target_action;
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize], &[usize])> for RetrievalAUROC {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (scores, relevance, query_ids): (&[f64], &[usize], &[usize]),
+    ) -> Result<(), MetricError> {
+        self.queries.update(scores, relevance, query_ids)
+    }
+
+    fn reset(&mut self) {
+        self.queries.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.queries.mean(self.empty_target_action, |ranked| {
+            let mut scores = BinaryScores::new(0);
+            scores.extend(
+                ranked
+                    .iter()
+                    .map(|&(score, relevance)| (score, relevance > 0))
+                    .collect(),
+            );
+            scores.auroc()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetrievalAUROC;
+    use crate::core::Metric;
+
+    #[test]
+    fn auroc_leaves_out_all_relevant_queries() {
+        let query_ids = [0, 0, 0, 1, 1, 2, 2];
+        let scores = [3.0, 1.0, 2.0, 0.4, 0.4, 5.0, 7.0];
+        let relevance = [1, 0, 0, 1, 0, 1, 2];
+
+        let mut auroc = RetrievalAUROC::new();
+        auroc.update((&scores, &relevance, &query_ids)).unwrap();
+        // query 0 is ranked perfectly, query 1 is a tie, query 2 has no irrelevant document
+        assert_eq!(auroc.compute(), Some(0.75));
+
+        auroc.reset();
+        assert_eq!(auroc.compute(), None);
+    }
+}
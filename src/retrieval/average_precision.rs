@@ -0,0 +1,106 @@
+use crate::core::{Metric, MetricError};
+
+use super::queries::{EmptyTargetAction, RetrievalQueries};
+
+/// Mean average precision: the average over queries of the precision at the rank of each
+/// relevant document, averaged over those documents.
+///
+/// Inputs are `(scores, relevance, query_ids)` as for [`RetrievalMRR`](crate::RetrievalMRR).
+/// With [`with_top_k`](RetrievalMAP::with_top_k) only the `k` highest-scored documents of each
+/// query count, and the precisions are averaged over the relevant documents among them (as in
+/// TorchMetrics); a query without relevant documents there scores `0`. Queries without any
+/// relevant document score according to [`EmptyTargetAction`].
+///
+/// ```
+/// use rust_metrics::{Metric, RetrievalMAP};
+///
+/// let query_ids = [0, 0, 0, 1, 1, 1, 1];
+/// let scores = [0.2, 0.3, 0.5, 0.1, 0.3, 0.5, 0.2];
+/// let relevance = [0, 0, 1, 0, 1, 0, 1];
+///
+/// let mut map = RetrievalMAP::default();
+/// map.update((&scores, &relevance, &query_ids)).unwrap();
+/// assert!((map.compute().unwrap() - 0.7916666666666666).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RetrievalMAP {
+    queries: RetrievalQueries,
+    empty_target_action: EmptyTargetAction,
+    top_k: Option<usize>,
+}
+
+impl RetrievalMAP {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only consider the `top_k` highest-scored documents of each query.
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        assert!(top_k > 0, "top_k must be positive");
+        self.top_k = Some(top_k);
+        self
+    }
+
+    pub fn with_empty_target_action(mut self, empty_target_action: EmptyTargetAction) -> Self {
+        self.empty_target_action = empty_target_action;
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize], &[usize])> for RetrievalMAP {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (scores, relevance, query_ids): (&[f64], &[usize], &[usize]),
+    ) -> Result<(), MetricError> {
+        self.queries.update(scores, relevance, query_ids)
+    }
+
+    fn reset(&mut self) {
+        self.queries.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let top_k = self.top_k.unwrap_or(usize::MAX);
+        self.queries.mean(self.empty_target_action, |ranked| {
+            let mut hits = 0usize;
+            let mut precision_sum = 0.0;
+            for (rank, &(_, relevance)) in ranked.iter().take(top_k).enumerate() {
+                if relevance > 0 {
+                    hits += 1;
+                    precision_sum += hits as f64 / (rank + 1) as f64;
+                }
+            }
+            if hits == 0 {
+                return Some(0.0);
+            }
+            Some(precision_sum / hits as f64)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetrievalMAP;
+    use crate::core::Metric;
+
+    #[test]
+    fn map_with_top_k_cutoff() {
+        let query_ids = [0, 0, 0, 1, 1, 1, 1];
+        let scores = [0.2, 0.3, 0.5, 0.1, 0.3, 0.5, 0.2];
+        let relevance = [0, 0, 1, 0, 1, 0, 1];
+
+        let mut map = RetrievalMAP::new().with_top_k(2);
+        map.update((&scores, &relevance, &query_ids)).unwrap();
+        // query 1 keeps only the hit at rank 2
+        assert!((map.compute().unwrap() - 0.75).abs() < f64::EPSILON);
+
+        let mut top_1 = RetrievalMAP::new().with_top_k(1);
+        top_1.update((&scores, &relevance, &query_ids)).unwrap();
+        assert_eq!(top_1.compute(), Some(0.5));
+
+        top_1.reset();
+        assert_eq!(top_1.compute(), None);
+    }
+}
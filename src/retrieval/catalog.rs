@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::{verify_label, verify_range};
+
+/// Fraction of a catalog of `catalog_size` items that was recommended at least once.
+///
+/// Each update takes the recommended item ids, which must lie in `0..catalog_size`. Low coverage
+/// means the recommender keeps surfacing the same part of the catalog.
+///
+/// ```
+/// use rust_metrics::{CatalogCoverage, Metric};
+///
+/// let mut coverage = CatalogCoverage::new(10);
+/// coverage.update(&[0, 1, 2]).unwrap();
+/// coverage.update(&[2, 3, 1]).unwrap();
+/// assert_eq!(coverage.compute(), Some(0.4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CatalogCoverage {
+    recommended: Vec<bool>,
+    distinct: usize,
+}
+
+impl CatalogCoverage {
+    pub fn new(catalog_size: usize) -> Self {
+        assert!(catalog_size > 0, "catalog_size must be positive");
+        Self {
+            recommended: vec![false; catalog_size],
+            distinct: 0,
+        }
+    }
+}
+
+impl Metric<&[usize]> for CatalogCoverage {
+    type Output = f64;
+
+    fn update(&mut self, items: &[usize]) -> Result<(), MetricError> {
+        for &item in items {
+            verify_label(item, self.recommended.len())?;
+        }
+        for &item in items {
+            if !self.recommended[item] {
+                self.recommended[item] = true;
+                self.distinct += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.recommended.fill(false);
+        self.distinct = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.distinct == 0 {
+            return None;
+        }
+        Some(self.distinct as f64 / self.recommended.len() as f64)
+    }
+}
+
+/// Mean self-information `-log2 p(i)` of the recommended items, in bits.
+///
+/// Popular items carry little information, so higher novelty means less obvious
+/// recommendations. [`from_popularity`](Novelty::from_popularity) takes prior popularities `p(i)`,
+/// e.g. the share of users who interacted with item `i`; [`new`](Novelty::new) instead estimates
+/// them from the recommendations themselves, which makes the score their entropy.
+///
+/// ```
+/// use rust_metrics::{Metric, Novelty};
+///
+/// let mut novelty = Novelty::from_popularity(vec![0.5, 0.25, 0.125]).unwrap();
+/// novelty.update(&[0, 1, 2, 2]).unwrap();
+/// assert_eq!(novelty.compute(), Some(2.25));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Novelty {
+    popularity: Option<Vec<f64>>,
+    counts: HashMap<usize, usize>,
+    total: usize,
+}
+
+impl Novelty {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `popularity[i]` in `(0, 1]` as the probability of item `i`; recommended ids must lie in
+    /// `0..popularity.len()`.
+    pub fn from_popularity(popularity: Vec<f64>) -> Result<Self, MetricError> {
+        for &probability in &popularity {
+            verify_range(probability, f64::MIN_POSITIVE, 1.0)?;
+        }
+        Ok(Self {
+            popularity: Some(popularity),
+            ..Self::default()
+        })
+    }
+}
+
+impl Metric<&[usize]> for Novelty {
+    type Output = f64;
+
+    fn update(&mut self, items: &[usize]) -> Result<(), MetricError> {
+        if let Some(popularity) = &self.popularity {
+            for &item in items {
+                verify_label(item, popularity.len())?;
+            }
+        }
+        for &item in items {
+            *self.counts.entry(item).or_default() += 1;
+        }
+        self.total += items.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.counts.clear();
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        let total = self.total as f64;
+        let information: f64 = self
+            .counts
+            .iter()
+            .map(|(&item, &count)| {
+                let probability = match &self.popularity {
+                    Some(popularity) => popularity[item],
+                    None => count as f64 / total,
+                };
+                -(count as f64) * probability.log2()
+            })
+            .sum();
+        Some(information / total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CatalogCoverage, Novelty};
+    use crate::MetricError;
+    use crate::core::Metric;
+
+    #[test]
+    fn coverage_rejects_items_outside_the_catalog() {
+        let mut coverage = CatalogCoverage::new(4);
+        assert!(matches!(
+            coverage.update(&[1, 4]),
+            Err(MetricError::IncompatibleInput { .. })
+        ));
+        assert_eq!(coverage.compute(), None);
+
+        coverage.update(&[3, 3]).unwrap();
+        assert_eq!(coverage.compute(), Some(0.25));
+        coverage.reset();
+        assert_eq!(coverage.compute(), None);
+    }
+
+    #[test]
+    fn empirical_novelty_is_entropy() {
+        let mut novelty = Novelty::new();
+        novelty.update(&[7, 7, 7, 7]).unwrap();
+        assert_eq!(novelty.compute(), Some(0.0));
+
+        novelty.update(&[1, 2, 3, 4]).unwrap();
+        // 7 has probability 1/2, the others 1/8 each
+        assert!((novelty.compute().unwrap() - 2.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::cosine_similarity;
+
+/// Intra-list diversity: the mean pairwise cosine dissimilarity `1 - cos(a, b)` between the items
+/// of a recommendation slate, averaged over slates.
+///
+/// Each update takes a batch of slates, every slate being the embeddings of its recommended
+/// items. Slates with fewer than two items have no pairs and are left out. Values range from `0`
+/// for slates of identical items to `2`, with `1` for mutually orthogonal embeddings.
+///
+/// ```
+/// use rust_metrics::{IntraListDiversity, Metric};
+///
+/// let similar: [&[f32]; 2] = [&[1.0, 0.0], &[1.0, 0.0]];
+/// let varied: [&[f32]; 3] = [&[1.0, 0.0], &[0.0, 1.0], &[-1.0, 0.0]];
+///
+/// let mut diversity = IntraListDiversity::default();
+/// diversity.update(&[&similar[..], &varied[..]]).unwrap();
+/// // varied: pairs dissimilar by 1, 2, and 1
+/// assert!((diversity.compute().unwrap() - 2.0 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IntraListDiversity {
+    sum: f64,
+    slates: usize,
+}
+
+impl IntraListDiversity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric<&[&[&[f32]]]> for IntraListDiversity {
+    type Output = f64;
+
+    fn update(&mut self, slates: &[&[&[f32]]]) -> Result<(), MetricError> {
+        for slate in slates {
+            let dimension = slate.first().map_or(0, |item| item.len());
+            if let Some(item) = slate.iter().find(|item| item.len() != dimension) {
+                return Err(MetricError::IncompatibleInput {
+                    expected: format!("item embeddings of dimension {}", dimension),
+                    got: format!("dimension {}", item.len()),
+                });
+            }
+        }
+
+        for slate in slates {
+            if slate.len() < 2 {
+                continue;
+            }
+            let mut dissimilarity = 0.0;
+            for (idx, a) in slate.iter().enumerate() {
+                for b in &slate[idx + 1..] {
+                    dissimilarity += 1.0 - cosine_similarity(a, b);
+                }
+            }
+            let pairs = slate.len() * (slate.len() - 1) / 2;
+            self.sum += dissimilarity / pairs as f64;
+            self.slates += 1;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.slates = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.slates == 0 {
+            return None;
+        }
+        Some(self.sum / self.slates as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntraListDiversity;
+    use crate::MetricError;
+    use crate::core::Metric;
+
+    #[test]
+    fn diversity_skips_single_item_slates() {
+        let single: [&[f32]; 1] = [&[1.0, 2.0]];
+        let mut diversity = IntraListDiversity::new();
+        diversity.update(&[&single[..]]).unwrap();
+        assert_eq!(diversity.compute(), None);
+
+        let orthogonal: [&[f32]; 2] = [&[0.0, 3.0], &[2.0, 0.0]];
+        diversity.update(&[&orthogonal[..]]).unwrap();
+        assert!((diversity.compute().unwrap() - 1.0).abs() < 1e-12);
+
+        let ragged: [&[f32]; 2] = [&[0.0, 3.0], &[2.0]];
+        assert!(matches!(
+            diversity.update(&[&ragged[..]]),
+            Err(MetricError::IncompatibleInput { .. })
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::core::{Metric, MetricError};
+
+use super::queries::{EmptyTargetAction, RetrievalQueries};
+
+/// HitRate@k: the fraction of queries with at least one relevant document among their `k`
+/// highest-scored documents.
+///
+/// Inputs are `(scores, relevance, query_ids)` as for [`RetrievalMRR`](crate::RetrievalMRR).
+/// Queries without any relevant document score according to [`EmptyTargetAction`].
+///
+/// ```
+/// use rust_metrics::{Metric, RetrievalHitRate};
+///
+/// let query_ids = [0, 0, 0, 1, 1, 1, 1];
+/// let scores = [0.2, 0.3, 0.5, 0.1, 0.3, 0.5, 0.2];
+/// let relevance = [0, 0, 1, 0, 1, 0, 1];
+///
+/// let mut hit_rate = RetrievalHitRate::new(1);
+/// hit_rate.update((&scores, &relevance, &query_ids)).unwrap();
+/// assert_eq!(hit_rate.compute(), Some(0.5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetrievalHitRate {
+    queries: RetrievalQueries,
+    empty_target_action: EmptyTargetAction,
+    k: usize,
+}
+
+impl RetrievalHitRate {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "k must be positive");
+        Self {
+            queries: RetrievalQueries::default(),
+            empty_target_action: EmptyTargetAction::default(),
+            k,
+        }
+    }
+
+    pub fn with_empty_target_action(mut self, empty_target_action: EmptyTargetAction) -> Self {
+        self.empty_target_action = empty_target_action;
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize], &[usize])> for RetrievalHitRate {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (scores, relevance, query_ids): (&[f64], &[usize], &[usize]),
+    ) -> Result<(), MetricError> {
+        self.queries.update(scores, relevance, query_ids)
+    }
+
+    fn reset(&mut self) {
+        self.queries.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.queries.mean(self.empty_target_action, |ranked| {
+            let hit = ranked
+                .iter()
+                .take(self.k)
+                .any(|&(_, relevance)| relevance > 0);
+            Some(if hit { 1.0 } else { 0.0 })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetrievalHitRate;
+    use crate::core::Metric;
+
+    #[test]
+    fn hit_rate_grows_with_k() {
+        let query_ids = [0, 0, 0, 1, 1, 1, 2, 2];
+        let scores = [0.9, 0.5, 0.1, 0.8, 0.7, 0.6, 0.4, 0.3];
+        let relevance = [0, 0, 1, 0, 1, 0, 1, 0];
+
+        let expected = [(1, 1.0 / 3.0), (2, 2.0 / 3.0), (3, 1.0)];
+        for (k, rate) in expected {
+            let mut hit_rate = RetrievalHitRate::new(k);
+            hit_rate.update((&scores, &relevance, &query_ids)).unwrap();
+            assert!((hit_rate.compute().unwrap() - rate).abs() < f64::EPSILON);
+        }
+    }
+}
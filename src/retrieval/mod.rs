@@ -0,0 +1,27 @@
+//! Retrieval and recommendation metrics over documents scored per query.
+//!
+//! Every struct in this module implements [`Metric`](crate::core::Metric). The ranking metrics
+//! take `(scores, relevance, query_ids)` triples: documents are grouped by query across updates,
+//! ranked by descending score, and the per-query values are averaged. The beyond-accuracy
+//! metrics take the recommended item ids ([`CatalogCoverage`], [`Novelty`], [`Personalization`])
+//! or their embeddings ([`IntraListDiversity`], [`Serendipity`]).
+
+pub mod auroc;
+pub mod average_precision;
+pub mod catalog;
+pub mod diversity;
+pub mod hit_rate;
+pub mod mrr;
+pub mod personalization;
+mod queries;
+pub mod serendipity;
+
+pub use auroc::RetrievalAUROC;
+pub use average_precision::RetrievalMAP;
+pub use catalog::{CatalogCoverage, Novelty};
+pub use diversity::IntraListDiversity;
+pub use hit_rate::RetrievalHitRate;
+pub use mrr::RetrievalMRR;
+pub use personalization::Personalization;
+pub use queries::EmptyTargetAction;
+pub use serendipity::Serendipity;
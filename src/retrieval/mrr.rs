@@ -0,0 +1,111 @@
+use crate::core::{Metric, MetricError};
+
+use super::queries::{EmptyTargetAction, RetrievalQueries};
+
+/// Mean reciprocal rank: the average over queries of `1 / rank` of the first relevant document.
+///
+/// Inputs are `(scores, relevance, query_ids)` with one entry per retrieved document; a document
+/// is relevant when its relevance is positive, and a higher score ranks it earlier. Documents are
+/// grouped by query id across updates, so every document is kept until `reset`. Queries without
+/// relevant documents score according to [`EmptyTargetAction`], `0` by default.
+///
+/// ```
+/// use rust_metrics::{Metric, RetrievalMRR};
+///
+/// let query_ids = [0, 0, 0, 1, 1, 1, 1];
+/// let scores = [0.2, 0.3, 0.5, 0.1, 0.3, 0.5, 0.2];
+/// let relevance = [0, 0, 1, 0, 1, 0, 1];
+///
+/// let mut mrr = RetrievalMRR::default();
+/// mrr.update((&scores, &relevance, &query_ids)).unwrap();
+/// assert_eq!(mrr.compute(), Some(0.75));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RetrievalMRR {
+    queries: RetrievalQueries,
+    empty_target_action: EmptyTargetAction,
+    top_k: Option<usize>,
+}
+
+impl RetrievalMRR {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only look for the first relevant document among the `top_k` highest-scored ones.
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        assert!(top_k > 0, "top_k must be positive");
+        self.top_k = Some(top_k);
+        self
+    }
+
+    pub fn with_empty_target_action(mut self, empty_target_action: EmptyTargetAction) -> Self {
+        self.empty_target_action = empty_target_action;
+        self
+    }
+}
+
+impl Metric<(&[f64], &[usize], &[usize])> for RetrievalMRR {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (scores, relevance, query_ids): (&[f64], &[usize], &[usize]),
+    ) -> Result<(), MetricError> {
+        self.queries.update(scores, relevance, query_ids)
+    }
+
+    fn reset(&mut self) {
+        self.queries.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let top_k = self.top_k.unwrap_or(usize::MAX);
+        self.queries.mean(self.empty_target_action, |ranked| {
+            let first_hit = ranked
+                .iter()
+                .take(top_k)
+                .position(|&(_, relevance)| relevance > 0);
+            Some(first_hit.map_or(0.0, |idx| 1.0 / (idx + 1) as f64))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetrievalMRR;
+    use crate::core::Metric;
+    use crate::retrieval::EmptyTargetAction;
+
+    #[test]
+    fn mrr_groups_queries_across_updates() {
+        let mut mrr = RetrievalMRR::default();
+        mrr.update((&[0.2, 0.3, 0.1], &[0, 0, 1], &[0, 0, 1]))
+            .unwrap();
+        mrr.update((&[0.5, 0.5, 0.3, 0.2], &[1, 0, 1, 1], &[0, 1, 1, 2]))
+            .unwrap();
+        // query 0: first, query 1: second, query 2: first
+        assert!((mrr.compute().unwrap() - 2.5 / 3.0).abs() < f64::EPSILON);
+
+        let mut top_1 = mrr.clone().with_top_k(1);
+        assert!((top_1.compute().unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+        top_1.reset();
+        assert_eq!(top_1.compute(), None);
+    }
+
+    #[test]
+    fn queries_without_relevant_documents() {
+        let scores = [0.9, 0.1, 0.4];
+        let relevance = [0, 1, 0];
+        let query_ids = [0, 0, 1];
+        for (action, expected) in [
+            (EmptyTargetAction::Neg, 0.25),
+            (EmptyTargetAction::Pos, 0.75),
+            (EmptyTargetAction::Skip, 0.5),
+        ] {
+            let mut mrr = RetrievalMRR::new().with_empty_target_action(action);
+            mrr.update((&scores, &relevance, &query_ids)).unwrap();
+            assert_eq!(mrr.compute(), Some(expected));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BinaryHeap;
+
+use crate::core::{Metric, MetricError};
+
+/// Default number of users kept for the pairwise comparison.
+const DEFAULT_MAX_USERS: usize = 1000;
+
+/// Personalization: `1 -` the mean pairwise Jaccard similarity between the top-`k`
+/// recommendations of different users.
+///
+/// Each update takes one ranked recommendation list per user. Values near `0` mean every user
+/// sees the same items, values near `1` that lists barely overlap. Comparing all pairs is
+/// quadratic, so the metric keeps a uniform sample of at most `max_users` users (default
+/// `1000`): every user gets a pseudo-random priority from its arrival order and the lowest
+/// priorities are kept, which makes the sample reproducible for the same update sequence. Below
+/// that many users the result is exact.
+///
+/// ```
+/// use rust_metrics::{Metric, Personalization};
+///
+/// let lists: [&[usize]; 3] = [&[1, 2, 3], &[1, 2, 4], &[5, 6, 7]];
+///
+/// let mut personalization = Personalization::new(3);
+/// personalization.update(&lists).unwrap();
+/// // Jaccard similarities: 0.5, 0, 0
+/// assert!((personalization.compute().unwrap() - (1.0 - 0.5 / 3.0)).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Personalization {
+    k: usize,
+    max_users: usize,
+    sample: BinaryHeap<(u64, Vec<usize>)>,
+    users: u64,
+}
+
+impl Personalization {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "k must be positive");
+        Self {
+            k,
+            max_users: DEFAULT_MAX_USERS,
+            sample: BinaryHeap::new(),
+            users: 0,
+        }
+    }
+
+    /// Compare at most `max_users` sampled users; larger samples are more precise and cost
+    /// quadratic time in `compute`.
+    pub fn with_max_users(mut self, max_users: usize) -> Self {
+        assert!(max_users >= 2, "max_users must be at least 2");
+        self.max_users = max_users;
+        self
+    }
+}
+
+impl Metric<&[&[usize]]> for Personalization {
+    type Output = f64;
+
+    fn update(&mut self, recommendations: &[&[usize]]) -> Result<(), MetricError> {
+        for list in recommendations {
+            let priority = splitmix64(self.users);
+            self.users += 1;
+            if self.sample.len() == self.max_users {
+                match self.sample.peek() {
+                    Some(&(highest, _)) if priority < highest => {
+                        self.sample.pop();
+                    }
+                    _ => continue,
+                }
+            }
+            let mut items: Vec<usize> = list.iter().take(self.k).copied().collect();
+            items.sort_unstable();
+            items.dedup();
+            self.sample.push((priority, items));
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sample.clear();
+        self.users = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.sample.len() < 2 {
+            return None;
+        }
+        let lists: Vec<&[usize]> = self.sample.iter().map(|(_, items)| &items[..]).collect();
+        let mut similarity = 0.0;
+        for (idx, a) in lists.iter().enumerate() {
+            for b in &lists[idx + 1..] {
+                similarity += jaccard(a, b);
+            }
+        }
+        let pairs = lists.len() * (lists.len() - 1) / 2;
+        Some(1.0 - similarity / pairs as f64)
+    }
+}
+
+/// Jaccard similarity of two sorted, deduplicated item lists; two empty lists are identical.
+fn jaccard(a: &[usize], b: &[usize]) -> f64 {
+    let (mut i, mut j, mut shared) = (0, 0, 0usize);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    let union = a.len() + b.len() - shared;
+    if union == 0 {
+        return 1.0;
+    }
+    shared as f64 / union as f64
+}
+
+/// SplitMix64 finalizer, a cheap bijective mix of the arrival index into a sampling priority.
+fn splitmix64(index: u64) -> u64 {
+    let mut z = index.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Personalization;
+    use crate::core::Metric;
+
+    #[test]
+    fn identical_lists_are_not_personalized() {
+        let mut personalization = Personalization::new(2);
+        personalization.update(&[&[4, 5, 9]]).unwrap();
+        assert_eq!(personalization.compute(), None);
+
+        // only the top 2 items count
+        personalization.update(&[&[5, 4, 1], &[4, 5]]).unwrap();
+        assert_eq!(personalization.compute(), Some(0.0));
+
+        personalization.reset();
+        assert_eq!(personalization.compute(), None);
+    }
+
+    #[test]
+    fn sample_is_bounded() {
+        let lists: Vec<Vec<usize>> = (0..50).map(|user| vec![user % 2, 100 + user]).collect();
+        let lists: Vec<&[usize]> = lists.iter().map(|list| &list[..]).collect();
+
+        let mut exact = Personalization::new(2);
+        exact.update(&lists).unwrap();
+        let mut sampled = Personalization::new(2).with_max_users(20);
+        sampled.update(&lists[..25]).unwrap();
+        sampled.update(&lists[25..]).unwrap();
+
+        assert_eq!(sampled.sample.len(), 20);
+        assert!((sampled.compute().unwrap() - exact.compute().unwrap()).abs() < 0.05);
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use crate::core::MetricError;
+use crate::utils::verify_range;
+
+/// Score given to a query without any relevant document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyTargetAction {
+    /// Leave the query out of the average.
+    Skip,
+    /// Count the query as `0`.
+    #[default]
+    Neg,
+    /// Count the query as `1`.
+    Pos,
+}
+
+/// Scored documents grouped by query, shared by the retrieval metrics.
+///
+/// Every `(score, relevance)` pair is kept until `reset`, since a query's ranking is only known
+/// once all of its documents were seen; documents of one query may arrive over several updates.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RetrievalQueries {
+    queries: BTreeMap<usize, Vec<(f64, usize)>>,
+}
+
+impl RetrievalQueries {
+    pub fn update(
+        &mut self,
+        scores: &[f64],
+        relevance: &[usize],
+        query_ids: &[usize],
+    ) -> Result<(), MetricError> {
+        if scores.len() != relevance.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: scores.len(),
+                targets: relevance.len(),
+            });
+        }
+        if query_ids.len() != scores.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: scores.len(),
+                targets: query_ids.len(),
+            });
+        }
+        for &score in scores {
+            verify_range(score, f64::MIN, f64::MAX)?;
+        }
+        for ((&score, &relevance), &query) in scores.iter().zip(relevance).zip(query_ids) {
+            self.queries
+                .entry(query)
+                .or_default()
+                .push((score, relevance));
+        }
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.queries.clear();
+    }
+
+    /// Mean of `score` over all queries, each given its documents ranked by descending score
+    /// (ties keep their update order); queries without relevant documents follow
+    /// `empty_target_action` and queries scored `None` are left out. `None` until a query was
+    /// scored.
+    pub fn mean(
+        &self,
+        empty_target_action: EmptyTargetAction,
+        score: impl Fn(&[(f64, usize)]) -> Option<f64>,
+    ) -> Option<f64> {
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        for documents in self.queries.values() {
+            let value = if documents.iter().any(|&(_, relevance)| relevance > 0) {
+                let mut ranked = documents.clone();
+                ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+                match score(&ranked) {
+                    Some(value) => value,
+                    None => continue,
+                }
+            } else {
+                match empty_target_action {
+                    EmptyTargetAction::Skip => continue,
+                    EmptyTargetAction::Neg => 0.0,
+                    EmptyTargetAction::Pos => 1.0,
+                }
+            };
+            sum += value;
+            count += 1;
+        }
+        if count == 0 {
+            return None;
+        }
+        Some(sum / count as f64)
+    }
+}
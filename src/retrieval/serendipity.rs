@@ -0,0 +1,160 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::cosine_similarity;
+
+/// Serendipity: how much of a slate is both relevant and unlike what the user already knows.
+///
+/// Each update takes, per query, the embeddings of the recommended items, their relevance, and
+/// the embeddings of the user's history as a baseline. An item's unexpectedness is `1 -` its
+/// highest cosine similarity to a history item (negative similarities count as `0`, an empty
+/// history makes every item fully unexpected); relevant items contribute their unexpectedness
+/// and irrelevant ones `0`. The per-query mean over recommended items is averaged over queries
+/// with at least one recommendation.
+///
+/// ```
+/// use rust_metrics::{Metric, Serendipity};
+///
+/// let recommended: [&[f32]; 2] = [&[1.0, 0.0], &[0.0, 1.0]];
+/// let relevance = [1_usize, 1];
+/// let history: [&[f32]; 1] = [&[1.0, 0.0]];
+///
+/// let mut serendipity = Serendipity::default();
+/// serendipity
+///     .update((&[&recommended[..]], &[&relevance[..]], &[&history[..]]))
+///     .unwrap();
+/// // the first item repeats the history, the second is new
+/// assert!((serendipity.compute().unwrap() - 0.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Serendipity {
+    sum: f64,
+    queries: usize,
+}
+
+impl Serendipity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric<(&[&[&[f32]]], &[&[usize]], &[&[&[f32]]])> for Serendipity {
+    type Output = f64;
+
+    fn update(
+        &mut self,
+        (recommended, relevance, history): (&[&[&[f32]]], &[&[usize]], &[&[&[f32]]]),
+    ) -> Result<(), MetricError> {
+        if recommended.len() != relevance.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: recommended.len(),
+                targets: relevance.len(),
+            });
+        }
+        if history.len() != recommended.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: recommended.len(),
+                targets: history.len(),
+            });
+        }
+        for ((items, relevance), history) in recommended.iter().zip(relevance).zip(history) {
+            if items.len() != relevance.len() {
+                return Err(MetricError::LengthMismatch {
+                    predictions: items.len(),
+                    targets: relevance.len(),
+                });
+            }
+            let dimension = items
+                .first()
+                .or(history.first())
+                .map_or(0, |item| item.len());
+            if let Some(item) = items
+                .iter()
+                .chain(history.iter())
+                .find(|item| item.len() != dimension)
+            {
+                return Err(MetricError::IncompatibleInput {
+                    expected: format!("item embeddings of dimension {}", dimension),
+                    got: format!("dimension {}", item.len()),
+                });
+            }
+        }
+
+        for ((items, relevance), history) in recommended.iter().zip(relevance).zip(history) {
+            if items.is_empty() {
+                continue;
+            }
+            let serendipitous: f64 = items
+                .iter()
+                .zip(relevance.iter())
+                .filter(|(_, relevance)| **relevance > 0)
+                .map(|(item, _)| {
+                    let familiarity = history
+                        .iter()
+                        .map(|known| cosine_similarity(item, known))
+                        .fold(0.0, f64::max);
+                    1.0 - familiarity
+                })
+                .sum();
+            self.sum += serendipitous / items.len() as f64;
+            self.queries += 1;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.queries = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.queries == 0 {
+            return None;
+        }
+        Some(self.sum / self.queries as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Serendipity;
+    use crate::core::Metric;
+
+    #[test]
+    fn irrelevant_or_familiar_items_are_not_serendipitous() {
+        let items: [&[f32]; 3] = [&[1.0, 0.0], &[0.0, 1.0], &[0.0, -1.0]];
+        let relevance = [1_usize, 0, 1];
+        let history: [&[f32]; 2] = [&[2.0, 0.0], &[0.0, 1.0]];
+        let no_history: [&[f32]; 0] = [];
+
+        let mut serendipity = Serendipity::new();
+        serendipity
+            .update((&[&items[..]], &[&relevance[..]], &[&history[..]]))
+            .unwrap();
+        // only the third item is relevant and unlike the history
+        assert!((serendipity.compute().unwrap() - 1.0 / 3.0).abs() < 1e-12);
+
+        serendipity
+            .update((&[&items[..]], &[&relevance[..]], &[&no_history[..]]))
+            .unwrap();
+        assert!((serendipity.compute().unwrap() - 0.5).abs() < 1e-12);
+
+        serendipity.reset();
+        assert_eq!(serendipity.compute(), None);
+    }
+
+    #[test]
+    fn empty_slate_with_history_is_skipped() {
+        let items: [&[f32]; 1] = [&[0.0, 1.0]];
+        let no_items: [&[f32]; 0] = [];
+        let history: [&[f32]; 1] = [&[1.0, 0.0]];
+
+        let mut serendipity = Serendipity::new();
+        serendipity
+            .update((
+                &[&items[..], &no_items[..]],
+                &[&[1][..], &[][..]],
+                &[&history[..], &history[..]],
+            ))
+            .unwrap();
+        assert!((serendipity.compute().unwrap() - 1.0).abs() < 1e-12);
+    }
+}
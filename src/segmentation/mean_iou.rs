@@ -0,0 +1,131 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::verify_label;
+
+/// Mean intersection over union of segmentation masks, accumulated over the whole dataset.
+///
+/// Each update takes the predicted and target class id of every pixel, e.g. one or more
+/// flattened masks. Per-class intersections and unions are summed across updates, so every
+/// class is scored on all pixels seen. Unlike
+/// [`MulticlassJaccardIndex`](crate::MulticlassJaccardIndex), which scores per-sample class
+/// probabilities, the mean only covers classes that occur in the predictions or targets.
+///
+/// ```
+/// use rust_metrics::{MeanIoU, Metric};
+///
+/// let preds = [0, 0, 1, 1, 2, 2];
+/// let target = [0, 1, 1, 1, 2, 0];
+///
+/// let mut metric = MeanIoU::new(3);
+/// metric.update((&preds, &target)).unwrap();
+/// // class 0: 1/3, class 1: 2/3, class 2: 1/2
+/// assert!((metric.compute().unwrap() - 0.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeanIoU {
+    num_classes: usize,
+    ignore_index: Option<usize>,
+    intersection: Vec<u64>,
+    predicted: Vec<u64>,
+    target: Vec<u64>,
+}
+
+impl MeanIoU {
+    pub fn new(num_classes: usize) -> Self {
+        assert!(num_classes > 0, "num_classes must be positive");
+        Self {
+            num_classes,
+            ignore_index: None,
+            intersection: vec![0; num_classes],
+            predicted: vec![0; num_classes],
+            target: vec![0; num_classes],
+        }
+    }
+
+    /// Skip pixels whose target equals `ignore_index`, e.g. the `255` void label of many
+    /// segmentation datasets.
+    pub fn with_ignore_index(mut self, ignore_index: usize) -> Self {
+        self.ignore_index = Some(ignore_index);
+        self
+    }
+
+    /// Intersection over union of every class; `None` for classes that never occurred.
+    pub fn compute_per_class(&self) -> Vec<Option<f64>> {
+        (0..self.num_classes)
+            .map(|class| {
+                let union = self.predicted[class] + self.target[class] - self.intersection[class];
+                if union == 0 {
+                    return None;
+                }
+                Some(self.intersection[class] as f64 / union as f64)
+            })
+            .collect()
+    }
+}
+
+impl Metric<(&[usize], &[usize])> for MeanIoU {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[usize], &[usize])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        let pixels: Vec<(usize, usize)> = predictions
+            .iter()
+            .copied()
+            .zip(targets.iter().copied())
+            .filter(|&(_, target)| Some(target) != self.ignore_index)
+            .collect();
+        for &(prediction, target) in &pixels {
+            verify_label(prediction, self.num_classes)?;
+            verify_label(target, self.num_classes)?;
+        }
+        for (prediction, target) in pixels {
+            self.predicted[prediction] += 1;
+            self.target[target] += 1;
+            if prediction == target {
+                self.intersection[target] += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.intersection.fill(0);
+        self.predicted.fill(0);
+        self.target.fill(0);
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let present: Vec<f64> = self.compute_per_class().into_iter().flatten().collect();
+        if present.is_empty() {
+            return None;
+        }
+        Some(present.iter().sum::<f64>() / present.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeanIoU;
+    use crate::core::Metric;
+
+    #[test]
+    fn mean_iou_pools_pixels_across_updates() {
+        let mut metric = MeanIoU::new(3).with_ignore_index(255);
+        metric.update((&[0, 1, 1], &[0, 1, 255])).unwrap();
+        metric.update((&[1, 0], &[0, 0])).unwrap();
+
+        assert_eq!(
+            metric.compute_per_class(),
+            vec![Some(2.0 / 3.0), Some(0.5), None]
+        );
+        assert!((metric.compute().unwrap() - 7.0 / 12.0).abs() < 1e-12);
+
+        assert!(metric.update((&[3], &[0])).is_err());
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
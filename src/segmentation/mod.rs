@@ -0,0 +1,8 @@
+//! Semantic segmentation metrics over per-pixel class ids.
+//!
+//! All types implement [`Metric`](crate::core::Metric) for batched updates; pass flattened masks
+//! of predicted and target class ids.
+
+pub mod mean_iou;
+
+pub use mean_iou::MeanIoU;
@@ -0,0 +1,182 @@
+use crate::core::MetricError;
+
+/// Tagging scheme used to encode chunk boundaries in a tag sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaggingScheme {
+    /// `B-`/`I-`/`O` tags where every chunk starts with `B-` (also known as BIO).
+    #[default]
+    Iob2,
+    /// `B-`/`I-`/`L-`/`U-`/`O` tags. The equivalent BIOES `E-`/`S-` prefixes are accepted too.
+    Bilou,
+}
+
+/// A typed span `[start, end)` decoded from a tag sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chunk {
+    pub label: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Chunk {
+    /// Whether both chunks share a type and at least one token.
+    pub fn overlaps(&self, other: &Chunk) -> bool {
+        self.label == other.label && self.start < other.end && other.start < self.end
+    }
+}
+
+/// How predicted chunks are matched against gold chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkMatch {
+    /// Type and boundaries must match exactly.
+    #[default]
+    Strict,
+    /// Type must match and the spans must share at least one token.
+    Partial,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Prefix {
+    Begin,
+    Inside,
+    Last,
+    Unit,
+    Outside,
+}
+
+fn parse_tag(tag: &str, scheme: TaggingScheme) -> Result<(Prefix, &str), MetricError> {
+    if tag == "O" {
+        return Ok((Prefix::Outside, ""));
+    }
+    let (prefix, label) = tag.split_once('-').unwrap_or((tag, ""));
+    let prefix = match (prefix, scheme) {
+        ("B", _) => Prefix::Begin,
+        ("I", _) => Prefix::Inside,
+        ("L" | "E", TaggingScheme::Bilou) => Prefix::Last,
+        ("U" | "S", TaggingScheme::Bilou) => Prefix::Unit,
+        _ => {
+            return Err(MetricError::IncompatibleInput {
+                expected: format!("tag valid for the {:?} scheme", scheme),
+                got: tag.to_string(),
+            });
+        }
+    };
+    Ok((prefix, label))
+}
+
+/// Decode the chunks of a single tag sequence.
+///
+/// Decoding is lenient in the same way as `conlleval`: an `I-` tag that does not continue a chunk
+/// of the same type opens a new chunk instead of being rejected.
+///
+/// ```
+/// use rust_metrics::sequence::{Chunk, TaggingScheme, extract_chunks};
+///
+/// let chunks = extract_chunks(&["B-PER", "I-PER", "O", "B-LOC"], TaggingScheme::Iob2).unwrap();
+/// assert_eq!(chunks[0], Chunk { label: "PER".to_string(), start: 0, end: 2 });
+/// assert_eq!(chunks[1], Chunk { label: "LOC".to_string(), start: 3, end: 4 });
+/// ```
+pub fn extract_chunks(tags: &[&str], scheme: TaggingScheme) -> Result<Vec<Chunk>, MetricError> {
+    let mut chunks = Vec::new();
+    let mut open: Option<(&str, usize)> = None;
+
+    for (idx, &tag) in tags.iter().enumerate() {
+        let (prefix, label) = parse_tag(tag, scheme)?;
+        let continues = matches!(open, Some((open_label, _)) if open_label == label);
+
+        match prefix {
+            Prefix::Inside if continues => {}
+            Prefix::Last if continues => {
+                let (_, start) = open.take().expect("continued chunk must be open");
+                chunks.push(Chunk {
+                    label: label.to_string(),
+                    start,
+                    end: idx + 1,
+                });
+            }
+            _ => {
+                if let Some((open_label, start)) = open.take() {
+                    chunks.push(Chunk {
+                        label: open_label.to_string(),
+                        start,
+                        end: idx,
+                    });
+                }
+                match prefix {
+                    Prefix::Begin | Prefix::Inside => open = Some((label, idx)),
+                    Prefix::Last | Prefix::Unit => chunks.push(Chunk {
+                        label: label.to_string(),
+                        start: idx,
+                        end: idx + 1,
+                    }),
+                    Prefix::Outside => {}
+                }
+            }
+        }
+    }
+
+    if let Some((open_label, start)) = open {
+        chunks.push(Chunk {
+            label: open_label.to_string(),
+            start,
+            end: tags.len(),
+        });
+    }
+
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Chunk, TaggingScheme, extract_chunks};
+
+    fn chunk(label: &str, start: usize, end: usize) -> Chunk {
+        Chunk {
+            label: label.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn iob2_chunks() {
+        let tags = ["B-PER", "I-PER", "O", "I-LOC", "B-LOC", "B-MISC", "I-PER"];
+        let chunks = extract_chunks(&tags, TaggingScheme::Iob2).unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                chunk("PER", 0, 2),
+                chunk("LOC", 3, 4),
+                chunk("LOC", 4, 5),
+                chunk("MISC", 5, 6),
+                chunk("PER", 6, 7),
+            ]
+        );
+
+        assert!(extract_chunks(&["U-PER"], TaggingScheme::Iob2).is_err());
+    }
+
+    #[test]
+    fn bilou_chunks() {
+        let tags = [
+            "U-PER", "B-LOC", "I-LOC", "L-LOC", "O", "S-ORG", "B-ORG", "E-ORG",
+        ];
+        let chunks = extract_chunks(&tags, TaggingScheme::Bilou).unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                chunk("PER", 0, 1),
+                chunk("LOC", 1, 4),
+                chunk("ORG", 5, 6),
+                chunk("ORG", 6, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn chunk_overlap() {
+        assert!(chunk("PER", 0, 3).overlaps(&chunk("PER", 2, 4)));
+        assert!(!chunk("PER", 0, 2).overlaps(&chunk("PER", 2, 4)));
+        assert!(!chunk("PER", 0, 3).overlaps(&chunk("LOC", 0, 3)));
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::core::{Metric, MetricError};
+
+use super::chunk::{TaggingScheme, extract_chunks};
+
+/// Precision, recall, and F1 for a set of entity spans.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EntityScores {
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+    /// Number of gold entities.
+    pub support: usize,
+}
+
+/// Span-level scores reported by [`EntityF1`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EntityReport {
+    /// Scores over all entities pooled together.
+    pub micro_avg: EntityScores,
+    /// Unweighted mean of the per-type scores.
+    pub macro_avg: EntityScores,
+    pub per_type: HashMap<String, EntityScores>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct EntityCounts {
+    true_positive: usize,
+    predicted: usize,
+    gold: usize,
+}
+
+impl EntityCounts {
+    fn scores(&self) -> EntityScores {
+        let precision = ratio(self.true_positive, self.predicted);
+        let recall = ratio(self.true_positive, self.gold);
+        let f1 = if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        };
+        EntityScores {
+            precision,
+            recall,
+            f1,
+            support: self.gold,
+        }
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Span-level (seqeval-style) precision, recall, and F1 for named entity recognition.
+///
+/// Each sample is a sequence of tags. Entities are decoded from predicted and gold tags with the
+/// configured [`TaggingScheme`], and a predicted entity only counts as correct when both its type
+/// and its exact boundaries match a gold entity.
+///
+/// ```
+/// use rust_metrics::{EntityF1, Metric};
+///
+/// let preds: [&[&str]; 2] = [
+///     &["O", "O", "B-MISC", "I-MISC", "I-MISC", "I-MISC", "O"],
+///     &["B-PER", "I-PER", "O"],
+/// ];
+/// let targets: [&[&str]; 2] = [
+///     &["O", "O", "O", "B-MISC", "I-MISC", "I-MISC", "O"],
+///     &["B-PER", "I-PER", "O"],
+/// ];
+///
+/// let mut metric = EntityF1::default();
+/// metric.update((&preds, &targets)).unwrap();
+/// let report = metric.compute().unwrap();
+/// assert!((report.micro_avg.f1 - 0.5).abs() < f64::EPSILON);
+/// assert_eq!(report.per_type["PER"].f1, 1.0);
+/// assert_eq!(report.per_type["MISC"].f1, 0.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EntityF1 {
+    scheme: TaggingScheme,
+    counts: BTreeMap<String, EntityCounts>,
+    total: usize,
+}
+
+impl EntityF1 {
+    pub fn new(scheme: TaggingScheme) -> Self {
+        Self {
+            scheme,
+            counts: BTreeMap::new(),
+            total: 0,
+        }
+    }
+}
+
+impl Metric<(&[&[&str]], &[&[&str]])> for EntityF1 {
+    type Output = EntityReport;
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[&str]], &[&[&str]]),
+    ) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+
+        let mut decoded = Vec::with_capacity(predictions.len());
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            if prediction.len() != target.len() {
+                return Err(MetricError::LengthMismatch {
+                    predictions: prediction.len(),
+                    targets: target.len(),
+                });
+            }
+            decoded.push((
+                extract_chunks(prediction, self.scheme)?,
+                extract_chunks(target, self.scheme)?,
+            ));
+        }
+
+        for (predicted_chunks, gold_chunks) in decoded {
+            let gold_set: HashSet<_> = gold_chunks.iter().collect();
+            for chunk in &predicted_chunks {
+                let counts = self.counts.entry(chunk.label.clone()).or_default();
+                counts.predicted += 1;
+                if gold_set.contains(chunk) {
+                    counts.true_positive += 1;
+                }
+            }
+            for chunk in &gold_chunks {
+                self.counts.entry(chunk.label.clone()).or_default().gold += 1;
+            }
+            self.total += 1;
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.counts.clear();
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+
+        let mut pooled = EntityCounts::default();
+        let mut macro_avg = EntityScores::default();
+        let mut per_type = HashMap::new();
+        for (label, counts) in &self.counts {
+            pooled.true_positive += counts.true_positive;
+            pooled.predicted += counts.predicted;
+            pooled.gold += counts.gold;
+
+            let scores = counts.scores();
+            macro_avg.precision += scores.precision;
+            macro_avg.recall += scores.recall;
+            macro_avg.f1 += scores.f1;
+            per_type.insert(label.clone(), scores);
+        }
+
+        let num_types = self.counts.len().max(1) as f64;
+        macro_avg.precision /= num_types;
+        macro_avg.recall /= num_types;
+        macro_avg.f1 /= num_types;
+        macro_avg.support = pooled.gold;
+
+        Some(EntityReport {
+            micro_avg: pooled.scores(),
+            macro_avg,
+            per_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntityF1;
+    use crate::core::{Metric, MetricError};
+    use crate::sequence::TaggingScheme;
+
+    #[test]
+    fn entity_f1_over_batches() {
+        let mut metric = EntityF1::default();
+
+        let preds: [&[&str]; 1] = [&["O", "O", "B-MISC", "I-MISC", "I-MISC", "I-MISC", "O"]];
+        let targets: [&[&str]; 1] = [&["O", "O", "O", "B-MISC", "I-MISC", "I-MISC", "O"]];
+        metric.update((&preds, &targets)).unwrap();
+
+        let preds: [&[&str]; 1] = [&["B-PER", "I-PER", "O"]];
+        let targets: [&[&str]; 1] = [&["B-PER", "I-PER", "O"]];
+        metric.update((&preds, &targets)).unwrap();
+
+        let report = metric.compute().unwrap();
+        assert!((report.micro_avg.precision - 0.5).abs() < f64::EPSILON);
+        assert!((report.micro_avg.recall - 0.5).abs() < f64::EPSILON);
+        assert!((report.macro_avg.f1 - 0.5).abs() < f64::EPSILON);
+        assert_eq!(report.micro_avg.support, 2);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn entity_f1_bilou_and_validation() {
+        let mut metric = EntityF1::new(TaggingScheme::Bilou);
+        let preds: [&[&str]; 1] = [&["U-LOC", "B-PER", "L-PER", "O"]];
+        let targets: [&[&str]; 1] = [&["U-LOC", "B-PER", "I-PER", "L-PER"]];
+        metric.update((&preds, &targets)).unwrap();
+        let report = metric.compute().unwrap();
+        assert_eq!(report.per_type["LOC"].f1, 1.0);
+        assert_eq!(report.per_type["PER"].f1, 0.0);
+
+        let preds: [&[&str]; 1] = [&["O", "B-PER"]];
+        let targets: [&[&str]; 1] = [&["O"]];
+        let err = metric.update((&preds, &targets)).unwrap_err();
+        assert_eq!(
+            err,
+            MetricError::LengthMismatch {
+                predictions: 2,
+                targets: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Sequence labeling metrics that score typed spans decoded from tag sequences.
+//!
+//! All types implement [`Metric`](crate::core::Metric) for batched updates. The chunk decoding
+//! used by the metrics is exposed through [`extract_chunks`].
+
+pub mod chunk;
+pub mod entity_f1;
+pub mod precision_recall;
+
+pub use chunk::{Chunk, ChunkMatch, TaggingScheme, extract_chunks};
+pub use entity_f1::{EntityF1, EntityReport, EntityScores};
+pub use precision_recall::{ChunkPrecision, ChunkRecall};
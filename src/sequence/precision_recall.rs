@@ -0,0 +1,223 @@
+use crate::core::{Metric, MetricError};
+
+use super::chunk::{Chunk, ChunkMatch, TaggingScheme, extract_chunks};
+
+/// Matched and total chunk counts shared by [`ChunkPrecision`] and [`ChunkRecall`].
+#[derive(Debug, Clone, Default)]
+struct ChunkCounts {
+    scheme: TaggingScheme,
+    matching: ChunkMatch,
+    matched_predicted: usize,
+    predicted: usize,
+    matched_gold: usize,
+    gold: usize,
+    total: usize,
+}
+
+impl ChunkCounts {
+    fn new(scheme: TaggingScheme, matching: ChunkMatch) -> Self {
+        Self {
+            scheme,
+            matching,
+            ..Default::default()
+        }
+    }
+
+    fn matches(&self, chunk: &Chunk, others: &[Chunk]) -> bool {
+        match self.matching {
+            ChunkMatch::Strict => others.contains(chunk),
+            ChunkMatch::Partial => others.iter().any(|other| chunk.overlaps(other)),
+        }
+    }
+
+    fn update(
+        &mut self,
+        (predictions, targets): (&[&[&str]], &[&[&str]]),
+    ) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+
+        let mut decoded = Vec::with_capacity(predictions.len());
+        for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
+            if prediction.len() != target.len() {
+                return Err(MetricError::LengthMismatch {
+                    predictions: prediction.len(),
+                    targets: target.len(),
+                });
+            }
+            decoded.push((
+                extract_chunks(prediction, self.scheme)?,
+                extract_chunks(target, self.scheme)?,
+            ));
+        }
+
+        for (predicted_chunks, gold_chunks) in decoded {
+            for chunk in &predicted_chunks {
+                if self.matches(chunk, &gold_chunks) {
+                    self.matched_predicted += 1;
+                }
+            }
+            for chunk in &gold_chunks {
+                if self.matches(chunk, &predicted_chunks) {
+                    self.matched_gold += 1;
+                }
+            }
+            self.predicted += predicted_chunks.len();
+            self.gold += gold_chunks.len();
+            self.total += 1;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.matched_predicted = 0;
+        self.predicted = 0;
+        self.matched_gold = 0;
+        self.gold = 0;
+        self.total = 0;
+    }
+}
+
+/// Fraction of predicted chunks that match a gold chunk.
+///
+/// With [`ChunkMatch::Partial`] a predicted chunk is credited when it overlaps a gold chunk of
+/// the same type.
+///
+/// ```
+/// use rust_metrics::{ChunkPrecision, Metric};
+/// use rust_metrics::sequence::{ChunkMatch, TaggingScheme};
+///
+/// let preds: [&[&str]; 1] = [&["B-NP", "I-NP", "I-NP", "O", "B-VP"]];
+/// let targets: [&[&str]; 1] = [&["B-NP", "I-NP", "O", "O", "B-VP"]];
+///
+/// let mut strict = ChunkPrecision::default();
+/// strict.update((&preds, &targets)).unwrap();
+/// assert_eq!(strict.compute(), Some(0.5));
+///
+/// let mut partial = ChunkPrecision::new(TaggingScheme::Iob2, ChunkMatch::Partial);
+/// partial.update((&preds, &targets)).unwrap();
+/// assert_eq!(partial.compute(), Some(1.0));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChunkPrecision {
+    counts: ChunkCounts,
+}
+
+impl ChunkPrecision {
+    pub fn new(scheme: TaggingScheme, matching: ChunkMatch) -> Self {
+        Self {
+            counts: ChunkCounts::new(scheme, matching),
+        }
+    }
+}
+
+impl Metric<(&[&[&str]], &[&[&str]])> for ChunkPrecision {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[&[&str]], &[&[&str]])) -> Result<(), MetricError> {
+        self.counts.update(input)
+    }
+
+    fn reset(&mut self) {
+        self.counts.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.counts.total == 0 {
+            return None;
+        }
+        if self.counts.predicted == 0 {
+            return Some(0.0);
+        }
+        Some(self.counts.matched_predicted as f64 / self.counts.predicted as f64)
+    }
+}
+
+/// Fraction of gold chunks recovered by a predicted chunk.
+///
+/// With [`ChunkMatch::Partial`] a gold chunk is credited when it overlaps a predicted chunk of
+/// the same type.
+///
+/// ```
+/// use rust_metrics::{ChunkRecall, Metric};
+///
+/// let preds: [&[&str]; 1] = [&["B-NP", "I-NP", "O", "O", "O"]];
+/// let targets: [&[&str]; 1] = [&["B-NP", "I-NP", "O", "O", "B-VP"]];
+///
+/// let mut recall = ChunkRecall::default();
+/// recall.update((&preds, &targets)).unwrap();
+/// assert_eq!(recall.compute(), Some(0.5));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChunkRecall {
+    counts: ChunkCounts,
+}
+
+impl ChunkRecall {
+    pub fn new(scheme: TaggingScheme, matching: ChunkMatch) -> Self {
+        Self {
+            counts: ChunkCounts::new(scheme, matching),
+        }
+    }
+}
+
+impl Metric<(&[&[&str]], &[&[&str]])> for ChunkRecall {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[&[&str]], &[&[&str]])) -> Result<(), MetricError> {
+        self.counts.update(input)
+    }
+
+    fn reset(&mut self) {
+        self.counts.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.counts.total == 0 {
+            return None;
+        }
+        if self.counts.gold == 0 {
+            return Some(0.0);
+        }
+        Some(self.counts.matched_gold as f64 / self.counts.gold as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkPrecision, ChunkRecall};
+    use crate::core::Metric;
+    use crate::sequence::{ChunkMatch, TaggingScheme};
+
+    #[test]
+    fn chunk_precision_recall_over_batches() {
+        let mut precision = ChunkPrecision::default();
+        let mut recall = ChunkRecall::default();
+
+        let preds: [&[&str]; 2] = [&["B-PER", "I-PER", "O"], &["B-LOC", "O", "B-ORG"]];
+        let targets: [&[&str]; 2] = [&["B-PER", "I-PER", "O"], &["B-LOC", "I-LOC", "O"]];
+        precision.update((&preds, &targets)).unwrap();
+        recall.update((&preds, &targets)).unwrap();
+
+        assert!((precision.compute().unwrap() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(recall.compute(), Some(0.5));
+
+        precision.reset();
+        recall.reset();
+        assert_eq!(precision.compute(), None);
+        assert_eq!(recall.compute(), None);
+    }
+
+    #[test]
+    fn partial_matching_credits_overlaps() {
+        let mut recall = ChunkRecall::new(TaggingScheme::Bilou, ChunkMatch::Partial);
+        let preds: [&[&str]; 1] = [&["O", "U-PER", "O", "U-LOC"]];
+        let targets: [&[&str]; 1] = [&["B-PER", "L-PER", "O", "U-ORG"]];
+        recall.update((&preds, &targets)).unwrap();
+        assert_eq!(recall.compute(), Some(0.5));
+    }
+}
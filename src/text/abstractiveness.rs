@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::{MetricAggregator, Reduction, count_ngrams, normalize, tokenize};
+
+/// Fraction of summary n-grams that never appear in the source document, averaged over samples.
+///
+/// Inputs are `(summaries, sources)`: each generated summary is compared with the document it
+/// summarizes rather than with a reference summary. Text is normalized like
+/// [`RougeScore`](crate::RougeScore). Summaries shorter than `n` tokens are skipped.
+///
+/// ```
+/// use rust_metrics::{Metric, NovelNGramRatio};
+///
+/// let summaries = ["the cat sat on a rug"];
+/// let sources = ["the cat sat on the mat"];
+///
+/// let mut metric = NovelNGramRatio::new(1);
+/// metric.update((&summaries, &sources)).unwrap();
+/// assert!((metric.compute().unwrap() - 2.0 / 6.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NovelNGramRatio {
+    n_gram: usize,
+    metric_aggregator: MetricAggregator,
+}
+
+impl Default for NovelNGramRatio {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl NovelNGramRatio {
+    pub fn new(n_gram: usize) -> Self {
+        assert!(n_gram >= 1, "n_gram must be at least 1");
+        Self {
+            n_gram,
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for NovelNGramRatio {
+    type Output = f64;
+
+    fn update(&mut self, (summaries, sources): (&[&str], &[&str])) -> Result<(), MetricError> {
+        if summaries.len() != sources.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: summaries.len(),
+                targets: sources.len(),
+            });
+        }
+
+        for (summary, source) in summaries.iter().zip(sources.iter()) {
+            let summary_norm = normalize(summary);
+            let source_norm = normalize(source);
+            let summary_counts = count_ngrams(&tokenize(&summary_norm), self.n_gram);
+            let source_counts = count_ngrams(&tokenize(&source_norm), self.n_gram);
+
+            let total: usize = summary_counts.values().sum();
+            if total == 0 {
+                continue;
+            }
+            let novel: usize = summary_counts
+                .iter()
+                .filter(|(ngram, _)| !source_counts.contains_key(*ngram))
+                .map(|(_, &count)| count)
+                .sum();
+            self.metric_aggregator.update(novel as f64 / total as f64);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+/// Greedily match the longest shared token fragments between a summary and its source
+/// (Grusky et al., 2018) and return their lengths.
+fn extractive_fragments(summary: &[&str], source: &[&str]) -> Vec<usize> {
+    let source_tokens: HashSet<&str> = source.iter().copied().collect();
+    let mut fragments = Vec::new();
+    let mut i = 0;
+    while i < summary.len() {
+        let mut longest = 0;
+        if source_tokens.contains(summary[i]) {
+            let mut j = 0;
+            while j < source.len() {
+                if summary[i] == source[j] {
+                    let mut length = 0;
+                    while i + length < summary.len()
+                        && j + length < source.len()
+                        && summary[i + length] == source[j + length]
+                    {
+                        length += 1;
+                    }
+                    longest = longest.max(length);
+                    j += length;
+                } else {
+                    j += 1;
+                }
+            }
+        }
+        if longest > 0 {
+            fragments.push(longest);
+        }
+        i += longest.max(1);
+    }
+    fragments
+}
+
+fn fragment_statistic(
+    aggregator: &mut MetricAggregator,
+    (summaries, sources): (&[&str], &[&str]),
+    statistic: fn(usize) -> f64,
+) -> Result<(), MetricError> {
+    if summaries.len() != sources.len() {
+        return Err(MetricError::LengthMismatch {
+            predictions: summaries.len(),
+            targets: sources.len(),
+        });
+    }
+
+    for (summary, source) in summaries.iter().zip(sources.iter()) {
+        let summary_norm = normalize(summary);
+        let source_norm = normalize(source);
+        let summary_tokens = tokenize(&summary_norm);
+        if summary_tokens.is_empty() {
+            continue;
+        }
+        let fragments = extractive_fragments(&summary_tokens, &tokenize(&source_norm));
+        let total: f64 = fragments.into_iter().map(statistic).sum();
+        aggregator.update(total / summary_tokens.len() as f64);
+    }
+    Ok(())
+}
+
+/// Extractive fragment coverage: the share of summary tokens that belong to a fragment copied
+/// from the source, averaged over samples.
+///
+/// Inputs are `(summaries, sources)`.
+///
+/// ```
+/// use rust_metrics::{ExtractiveFragmentCoverage, Metric};
+///
+/// let summaries = ["the cat sat on a rug"];
+/// let sources = ["the cat sat on the mat"];
+///
+/// let mut metric = ExtractiveFragmentCoverage::default();
+/// metric.update((&summaries, &sources)).unwrap();
+/// assert!((metric.compute().unwrap() - 4.0 / 6.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExtractiveFragmentCoverage {
+    metric_aggregator: MetricAggregator,
+}
+
+impl Default for ExtractiveFragmentCoverage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExtractiveFragmentCoverage {
+    pub fn new() -> Self {
+        Self {
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for ExtractiveFragmentCoverage {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[&str], &[&str])) -> Result<(), MetricError> {
+        fragment_statistic(&mut self.metric_aggregator, input, |length| length as f64)
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+/// Extractive fragment density: the average length of the copied fragment each summary token
+/// belongs to, averaged over samples. Long copied passages push this up quadratically.
+///
+/// Inputs are `(summaries, sources)`.
+///
+/// ```
+/// use rust_metrics::{ExtractiveFragmentDensity, Metric};
+///
+/// let summaries = ["the cat sat on a rug"];
+/// let sources = ["the cat sat on the mat"];
+///
+/// let mut metric = ExtractiveFragmentDensity::default();
+/// metric.update((&summaries, &sources)).unwrap();
+/// assert!((metric.compute().unwrap() - 16.0 / 6.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExtractiveFragmentDensity {
+    metric_aggregator: MetricAggregator,
+}
+
+impl Default for ExtractiveFragmentDensity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExtractiveFragmentDensity {
+    pub fn new() -> Self {
+        Self {
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for ExtractiveFragmentDensity {
+    type Output = f64;
+
+    fn update(&mut self, input: (&[&str], &[&str])) -> Result<(), MetricError> {
+        fragment_statistic(&mut self.metric_aggregator, input, |length| {
+            (length * length) as f64
+        })
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ExtractiveFragmentCoverage, ExtractiveFragmentDensity, NovelNGramRatio,
+        extractive_fragments,
+    };
+    use crate::core::Metric;
+
+    #[test]
+    fn fragments_are_greedy() {
+        let summary = ["a", "b", "c", "x", "c", "d"];
+        let source = ["a", "b", "z", "a", "b", "c", "d"];
+        assert_eq!(extractive_fragments(&summary, &source), vec![3, 2]);
+    }
+
+    #[test]
+    fn abstractiveness_over_batches() {
+        let summaries = ["the cat sat", "completely new words"];
+        let sources = ["the cat sat on the mat", "the cat sat on the mat"];
+
+        let mut novel = NovelNGramRatio::default();
+        novel.update((&summaries, &sources)).unwrap();
+        assert!((novel.compute().unwrap() - 0.5).abs() < f64::EPSILON);
+
+        let mut coverage = ExtractiveFragmentCoverage::default();
+        coverage.update((&summaries, &sources)).unwrap();
+        assert!((coverage.compute().unwrap() - 0.5).abs() < f64::EPSILON);
+
+        let mut density = ExtractiveFragmentDensity::default();
+        density.update((&summaries, &sources)).unwrap();
+        assert!((density.compute().unwrap() - 1.5).abs() < f64::EPSILON);
+
+        density.reset();
+        assert_eq!(density.compute(), None);
+    }
+}
@@ -1,15 +1,29 @@
+use std::fs;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use fastembed::TextEmbedding;
+pub use fastembed::{EmbeddingModel, InitOptionsUserDefined, Pooling, TextInitOptions};
+use fastembed::{TextEmbedding, TokenizerFiles, UserDefinedEmbeddingModel};
 
 use crate::{
     core::{Metric, MetricError},
-    utils::cosine_similarity,
+    utils::{MetricAggregator, Reduction, SimilarityFunction},
 };
 
-/// Cosine similarity between sentence embeddings produced by `fastembed`.
+/// Similarity between sentence embeddings produced by `fastembed`, cosine unless another
+/// [`SimilarityFunction`] is chosen with
+/// [`with_similarity`](SentenceEmbeddingSimilarity::with_similarity).
 ///
-/// Requires the `text-bert` feature.
+/// Requires the `text-bert` feature. [`Default`] loads fastembed's default English model and
+/// panics if it cannot be loaded; [`with_model`](SentenceEmbeddingSimilarity::with_model) picks
+/// multilingual or larger models and reports load failures as [`MetricError::Backend`], as
+/// `update` does for embedding failures. In air-gapped environments,
+/// [`from_local_model`](SentenceEmbeddingSimilarity::from_local_model) loads an exported model
+/// from disk without any download.
+///
+/// Each pair is scored as soon as it is embedded, so only one similarity per pair is retained.
+/// For large evaluation sets, [`with_reduction`](SentenceEmbeddingSimilarity::with_reduction)
+/// folds the similarities into a running reduction and keeps constant memory.
 ///
 /// ```rust,ignore
 /// use rust_metrics::{Metric, SentenceEmbeddingSimilarity};
@@ -23,15 +37,24 @@ use crate::{
 #[cfg_attr(docsrs, doc(cfg(feature = "text-bert")))]
 pub struct SentenceEmbeddingSimilarity {
     model: Arc<Mutex<TextEmbedding>>,
-    prediction_embeddings: Vec<Vec<f32>>,
-    target_embeddings: Vec<Vec<f32>>,
+    similarity: SimilarityFunction,
+    batch_size: usize,
+    similarities: Similarities,
+}
+
+/// fastembed's own default batch size.
+const DEFAULT_BATCH_SIZE: usize = 256;
+
+/// Every per-pair similarity, or only their running reduction.
+enum Similarities {
+    All(Vec<f64>),
+    Reduced(MetricAggregator),
 }
 
 impl Default for SentenceEmbeddingSimilarity {
     fn default() -> Self {
-        let model =
-            TextEmbedding::try_new(Default::default()).expect("Failed to initialize TextEmbedding");
-        Self::new(Arc::new(Mutex::new(model)))
+        Self::with_model(EmbeddingModel::default(), TextInitOptions::default())
+            .expect("Failed to initialize TextEmbedding")
     }
 }
 
@@ -39,17 +62,125 @@ impl SentenceEmbeddingSimilarity {
     pub fn new(model: Arc<Mutex<TextEmbedding>>) -> Self {
         Self {
             model,
-            prediction_embeddings: Vec::new(),
-            target_embeddings: Vec::new(),
+            similarity: SimilarityFunction::Cosine,
+            batch_size: DEFAULT_BATCH_SIZE,
+            similarities: Similarities::All(Vec::new()),
         }
     }
 
-    fn embed_sentences(&self, sentences: &[&str]) -> Vec<Vec<f32>> {
+    /// Score pairs with `similarity`, e.g. [`SimilarityFunction::DotProduct`] for models trained
+    /// for dot-product retrieval.
+    pub fn with_similarity(mut self, similarity: SimilarityFunction) -> Self {
+        self.similarity = similarity;
+        self
+    }
+
+    /// Load an ONNX sentence-embedding model and its tokenizer from `dir`, without network access.
+    ///
+    /// `dir` must hold `model.onnx` alongside the Hugging Face tokenizer files `tokenizer.json`,
+    /// `config.json`, `special_tokens_map.json`, and `tokenizer_config.json`. `pooling` must match
+    /// how the model was trained, e.g. [`Pooling::Cls`] for BGE and [`Pooling::Mean`] for
+    /// sentence-transformers models. Missing files and load failures are returned as
+    /// [`MetricError::Backend`].
+    ///
+    /// ```rust,ignore
+    /// use rust_metrics::{InitOptionsUserDefined, Pooling, SentenceEmbeddingSimilarity};
+    ///
+    /// let metric = SentenceEmbeddingSimilarity::from_local_model(
+    ///     "models/all-MiniLM-L6-v2",
+    ///     Pooling::Mean,
+    ///     InitOptionsUserDefined::default().with_intra_threads(4),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn from_local_model(
+        dir: impl AsRef<Path>,
+        pooling: Pooling,
+        options: InitOptionsUserDefined,
+    ) -> Result<Self, MetricError> {
+        let dir = dir.as_ref();
+        let read = |name: &str| {
+            let path = dir.join(name);
+            fs::read(&path)
+                .map_err(|err| MetricError::Backend(format!("{}: {}", path.display(), err)))
+        };
+        let tokenizer_files = TokenizerFiles {
+            tokenizer_file: read("tokenizer.json")?,
+            config_file: read("config.json")?,
+            special_tokens_map_file: read("special_tokens_map.json")?,
+            tokenizer_config_file: read("tokenizer_config.json")?,
+        };
+        let model = UserDefinedEmbeddingModel::new(read("model.onnx")?, tokenizer_files)
+            .with_pooling(pooling);
+        let model = TextEmbedding::try_new_from_user_defined(model, options)
+            .map_err(|err| MetricError::Backend(err.to_string()))?;
+        Ok(Self::new(Arc::new(Mutex::new(model))))
+    }
+
+    /// Embed at most `batch_size` pairs per model call (default `256`).
+    ///
+    /// Large updates are split into chunks and only one chunk of embeddings is alive at a time,
+    /// which bounds memory and the latency of each model call. The number of ONNX Runtime threads
+    /// is fixed when the model is loaded, through `with_intra_threads` on the options given to
+    /// [`with_model`](SentenceEmbeddingSimilarity::with_model) or
+    /// [`from_local_model`](SentenceEmbeddingSimilarity::from_local_model).
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Keep only the `reduction` of the similarities instead of one value per pair; `compute`
+    /// then returns a single-element vector.
+    ///
+    /// ```rust,ignore
+    /// use rust_metrics::{Metric, Reduction, SentenceEmbeddingSimilarity};
+    ///
+    /// let mut metric = SentenceEmbeddingSimilarity::default().with_reduction(Reduction::Mean);
+    /// metric
+    ///     .update((&["hello there", "general kenobi"], &["hello there", "master kenobi"]))
+    ///     .unwrap();
+    /// assert_eq!(metric.compute().unwrap().len(), 1);
+    /// ```
+    pub fn with_reduction(mut self, reduction: Reduction) -> Self {
+        self.similarities = Similarities::Reduced(MetricAggregator::new(reduction));
+        self
+    }
+
+    /// Load `model` with the remaining `options` (cache directory, maximum length, ...).
+    ///
+    /// Download and initialization failures are returned as [`MetricError::Backend`].
+    ///
+    /// ```rust,ignore
+    /// use rust_metrics::{EmbeddingModel, SentenceEmbeddingSimilarity, TextInitOptions};
+    ///
+    /// let options = TextInitOptions::default().with_show_download_progress(false);
+    /// let metric = SentenceEmbeddingSimilarity::with_model(
+    ///     EmbeddingModel::ParaphraseMLMiniLML12V2,
+    ///     options,
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn with_model(
+        model: EmbeddingModel,
+        options: TextInitOptions,
+    ) -> Result<Self, MetricError> {
+        let mut options = options;
+        options.model_name = model;
+        let model =
+            TextEmbedding::try_new(options).map_err(|err| MetricError::Backend(err.to_string()))?;
+        Ok(Self::new(Arc::new(Mutex::new(model))))
+    }
+
+    fn embed_sentences(&self, sentences: &[&str]) -> Result<Vec<Vec<f32>>, MetricError> {
         let inputs: Vec<String> = sentences.iter().map(|s| (*s).to_string()).collect();
-        let mut model = self.model.lock().expect("TextEmbedding lock poisoned");
+        let mut model = self
+            .model
+            .lock()
+            .map_err(|_| MetricError::Backend("TextEmbedding lock poisoned".to_string()))?;
         model
-            .embed(inputs, None)
-            .expect("Failed to embed sentences")
+            .embed(inputs, Some(self.batch_size))
+            .map_err(|err| MetricError::Backend(err.to_string()))
     }
 }
 
@@ -64,31 +195,44 @@ impl Metric<(&[&str], &[&str])> for SentenceEmbeddingSimilarity {
             });
         }
 
-        let prediction_embeddings = self.embed_sentences(predictions);
-        let target_embeddings = self.embed_sentences(targets);
-        self.prediction_embeddings.extend(prediction_embeddings);
-        self.target_embeddings.extend(target_embeddings);
+        // score every chunk before folding so a failing chunk leaves the state untouched
+        let mut batch_similarities = Vec::with_capacity(predictions.len());
+        for (pred_chunk, target_chunk) in predictions
+            .chunks(self.batch_size)
+            .zip(targets.chunks(self.batch_size))
+        {
+            let prediction_embeddings = self.embed_sentences(pred_chunk)?;
+            let target_embeddings = self.embed_sentences(target_chunk)?;
+            batch_similarities.extend(
+                prediction_embeddings
+                    .iter()
+                    .zip(target_embeddings.iter())
+                    .map(|(pred, tgt)| self.similarity.similarity(pred, tgt)),
+            );
+        }
+        for similarity in batch_similarities {
+            match &mut self.similarities {
+                Similarities::All(similarities) => similarities.push(similarity),
+                Similarities::Reduced(aggregator) => aggregator.update(similarity),
+            }
+        }
 
         Ok(())
     }
 
     fn reset(&mut self) {
-        self.prediction_embeddings = Vec::new();
-        self.target_embeddings = Vec::new();
+        match &mut self.similarities {
+            Similarities::All(similarities) => similarities.clear(),
+            Similarities::Reduced(aggregator) => aggregator.reset(),
+        }
     }
 
     fn compute(&self) -> Option<Self::Output> {
-        if self.prediction_embeddings.is_empty() {
-            return None;
+        match &self.similarities {
+            Similarities::All(similarities) if similarities.is_empty() => None,
+            Similarities::All(similarities) => Some(similarities.clone()),
+            Similarities::Reduced(aggregator) => aggregator.compute().map(|value| vec![value]),
         }
-
-        Some(
-            self.prediction_embeddings
-                .iter()
-                .zip(self.target_embeddings.iter())
-                .map(|(pred, tgt)| cosine_similarity(pred, tgt))
-                .collect(),
-        )
     }
 }
 
@@ -96,6 +240,7 @@ impl Metric<(&[&str], &[&str])> for SentenceEmbeddingSimilarity {
 mod tests {
     use super::SentenceEmbeddingSimilarity;
     use crate::core::Metric;
+    use crate::utils::Reduction;
 
     #[test]
     fn bert_score_batches() {
@@ -115,4 +260,55 @@ mod tests {
         bert_score.reset();
         assert_eq!(bert_score.compute(), None);
     }
+
+    #[test]
+    fn bert_score_reduction() {
+        let preds = ["hello there", "general kenobi"];
+        let targets = ["hello there", "master kenobi"];
+
+        let mut per_pair = SentenceEmbeddingSimilarity::default();
+        per_pair.update((&preds, &targets)).unwrap();
+        let per_pair = per_pair.compute().unwrap();
+
+        let mut reduced = SentenceEmbeddingSimilarity::default()
+            .with_batch_size(1)
+            .with_reduction(Reduction::Min);
+        reduced.update((&preds[..1], &targets[..1])).unwrap();
+        reduced.update((&preds[1..], &targets[1..])).unwrap();
+        let result = reduced.compute().unwrap();
+        assert_eq!(result.len(), 1);
+        assert!((result[0] - per_pair[1]).abs() < 1e-6);
+
+        reduced.reset();
+        assert_eq!(reduced.compute(), None);
+    }
+
+    #[test]
+    fn bert_score_chunked_update() {
+        let preds = [
+            "hello there",
+            "general kenobi",
+            "the cat sat",
+            "it is raining",
+        ];
+        let targets = [
+            "hello there",
+            "master kenobi",
+            "a cat sat down",
+            "the sun is out",
+        ];
+
+        let mut unchunked = SentenceEmbeddingSimilarity::default();
+        unchunked.update((&preds, &targets)).unwrap();
+        let unchunked = unchunked.compute().unwrap();
+
+        // a full chunk of three pairs and a partial one, in a single update
+        let mut chunked = SentenceEmbeddingSimilarity::default().with_batch_size(3);
+        chunked.update((&preds, &targets)).unwrap();
+        let chunked = chunked.compute().unwrap();
+        assert_eq!(chunked.len(), unchunked.len());
+        for (chunked, unchunked) in chunked.iter().zip(&unchunked) {
+            assert!((chunked - unchunked).abs() < 1e-6);
+        }
+    }
 }
@@ -1,8 +1,41 @@
+use std::sync::Arc;
+
 use crate::core::{Metric, MetricError};
-use crate::utils::{count_ngrams, tokenize};
+use crate::utils::{
+    MetricAggregator, Reduction, TextPreprocessing, Tokenizer, WhitespaceTokenizer, count_ngrams,
+};
+
+/// How [`Bleu`] treats n-gram orders without any match, following the methods compared by
+/// Chen & Cherry (2014) and offered by sacreBLEU.
+///
+/// Without smoothing a single order without matches zeroes the whole score, which is common for
+/// short sentences. A prediction without unigram matches still scores `0` unless noted otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BleuSmoothing {
+    #[default]
+    None,
+    /// Replace zero match counts with `epsilon` (method 1, sacreBLEU `floor`, usually `0.1`),
+    /// unigrams included.
+    Floor(f64),
+    /// Add `k` to the matches and totals of every order above unigrams (method 2 for `k = 1`, as
+    /// in Lin & Och 2004, sacreBLEU `add-k`).
+    AddK(f64),
+    /// NIST geometric smoothing: the `i`-th order without matches gets precision
+    /// `1 / (2^i * total)` (method 3, sacreBLEU `exp`), unigrams included.
+    Exponential,
+}
 
-/// Cumulative BLEU score with optional smoothing and arbitrary n-gram depth.
+/// Cumulative BLEU score with configurable [`BleuSmoothing`] and arbitrary n-gram depth.
 ///
+/// Text is compared as given and split on whitespace; use
+/// [`with_preprocessing`](Bleu::with_preprocessing) for case folding or Unicode normalization and
+/// [`with_tokenizer`](Bleu::with_tokenizer) for another [`Tokenizer`].
+///
+/// By default n-gram statistics are pooled into one corpus-level score;
+/// [`with_sentence_reduction`](Bleu::with_sentence_reduction) instead scores every sentence on its
+/// own and reduces those scores, as benchmarks reporting average sentence BLEU do.
+/// [`with_sample_scores`](Bleu::with_sample_scores) additionally keeps the sentence BLEU of every
+/// sample for error analysis, at the cost of one `f64` per sample.
 ///
 /// ```
 /// use rust_metrics::{Bleu, Metric};
@@ -21,26 +54,79 @@ pub struct Bleu {
     targets_len: usize,
     numerator: Vec<f64>,
     denominator: Vec<f64>,
-    smooth: bool,
+    smoothing: BleuSmoothing,
+    preprocessing: TextPreprocessing,
+    tokenizer: Arc<dyn Tokenizer>,
+    sample_scores: Option<Vec<f64>>,
+    sentence_aggregator: Option<MetricAggregator>,
 }
 
 impl Default for Bleu {
     fn default() -> Self {
-        Self::new(4, false)
+        Self::new(4, BleuSmoothing::None)
     }
 }
 
 impl Bleu {
-    pub fn new(n_gram: usize, smooth: bool) -> Self {
+    pub fn new(n_gram: usize, smoothing: BleuSmoothing) -> Self {
         Self {
             n_gram,
-            smooth,
+            smoothing,
             numerator: vec![0.0; n_gram],
             denominator: vec![0.0; n_gram],
             preds_len: 0,
             targets_len: 0,
+            preprocessing: TextPreprocessing::default(),
+            tokenizer: Arc::new(WhitespaceTokenizer),
+            sample_scores: None,
+            sentence_aggregator: None,
         }
     }
+
+    /// Clean predictions and targets with `preprocessing` before tokenizing.
+    pub fn with_preprocessing(mut self, preprocessing: TextPreprocessing) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
+    /// Split predictions and targets with `tokenizer` instead of on whitespace.
+    pub fn with_tokenizer(mut self, tokenizer: Box<dyn Tokenizer>) -> Self {
+        self.tokenizer = Arc::from(tokenizer);
+        self
+    }
+
+    /// Report the `reduction` of per-sentence BLEU scores instead of corpus BLEU.
+    ///
+    /// Short sentences often lack higher-order matches, so pick a [`BleuSmoothing`] in
+    /// [`new`](Bleu::new) to keep single misses from zeroing a sentence.
+    ///
+    /// ```
+    /// use rust_metrics::{Bleu, BleuSmoothing, Metric, Reduction};
+    ///
+    /// let preds = ["the cat sat", "a b"];
+    /// let targets = ["the cat sat", "c d"];
+    ///
+    /// let mut bleu =
+    ///     Bleu::new(2, BleuSmoothing::AddK(1.0)).with_sentence_reduction(Reduction::Mean);
+    /// bleu.update((&preds, &targets)).unwrap();
+    /// assert!((bleu.compute().unwrap() - 0.5).abs() < 1e-12);
+    /// ```
+    pub fn with_sentence_reduction(mut self, reduction: Reduction) -> Self {
+        self.sentence_aggregator = Some(MetricAggregator::new(reduction));
+        self
+    }
+
+    /// Also record the sentence BLEU of every sample, see [`sample_scores`](Bleu::sample_scores).
+    pub fn with_sample_scores(mut self) -> Self {
+        self.sample_scores = Some(Vec::new());
+        self
+    }
+
+    /// Sentence BLEU of each sample in update order (`0.0` for empty predictions or targets);
+    /// `None` unless enabled with [`with_sample_scores`](Bleu::with_sample_scores).
+    pub fn sample_scores(&self) -> Option<&[f64]> {
+        self.sample_scores.as_deref()
+    }
 }
 
 impl Metric<(&[&str], &[&str])> for Bleu {
@@ -55,11 +141,15 @@ impl Metric<(&[&str], &[&str])> for Bleu {
         }
 
         for (pred, target) in predictions.iter().zip(targets.iter()) {
-            let pred_tokens = tokenize(pred);
-            let target_tokens = tokenize(target);
+            let pred = self.preprocessing.apply(pred)?;
+            let target = self.preprocessing.apply(target)?;
+            let pred_tokens = self.tokenizer.tokenize(&pred);
+            let target_tokens = self.tokenizer.tokenize(&target);
             self.preds_len += pred_tokens.len();
             self.targets_len += target_tokens.len();
 
+            let mut sample_numerator = vec![0.0; self.n_gram];
+            let mut sample_denominator = vec![0.0; self.n_gram];
             for n in 1..=self.n_gram {
                 let pred_counts = count_ngrams(&pred_tokens, n);
                 let target_counts = count_ngrams(&target_tokens, n);
@@ -73,8 +163,28 @@ impl Metric<(&[&str], &[&str])> for Bleu {
                         clipped += p_count.min(t_count);
                     }
                 }
-                self.numerator[n - 1] += clipped as f64;
-                self.denominator[n - 1] += total as f64;
+                sample_numerator[n - 1] = clipped as f64;
+                sample_denominator[n - 1] = total as f64;
+            }
+            for n in 0..self.n_gram {
+                self.numerator[n] += sample_numerator[n];
+                self.denominator[n] += sample_denominator[n];
+            }
+            if self.sample_scores.is_some() || self.sentence_aggregator.is_some() {
+                let score = bleu_score(
+                    &sample_numerator,
+                    &sample_denominator,
+                    pred_tokens.len(),
+                    target_tokens.len(),
+                    self.smoothing,
+                )
+                .unwrap_or(0.0);
+                if let Some(sample_scores) = &mut self.sample_scores {
+                    sample_scores.push(score);
+                }
+                if let Some(sentence_aggregator) = &mut self.sentence_aggregator {
+                    sentence_aggregator.update(score);
+                }
             }
         }
         Ok(())
@@ -85,64 +195,75 @@ impl Metric<(&[&str], &[&str])> for Bleu {
         self.denominator.fill(0.0);
         self.preds_len = 0;
         self.targets_len = 0;
-    }
-
-    fn compute(&self) -> Option<Self::Output> {
-        if self.preds_len == 0 || self.targets_len == 0 {
-            return None;
+        if let Some(sample_scores) = &mut self.sample_scores {
+            sample_scores.clear();
         }
-
-        if self.numerator.first().copied().unwrap_or(0.0) == 0.0 {
-            return Some(0.0);
+        if let Some(sentence_aggregator) = &mut self.sentence_aggregator {
+            sentence_aggregator.reset();
         }
+    }
 
-        if !self.smooth && self.numerator.contains(&0.0) {
-            return Some(0.0);
+    fn compute(&self) -> Option<Self::Output> {
+        if let Some(sentence_aggregator) = &self.sentence_aggregator {
+            return sentence_aggregator.compute();
         }
+        bleu_score(
+            &self.numerator,
+            &self.denominator,
+            self.preds_len,
+            self.targets_len,
+            self.smoothing,
+        )
+    }
+}
 
-        let precision_scores: Vec<f64> = if self.smooth {
-            let mut precisions: Vec<f64> = self
-                .numerator
-                .iter()
-                .zip(&self.denominator)
-                .map(|(&num, &den)| (num + 1.0) / (den + 1.0))
-                .collect();
+/// BLEU from clipped n-gram matches (`numerator`) and predicted n-gram totals (`denominator`)
+/// per order, with the brevity penalty from the predicted and reference lengths.
+pub(crate) fn bleu_score(
+    numerator: &[f64],
+    denominator: &[f64],
+    preds_len: usize,
+    targets_len: usize,
+    smoothing: BleuSmoothing,
+) -> Option<f64> {
+    if preds_len == 0 || targets_len == 0 {
+        return None;
+    }
 
-            if let (Some(first), Some(&den)) = (precisions.get_mut(0), self.denominator.first()) {
-                *first = self.numerator[0] / den;
+    let mut precision_scores = Vec::with_capacity(numerator.len());
+    let mut exponential_divisor = 1.0;
+    for (order, (&num, &den)) in numerator.iter().zip(denominator).enumerate() {
+        let precision = match smoothing {
+            BleuSmoothing::AddK(k) if order > 0 => (num + k) / (den + k),
+            _ if num > 0.0 => num / den,
+            BleuSmoothing::Floor(epsilon) if den > 0.0 => epsilon / den,
+            BleuSmoothing::Exponential if den > 0.0 => {
+                exponential_divisor *= 2.0;
+                1.0 / (exponential_divisor * den)
             }
-
-            precisions
-        } else {
-            self.numerator
-                .iter()
-                .zip(&self.denominator)
-                .map(|(&num, &den)| num / den)
-                .collect()
+            _ => return Some(0.0),
         };
+        precision_scores.push(precision);
+    }
 
-        if precision_scores.iter().any(|&p| p <= 0.0) {
-            return Some(0.0);
-        }
-
-        let log_precision_sum: f64 = precision_scores
-            .iter()
-            .map(|&p| p.ln() / self.n_gram as f64)
-            .sum();
-        let geo_mean = log_precision_sum.exp();
+    let log_precision_sum: f64 = precision_scores
+        .iter()
+        .map(|&p| p.ln() / numerator.len() as f64)
+        .sum();
+    let geo_mean = log_precision_sum.exp();
 
-        let c = self.preds_len as f64;
-        let r = self.targets_len as f64;
-        let bp = if c > r { 1.0 } else { (1.0 - r / c).exp() };
+    let c = preds_len as f64;
+    let r = targets_len as f64;
+    let bp = if c > r { 1.0 } else { (1.0 - r / c).exp() };
 
-        Some(bp * geo_mean)
-    }
+    Some(bp * geo_mean)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Bleu;
+    use super::{Bleu, BleuSmoothing};
     use crate::core::Metric;
+    use crate::utils::Reduction;
 
     #[test]
     fn bleu_over_batches() {
@@ -170,12 +291,72 @@ mod tests {
         let preds = vec!["the cat sits"];
         let targets = vec!["the dog sits"];
 
-        let mut bleu = Bleu::new(2, false);
+        let mut bleu = Bleu::new(2, BleuSmoothing::None);
         bleu.update((&preds, &targets)).unwrap();
         assert_eq!(bleu.compute().unwrap(), 0.0);
 
-        let mut smoothed = Bleu::new(2, true);
+        let mut smoothed = Bleu::new(2, BleuSmoothing::AddK(1.0));
         smoothed.update((&preds, &targets)).unwrap();
         assert!(smoothed.compute().unwrap() > 0.0);
     }
+
+    #[test]
+    fn smoothing_methods_match_sacrebleu() {
+        // bigram matches: 0 of 2, unigram matches: 2 of 3
+        let preds = ["the cat sits"];
+        let targets = ["the dog sits"];
+        let expected = [
+            (
+                BleuSmoothing::Floor(0.1),
+                (2.0_f64 / 3.0 * 0.1 / 2.0).sqrt(),
+            ),
+            (BleuSmoothing::AddK(1.0), (2.0_f64 / 3.0 / 3.0).sqrt()),
+            (BleuSmoothing::Exponential, (2.0_f64 / 3.0 / 4.0).sqrt()),
+        ];
+        for (smoothing, score) in expected {
+            let mut bleu = Bleu::new(2, smoothing);
+            bleu.update((&preds, &targets)).unwrap();
+            assert!(
+                (bleu.compute().unwrap() - score).abs() < 1e-12,
+                "{:?}",
+                smoothing
+            );
+        }
+
+        // floor and exponential smoothing also rescue orders without unigram matches
+        let mut floor = Bleu::new(1, BleuSmoothing::Floor(0.1));
+        floor.update((&["a b"], &["c d"])).unwrap();
+        assert!((floor.compute().unwrap() - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sentence_scores_alongside_corpus_score() {
+        let mut bleu = Bleu::new(2, BleuSmoothing::None).with_sample_scores();
+        bleu.update((&["the cat sat", "a b"], &["the cat sat", "c d"]))
+            .unwrap();
+        assert_eq!(bleu.sample_scores(), Some(&[1.0, 0.0][..]));
+        assert!(bleu.compute().unwrap() > 0.0);
+
+        bleu.reset();
+        assert_eq!(bleu.sample_scores(), Some(&[][..]));
+        assert_eq!(Bleu::default().sample_scores(), None);
+    }
+
+    #[test]
+    fn sentence_reduction_differs_from_corpus_pooling() {
+        let preds = ["the cat sat on the mat", "hello there"];
+        let targets = ["the cat sat on the mat", "general kenobi"];
+
+        let mut corpus = Bleu::new(2, BleuSmoothing::None);
+        corpus.update((&preds, &targets)).unwrap();
+
+        let mut sentence =
+            Bleu::new(2, BleuSmoothing::None).with_sentence_reduction(Reduction::Max);
+        sentence.update((&preds, &targets)).unwrap();
+        assert_eq!(sentence.compute(), Some(1.0));
+        assert!(corpus.compute().unwrap() < 1.0);
+
+        sentence.reset();
+        assert_eq!(sentence.compute(), None);
+    }
 }
@@ -0,0 +1,205 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{count_ngrams, tokenize};
+
+const PUNCTUATION: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// Corpus-level chrF (character n-gram F-score), or chrF++ when word n-grams are enabled.
+///
+/// Character n-grams are taken over each sentence with whitespace removed; word n-grams split
+/// leading and trailing punctuation off tokens. Matches and n-gram totals are accumulated per
+/// order across batches, and the score is the mean over all orders of the per-order F-beta, as in
+/// TorchMetrics' `CHRFScore`. Scores are in `[0, 1]`.
+///
+/// ```
+/// use rust_metrics::{ChrF, Metric};
+///
+/// let preds = ["the cat is on the mat"];
+/// let targets = ["the cat is on the mat"];
+///
+/// let mut chrf = ChrF::default();
+/// chrf.update((&preds, &targets)).unwrap();
+/// assert!((chrf.compute().unwrap() - 1.0).abs() < 1e-12);
+///
+/// // chrF++: character orders 1..=6 and word orders 1..=2
+/// let mut chrf_pp = ChrF::new(6, 2, 2.0);
+/// chrf_pp.update((&preds, &["a cat is on the mat"])).unwrap();
+/// assert!((chrf_pp.compute().unwrap() - 0.8640).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChrF {
+    char_order: usize,
+    word_order: usize,
+    beta: f64,
+    char_counts: NGramCounts,
+    word_counts: NGramCounts,
+}
+
+impl Default for ChrF {
+    fn default() -> Self {
+        Self::new(6, 0, 2.0)
+    }
+}
+
+impl ChrF {
+    /// `char_order` character and `word_order` word n-gram orders; `word_order == 0` is plain
+    /// chrF and `2` the usual chrF++. `beta` weights recall `beta` times as much as precision.
+    pub fn new(char_order: usize, word_order: usize, beta: f64) -> Self {
+        assert!(char_order > 0, "char_order must be positive");
+        assert!(beta > 0.0, "beta must be positive");
+        Self {
+            char_order,
+            word_order,
+            beta,
+            char_counts: NGramCounts::new(char_order),
+            word_counts: NGramCounts::new(word_order),
+        }
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for ChrF {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[&str], &[&str])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+
+        for (pred, target) in predictions.iter().zip(targets.iter()) {
+            self.char_counts
+                .update(&characters(pred), &characters(target));
+            if self.word_order > 0 {
+                self.word_counts
+                    .update(&words_and_punctuation(pred), &words_and_punctuation(target));
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.char_counts.reset();
+        self.word_counts.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.char_counts.predicted[0] == 0.0 && self.char_counts.target[0] == 0.0 {
+            return None;
+        }
+        let f_scores = self.char_counts.f_scores(self.beta) + self.word_counts.f_scores(self.beta);
+        Some(f_scores / (self.char_order + self.word_order) as f64)
+    }
+}
+
+/// Matched, predicted, and target n-gram totals for orders `1..=order`.
+#[derive(Debug, Clone)]
+struct NGramCounts {
+    matched: Vec<f64>,
+    predicted: Vec<f64>,
+    target: Vec<f64>,
+}
+
+impl NGramCounts {
+    fn new(order: usize) -> Self {
+        Self {
+            matched: vec![0.0; order],
+            predicted: vec![0.0; order],
+            target: vec![0.0; order],
+        }
+    }
+
+    fn update(&mut self, pred_tokens: &[&str], target_tokens: &[&str]) {
+        for n in 1..=self.matched.len() {
+            let pred_counts = count_ngrams(pred_tokens, n);
+            let target_counts = count_ngrams(target_tokens, n);
+            for (ngram, &p_count) in &pred_counts {
+                if let Some(&t_count) = target_counts.get(ngram) {
+                    self.matched[n - 1] += p_count.min(t_count) as f64;
+                }
+            }
+            self.predicted[n - 1] += pred_counts.values().sum::<usize>() as f64;
+            self.target[n - 1] += target_counts.values().sum::<usize>() as f64;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.matched.fill(0.0);
+        self.predicted.fill(0.0);
+        self.target.fill(0.0);
+    }
+
+    /// Sum of the per-order F-beta scores; orders without predicted or target n-grams add `0`.
+    fn f_scores(&self, beta: f64) -> f64 {
+        let beta_sq = beta * beta;
+        let mut total = 0.0;
+        for n in 0..self.matched.len() {
+            if self.predicted[n] == 0.0 || self.target[n] == 0.0 || self.matched[n] == 0.0 {
+                continue;
+            }
+            let precision = self.matched[n] / self.predicted[n];
+            let recall = self.matched[n] / self.target[n];
+            total += (1.0 + beta_sq) * precision * recall / (beta_sq * precision + recall);
+        }
+        total
+    }
+}
+
+/// Every non-whitespace character as its own token.
+fn characters(sentence: &str) -> Vec<&str> {
+    sentence
+        .char_indices()
+        .filter(|(_, ch)| !ch.is_whitespace())
+        .map(|(idx, ch)| &sentence[idx..idx + ch.len_utf8()])
+        .collect()
+}
+
+/// Whitespace tokens with one leading or trailing punctuation character split off.
+fn words_and_punctuation(sentence: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    for word in tokenize(sentence) {
+        let mut chars = word.chars();
+        let (first, last) = (chars.next(), chars.next_back());
+        match (first, last) {
+            (_, Some(last)) if PUNCTUATION.contains(last) => {
+                let split = word.len() - last.len_utf8();
+                tokens.extend([&word[..split], &word[split..]]);
+            }
+            (Some(first), Some(_)) if PUNCTUATION.contains(first) => {
+                let split = first.len_utf8();
+                tokens.extend([&word[..split], &word[split..]]);
+            }
+            _ => tokens.push(word),
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChrF, words_and_punctuation};
+    use crate::core::Metric;
+
+    #[test]
+    fn chrf_over_batches() {
+        let mut chrf = ChrF::new(6, 2, 2.0);
+        chrf.update((&["the cat is on the mat"], &["there is a cat on the mat"]))
+            .unwrap();
+        let single = chrf.compute().unwrap();
+        assert!((single - 0.4942).abs() < 1e-4);
+
+        chrf.update((&["hello"], &["hello"])).unwrap();
+        assert!(chrf.compute().unwrap() > single);
+
+        chrf.reset();
+        assert_eq!(chrf.compute(), None);
+    }
+
+    #[test]
+    fn punctuation_is_split_from_words() {
+        assert_eq!(
+            words_and_punctuation("Hello, (world) !"),
+            vec!["Hello", ",", "(world", ")", "!"]
+        );
+    }
+}
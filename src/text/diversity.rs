@@ -0,0 +1,227 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::core::{Metric, MetricError};
+use crate::utils::{count_ngrams, normalize, tokenize};
+
+use super::bleu::{BleuSmoothing, bleu_score};
+
+/// Distinct-n: unique n-grams divided by all n-grams across every generation seen so far.
+///
+/// A reference-free, corpus-level diversity score; repetitive or mode-collapsed sampling pushes
+/// it towards `0`. Text is split on whitespace after ASCII letters are lowercased and every
+/// non-alphanumeric character is replaced with a space; other letters keep their case. Every
+/// distinct n-gram is kept in memory, so memory grows with the vocabulary of the corpus.
+///
+/// ```
+/// use rust_metrics::{DistinctNGrams, Metric};
+///
+/// let generations = ["the cat sat", "the cat ran"];
+///
+/// let mut metric = DistinctNGrams::new(2);
+/// metric.update(&generations).unwrap();
+/// // "the cat" appears twice among 4 bigrams
+/// assert!((metric.compute().unwrap() - 3.0 / 4.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DistinctNGrams {
+    n_gram: usize,
+    seen: HashSet<String>,
+    total: usize,
+}
+
+impl Default for DistinctNGrams {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl DistinctNGrams {
+    pub fn new(n_gram: usize) -> Self {
+        assert!(n_gram >= 1, "n_gram must be at least 1");
+        Self {
+            n_gram,
+            seen: HashSet::new(),
+            total: 0,
+        }
+    }
+}
+
+impl Metric<&[&str]> for DistinctNGrams {
+    type Output = f64;
+
+    fn update(&mut self, generations: &[&str]) -> Result<(), MetricError> {
+        for generation in generations {
+            let normalized = normalize(generation);
+            for (ngram, count) in count_ngrams(&tokenize(&normalized), self.n_gram) {
+                self.total += count;
+                // tokens never contain whitespace, so joining on a space is unambiguous
+                self.seen.insert(ngram.join(" "));
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.seen.clear();
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.seen.len() as f64 / self.total as f64)
+    }
+}
+
+/// Self-BLEU: the mean sentence BLEU of each generation against all other generations as
+/// references.
+///
+/// High values mean the generations resemble each other, so lower is more diverse. References
+/// clip each n-gram by its highest count in any other generation and the brevity penalty uses
+/// the closest reference length, as in multi-reference BLEU. Text is split on whitespace and
+/// smoothing is any [`BleuSmoothing`](crate::BleuSmoothing), as for [`Bleu`](crate::Bleu).
+///
+/// Every generation is stored and `compute` is quadratic in their number, so evaluate a sample
+/// of generations rather than a full corpus.
+///
+/// ```
+/// use rust_metrics::{Metric, SelfBleu};
+///
+/// let mut metric = SelfBleu::default();
+/// metric.update(&["the cat is on the mat", "the cat is on the mat"]).unwrap();
+/// assert!((metric.compute().unwrap() - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SelfBleu {
+    n_gram: usize,
+    smoothing: BleuSmoothing,
+    generations: Vec<Vec<String>>,
+}
+
+impl Default for SelfBleu {
+    fn default() -> Self {
+        Self::new(4, BleuSmoothing::None)
+    }
+}
+
+impl SelfBleu {
+    pub fn new(n_gram: usize, smoothing: BleuSmoothing) -> Self {
+        assert!(n_gram >= 1, "n_gram must be at least 1");
+        Self {
+            n_gram,
+            smoothing,
+            generations: Vec::new(),
+        }
+    }
+}
+
+impl Metric<&[&str]> for SelfBleu {
+    type Output = f64;
+
+    fn update(&mut self, generations: &[&str]) -> Result<(), MetricError> {
+        self.generations.extend(
+            generations
+                .iter()
+                .map(|generation| tokenize(generation).into_iter().map(String::from).collect()),
+        );
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.generations.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.generations.len() < 2 {
+            return None;
+        }
+        let tokens: Vec<Vec<&str>> = self
+            .generations
+            .iter()
+            .map(|generation| generation.iter().map(String::as_str).collect())
+            .collect();
+        let counts: Vec<Vec<HashMap<Vec<&str>, usize>>> = tokens
+            .iter()
+            .map(|generation| {
+                (1..=self.n_gram)
+                    .map(|n| count_ngrams(generation, n))
+                    .collect()
+            })
+            .collect();
+
+        let mut sum = 0.0;
+        let mut scored = 0usize;
+        for (idx, hypothesis) in counts.iter().enumerate() {
+            let mut numerator = vec![0.0; self.n_gram];
+            let mut denominator = vec![0.0; self.n_gram];
+            for (order, hypothesis_counts) in hypothesis.iter().enumerate() {
+                for (ngram, &count) in hypothesis_counts {
+                    let max_reference = counts
+                        .iter()
+                        .enumerate()
+                        .filter(|(other, _)| *other != idx)
+                        .filter_map(|(_, reference)| reference[order].get(ngram))
+                        .max()
+                        .copied()
+                        .unwrap_or(0);
+                    numerator[order] += count.min(max_reference) as f64;
+                    denominator[order] += count as f64;
+                }
+            }
+
+            let length = tokens[idx].len();
+            let reference_length = tokens
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != idx)
+                .map(|(_, reference)| reference.len())
+                .min_by_key(|&reference| (reference.abs_diff(length), reference))
+                .unwrap_or(0);
+            if let Some(score) = bleu_score(
+                &numerator,
+                &denominator,
+                length,
+                reference_length,
+                self.smoothing,
+            ) {
+                sum += score;
+                scored += 1;
+            }
+        }
+        if scored == 0 {
+            return None;
+        }
+        Some(sum / scored as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BleuSmoothing, DistinctNGrams, SelfBleu};
+    use crate::core::Metric;
+
+    #[test]
+    fn distinct_ngrams_over_batches() {
+        let mut metric = DistinctNGrams::new(2);
+        metric.update(&["a b a b"]).unwrap();
+        metric.update(&["A b, c"]).unwrap();
+        assert!((metric.compute().unwrap() - 3.0 / 5.0).abs() < f64::EPSILON);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn self_bleu_against_other_generations() {
+        let mut metric = SelfBleu::new(1, BleuSmoothing::None);
+        metric.update(&["a b"]).unwrap();
+        assert_eq!(metric.compute(), None);
+
+        metric.update(&["a c", "d e"]).unwrap();
+        assert!((metric.compute().unwrap() - 1.0 / 3.0).abs() < 1e-12);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
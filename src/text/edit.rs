@@ -1,8 +1,27 @@
+use std::sync::Arc;
+
 use crate::core::{Metric, MetricError};
-use crate::utils::{MetricAggregator, Reduction, levenshtein_distance};
+use crate::utils::{
+    MetricAggregator, Reduction, TextPreprocessing, Tokenizer, bounded_levenshtein_distance,
+    levenshtein_distance, sequence_levenshtein_distance,
+};
 
 /// Streaming Levenshtein distance.
 ///
+/// For untrusted inputs, [`with_max_length`](EditDistance::with_max_length) rejects strings
+/// longer than the given number of characters, and
+/// [`with_max_distance`](EditDistance::with_max_distance) switches to a banded DP that stops
+/// once the distance exceeds the bound and records the bound instead.
+///
+/// Text is compared as given unless
+/// [`with_preprocessing`](EditDistance::with_preprocessing) sets case folding or Unicode
+/// normalization. Distances are counted in characters unless a [`Tokenizer`] is set with
+/// [`with_tokenizer`](EditDistance::with_tokenizer), in which case whole tokens are inserted,
+/// deleted, or substituted.
+///
+/// [`with_sample_scores`](EditDistance::with_sample_scores) additionally keeps the distance of
+/// every sample, e.g. to inspect the worst examples.
+///
 /// ```
 /// use rust_metrics::{EditDistance, Metric};
 ///
@@ -14,6 +33,11 @@ use crate::utils::{MetricAggregator, Reduction, levenshtein_distance};
 /// ```
 #[derive(Debug, Clone)]
 pub struct EditDistance {
+    max_length: Option<usize>,
+    max_distance: Option<usize>,
+    preprocessing: TextPreprocessing,
+    tokenizer: Option<Arc<dyn Tokenizer>>,
+    sample_scores: Option<Vec<f64>>,
     metric_aggregator: MetricAggregator,
 }
 
@@ -26,9 +50,54 @@ impl Default for EditDistance {
 impl EditDistance {
     pub fn new(reduction: Reduction) -> Self {
         Self {
+            max_length: None,
+            max_distance: None,
+            preprocessing: TextPreprocessing::default(),
+            tokenizer: None,
+            sample_scores: None,
             metric_aggregator: MetricAggregator::new(reduction),
         }
     }
+
+    /// Reject batches containing a string of more than `max_length` characters.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Clamp every distance to `max_distance`, computing it in `O(max_distance * len)`.
+    pub fn with_max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// Clean predictions and targets with `preprocessing` before measuring the distance.
+    pub fn with_preprocessing(mut self, preprocessing: TextPreprocessing) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
+    /// Count token-level edits over the output of `tokenizer` instead of character edits.
+    ///
+    /// The banded [`with_max_distance`](EditDistance::with_max_distance) search only applies
+    /// to characters; token distances are computed in full and then clamped to the bound.
+    pub fn with_tokenizer(mut self, tokenizer: Box<dyn Tokenizer>) -> Self {
+        self.tokenizer = Some(Arc::from(tokenizer));
+        self
+    }
+
+    /// Also record the distance of every sample, see
+    /// [`sample_scores`](EditDistance::sample_scores).
+    pub fn with_sample_scores(mut self) -> Self {
+        self.sample_scores = Some(Vec::new());
+        self
+    }
+
+    /// Distance of each sample in update order; `None` unless enabled with
+    /// [`with_sample_scores`](EditDistance::with_sample_scores).
+    pub fn sample_scores(&self) -> Option<&[f64]> {
+        self.sample_scores.as_deref()
+    }
 }
 
 impl Metric<(&[&str], &[&str])> for EditDistance {
@@ -41,14 +110,47 @@ impl Metric<(&[&str], &[&str])> for EditDistance {
                 targets: targets.len(),
             });
         }
+        if let Some(max_length) = self.max_length {
+            for text in predictions.iter().chain(targets.iter()) {
+                let length = text.chars().count();
+                if length > max_length {
+                    return Err(MetricError::IncompatibleInput {
+                        expected: format!("at most {} characters", max_length),
+                        got: format!("{} characters", length),
+                    });
+                }
+            }
+        }
         for (&prediction, &target) in predictions.iter().zip(targets.iter()) {
-            let edit_distance = levenshtein_distance(prediction, target) as f64;
-            self.metric_aggregator.update(edit_distance);
+            let prediction = self.preprocessing.apply(prediction)?;
+            let target = self.preprocessing.apply(target)?;
+            let (prediction, target) = (prediction.as_ref(), target.as_ref());
+            let edit_distance = match (&self.tokenizer, self.max_distance) {
+                (Some(tokenizer), max_distance) => {
+                    let distance = sequence_levenshtein_distance(
+                        &tokenizer.tokenize(prediction),
+                        &tokenizer.tokenize(target),
+                    );
+                    max_distance.map_or(distance, |max_distance| distance.min(max_distance))
+                }
+                (None, Some(max_distance)) => {
+                    bounded_levenshtein_distance(prediction, target, max_distance)
+                        .unwrap_or(max_distance)
+                }
+                (None, None) => levenshtein_distance(prediction, target),
+            };
+            if let Some(sample_scores) = &mut self.sample_scores {
+                sample_scores.push(edit_distance as f64);
+            }
+            self.metric_aggregator.update(edit_distance as f64);
         }
         Ok(())
     }
 
     fn reset(&mut self) {
+        if let Some(sample_scores) = &mut self.sample_scores {
+            sample_scores.clear();
+        }
         self.metric_aggregator.reset();
     }
 
@@ -61,6 +163,7 @@ impl Metric<(&[&str], &[&str])> for EditDistance {
 mod tests {
     use super::EditDistance;
     use crate::core::Metric;
+    use crate::utils::Reduction;
 
     #[test]
     fn edit_over_batches() {
@@ -89,4 +192,19 @@ mod tests {
         let score = edit_distance.compute().unwrap();
         assert_eq!(score, 1.0);
     }
+
+    #[test]
+    fn edit_distance_guards() {
+        let mut capped = EditDistance::default().with_max_length(5);
+        assert!(capped.update((&["rain"], &["sunshine"])).is_err());
+        assert_eq!(capped.compute(), None);
+
+        let mut bounded = EditDistance::new(Reduction::Sum).with_max_distance(2);
+        bounded
+            .update((&["rain", "kitten"], &["shine", "sitting"]))
+            .unwrap();
+        assert_eq!(bounded.compute(), Some(4.0));
+        bounded.update((&["mat"], &["bat"])).unwrap();
+        assert_eq!(bounded.compute(), Some(5.0));
+    }
 }
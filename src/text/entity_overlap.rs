@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+
+use crate::core::{Metric, MetricError};
+use crate::utils::{normalize, tokenize};
+
+/// Share of named entities in each summary that the source document does not support, pooled
+/// over all summaries.
+///
+/// Entities are found with a light rule-based extractor: maximal runs of capitalized or numeric
+/// words, where a lone capitalized word at the start of a sentence (e.g. "The") is ignored. An
+/// entity is supported if its normalized tokens appear contiguously in the normalized source.
+/// Inputs are `(summaries, sources)`; `compute` is `None` until some summary entity was seen.
+///
+/// ```
+/// use rust_metrics::{HallucinatedEntityRate, Metric};
+///
+/// let summaries = ["The store Apple opened in Paris in 2021 is busy."];
+/// let sources = ["Apple opened its new store in Paris last year."];
+///
+/// let mut metric = HallucinatedEntityRate::default();
+/// metric.update((&summaries, &sources)).unwrap();
+/// // "apple" and "paris" are supported, "2021" is not
+/// assert!((metric.compute().unwrap() - 1.0 / 3.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HallucinatedEntityRate {
+    counts: EntityCounts,
+}
+
+impl HallucinatedEntityRate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for HallucinatedEntityRate {
+    type Output = f64;
+
+    fn update(&mut self, (summaries, sources): (&[&str], &[&str])) -> Result<(), MetricError> {
+        // matched entities are the supported summary entities
+        self.counts.update(summaries, sources, |summary, source| {
+            (extract_entities(summary), source)
+        })
+    }
+
+    fn reset(&mut self) {
+        self.counts = EntityCounts::default();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.counts.ratio().map(|supported| 1.0 - supported)
+    }
+}
+
+/// Share of named entities in each source document that its summary mentions, pooled over all
+/// samples.
+///
+/// Uses the same extractor and matching as [`HallucinatedEntityRate`], in the other direction.
+/// Inputs are `(summaries, sources)`; `compute` is `None` until some source entity was seen.
+///
+/// ```
+/// use rust_metrics::{EntityRecall, Metric};
+///
+/// let summaries = ["Apple opened a store."];
+/// let sources = ["Last week Apple opened a store in Paris."];
+///
+/// let mut metric = EntityRecall::default();
+/// metric.update((&summaries, &sources)).unwrap();
+/// assert_eq!(metric.compute(), Some(0.5));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EntityRecall {
+    counts: EntityCounts,
+}
+
+impl EntityRecall {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for EntityRecall {
+    type Output = f64;
+
+    fn update(&mut self, (summaries, sources): (&[&str], &[&str])) -> Result<(), MetricError> {
+        self.counts.update(summaries, sources, |summary, source| {
+            (extract_entities(source), summary)
+        })
+    }
+
+    fn reset(&mut self) {
+        self.counts = EntityCounts::default();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.counts.ratio()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct EntityCounts {
+    matched: usize,
+    total: usize,
+}
+
+impl EntityCounts {
+    /// `select` picks the entities to check and the text that should contain them.
+    fn update<'a>(
+        &mut self,
+        summaries: &[&'a str],
+        sources: &[&'a str],
+        select: impl Fn(&'a str, &'a str) -> (HashSet<Vec<String>>, &'a str),
+    ) -> Result<(), MetricError> {
+        if summaries.len() != sources.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: summaries.len(),
+                targets: sources.len(),
+            });
+        }
+        for (summary, source) in summaries.iter().zip(sources.iter()) {
+            let (entities, text) = select(summary, source);
+            let text_norm = normalize(text);
+            let text_tokens = tokenize(&text_norm);
+            for entity in &entities {
+                if contains_sequence(&text_tokens, entity) {
+                    self.matched += 1;
+                }
+            }
+            self.total += entities.len();
+        }
+        Ok(())
+    }
+
+    fn ratio(&self) -> Option<f64> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.matched as f64 / self.total as f64)
+    }
+}
+
+/// Distinct entities of `text`, each as its normalized tokens.
+fn extract_entities(text: &str) -> HashSet<Vec<String>> {
+    let mut entities = HashSet::new();
+    let mut run: Vec<&str> = Vec::new();
+    let mut run_starts_sentence = false;
+    let mut sentence_start = true;
+
+    let mut flush = |run: &mut Vec<&str>, starts_sentence: bool| {
+        let lone_sentence_opener =
+            run.len() == 1 && starts_sentence && !run[0].chars().any(|ch| ch.is_ascii_digit());
+        if !run.is_empty() && !lone_sentence_opener {
+            let tokens: Vec<String> = tokenize(&normalize(&run.join(" ")))
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            if !tokens.is_empty() {
+                entities.insert(tokens);
+            }
+        }
+        run.clear();
+    };
+
+    for word in text.split_whitespace() {
+        let core = word.trim_matches(|ch: char| !ch.is_alphanumeric());
+        let is_entity = core
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_uppercase() || ch.is_ascii_digit())
+            || core.chars().any(|ch| ch.is_ascii_digit());
+        if is_entity {
+            if run.is_empty() {
+                run_starts_sentence = sentence_start;
+            }
+            run.push(core);
+        } else {
+            flush(&mut run, run_starts_sentence);
+        }
+
+        // punctuation after a word ends the current entity
+        let trailing = word.trim_end_matches(|ch: char| !ch.is_alphanumeric());
+        if trailing.len() != word.len() {
+            flush(&mut run, run_starts_sentence);
+        }
+        sentence_start = word.ends_with(['.', '!', '?']);
+    }
+    flush(&mut run, run_starts_sentence);
+    entities
+}
+
+fn contains_sequence(haystack: &[&str], needle: &[String]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window.iter().zip(needle).all(|(a, b)| a == b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntityRecall, HallucinatedEntityRate, extract_entities};
+    use crate::core::Metric;
+
+    #[test]
+    fn extracts_multi_word_entities() {
+        let entities = extract_entities("The New York Times reported on Monday, citing NASA.");
+        let mut entities: Vec<String> = entities.into_iter().map(|e| e.join(" ")).collect();
+        entities.sort();
+        assert_eq!(entities, vec!["monday", "nasa", "the new york times"]);
+    }
+
+    #[test]
+    fn entity_metrics_over_batches() {
+        let summaries = ["Barack Obama visited Berlin.", "Nothing notable happened."];
+        let sources = [
+            "President Barack Obama gave a speech in Berlin on Tuesday.",
+            "A quiet day.",
+        ];
+
+        let mut hallucinated = HallucinatedEntityRate::new();
+        hallucinated.update((&summaries, &sources)).unwrap();
+        assert_eq!(hallucinated.compute(), Some(0.0));
+        hallucinated
+            .update((&["Angela Merkel visited Berlin."], &sources[..1]))
+            .unwrap();
+        assert!((hallucinated.compute().unwrap() - 1.0 / 4.0).abs() < f64::EPSILON);
+
+        let mut recall = EntityRecall::new();
+        recall.update((&summaries, &sources)).unwrap();
+        // "president barack obama", "berlin", "tuesday"
+        assert!((recall.compute().unwrap() - 1.0 / 3.0).abs() < f64::EPSILON);
+
+        hallucinated.reset();
+        assert_eq!(hallucinated.compute(), None);
+    }
+}
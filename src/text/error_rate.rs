@@ -0,0 +1,175 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{EditOperations, sequence_edit_operations, tokenize};
+
+/// Word error rate: word-level Levenshtein edits divided by the number of reference words.
+///
+/// Edits and reference lengths are summed across all samples and batches before dividing, as in
+/// jiwer and TorchMetrics, so long references weigh more than short ones. Words are split on
+/// whitespace; the rate can exceed `1.0` when predictions insert many words.
+/// [`operations`](WordErrorRate::operations) breaks the errors down by edit type.
+///
+/// ```
+/// use rust_metrics::{Metric, WordErrorRate};
+///
+/// let preds = ["this is the prediction", "there is an other sample"];
+/// let targets = ["this is the reference", "there is another one"];
+///
+/// let mut wer = WordErrorRate::default();
+/// wer.update((&preds, &targets)).unwrap();
+/// assert!((wer.compute().unwrap() - 0.5).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WordErrorRate {
+    operations: EditOperations,
+    total: usize,
+}
+
+impl WordErrorRate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulated word-level substitutions, insertions, and deletions behind the rate.
+    pub fn operations(&self) -> EditOperations {
+        self.operations
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for WordErrorRate {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[&str], &[&str])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for (pred, target) in predictions.iter().zip(targets.iter()) {
+            let pred_words = tokenize(pred);
+            let target_words = tokenize(target);
+            self.operations
+                .add(sequence_edit_operations(&pred_words, &target_words));
+            self.total += target_words.len();
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.operations = EditOperations::default();
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.operations.total() as f64 / self.total as f64)
+    }
+}
+
+/// Character error rate: character-level Levenshtein edits divided by the number of reference
+/// characters.
+///
+/// Like [`WordErrorRate`], edits and reference lengths are accumulated as a running numerator and
+/// denominator rather than averaging per-sample rates, matching jiwer and TorchMetrics.
+/// Characters are Unicode scalar values and whitespace counts as a character.
+/// [`operations`](CharErrorRate::operations) breaks the errors down by edit type.
+///
+/// ```
+/// use rust_metrics::{CharErrorRate, Metric};
+///
+/// let preds = ["this is the prediction", "there is an other sample"];
+/// let targets = ["this is the reference", "there is another one"];
+///
+/// let mut cer = CharErrorRate::default();
+/// cer.update((&preds, &targets)).unwrap();
+/// assert!((cer.compute().unwrap() - 0.3415).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CharErrorRate {
+    operations: EditOperations,
+    total: usize,
+}
+
+impl CharErrorRate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulated character-level substitutions, insertions, and deletions behind the rate.
+    pub fn operations(&self) -> EditOperations {
+        self.operations
+    }
+}
+
+impl Metric<(&[&str], &[&str])> for CharErrorRate {
+    type Output = f64;
+
+    fn update(&mut self, (predictions, targets): (&[&str], &[&str])) -> Result<(), MetricError> {
+        if predictions.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: targets.len(),
+            });
+        }
+        for (pred, target) in predictions.iter().zip(targets.iter()) {
+            let pred_chars: Vec<char> = pred.chars().collect();
+            let target_chars: Vec<char> = target.chars().collect();
+            self.operations
+                .add(sequence_edit_operations(&pred_chars, &target_chars));
+            self.total += target_chars.len();
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.operations = EditOperations::default();
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(self.operations.total() as f64 / self.total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CharErrorRate, WordErrorRate};
+    use crate::core::Metric;
+
+    #[test]
+    fn wer_pools_edits_over_batches() {
+        let mut wer = WordErrorRate::default();
+        wer.update((&["a b c d"], &["a x c d"])).unwrap();
+        wer.update((&["hello world"], &["hello"])).unwrap();
+        // (1 + 1) edits over (4 + 1) reference words, not the mean of 0.25 and 1.0
+        assert!((wer.compute().unwrap() - 0.4).abs() < f64::EPSILON);
+        let operations = wer.operations();
+        assert_eq!(operations.substitutions, 1);
+        assert_eq!(operations.insertions, 1);
+        assert_eq!(operations.deletions, 0);
+
+        wer.reset();
+        assert_eq!(wer.compute(), None);
+    }
+
+    #[test]
+    fn cer_pools_edits_over_batches() {
+        let mut cer = CharErrorRate::default();
+        cer.update((&["kitten"], &["sitting"])).unwrap();
+        cer.update((&["héllo"], &["hello"])).unwrap();
+        assert!((cer.compute().unwrap() - 4.0 / 12.0).abs() < f64::EPSILON);
+        // kitten -> sitting substitutes k/s and e/i and misses the final g; héllo adds é/e
+        let operations = cer.operations();
+        assert_eq!(operations.substitutions, 3);
+        assert_eq!(operations.deletions, 1);
+        assert_eq!(operations.insertions, 0);
+
+        cer.reset();
+        assert_eq!(cer.compute(), None);
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use ort::session::Session;
+use ort::value::Tensor;
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use crate::core::{Metric, MetricError};
+
+/// Default number of tokens per (source, summary) pair, the usual limit of BERT-style encoders.
+const DEFAULT_MAX_LENGTH: usize = 512;
+
+/// Default number of pairs per model call.
+const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// Mean probability that each summary is entailed by its source, according to an NLI
+/// cross-encoder.
+///
+/// Requires the `nli` feature. The model is an ONNX export of a sequence-classification model
+/// fine-tuned on natural language inference (e.g. a DeBERTa or RoBERTa MNLI checkpoint), loaded
+/// with [`from_local_model`](FactualConsistency::from_local_model). Each source is fed as the
+/// premise and its summary as the hypothesis; the softmax of the logits at `entailment_index` is
+/// the pair's score. Low scores flag summaries the source does not support.
+///
+/// Inputs are `(summaries, sources)`. Pairs longer than the maximum length are truncated from the
+/// longer side, which is usually the source, so very long documents should be split beforehand.
+/// Tokenizer and model failures are reported as [`MetricError::Backend`].
+///
+/// ```rust,ignore
+/// use rust_metrics::{FactualConsistency, Metric};
+///
+/// // cross-encoder/nli-deberta-v3-small labels: contradiction, entailment, neutral
+/// let mut metric =
+///     FactualConsistency::from_local_model("models/nli-deberta-v3-small", 1).unwrap();
+/// let summaries = ["Apple opened a store in Paris."];
+/// let sources = ["Apple opened its new store in Paris last year."];
+/// metric.update((&summaries, &sources)).unwrap();
+/// assert!(metric.compute().unwrap() > 0.5);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "nli")))]
+pub struct FactualConsistency {
+    session: Session,
+    tokenizer: Tokenizer,
+    needs_token_type_ids: bool,
+    entailment_index: usize,
+    batch_size: usize,
+    sum: f64,
+    count: usize,
+}
+
+impl FactualConsistency {
+    /// Load `model.onnx` and `tokenizer.json` from `dir`, without network access.
+    ///
+    /// `entailment_index` is the position of the entailment label in the model's `id2label`
+    /// mapping, which differs between checkpoints.
+    pub fn from_local_model(
+        dir: impl AsRef<Path>,
+        entailment_index: usize,
+    ) -> Result<Self, MetricError> {
+        let dir = dir.as_ref();
+        let mut tokenizer = Tokenizer::from_file(dir.join("tokenizer.json")).map_err(backend)?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: DEFAULT_MAX_LENGTH,
+                ..Default::default()
+            }))
+            .map_err(backend)?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+
+        let session = Session::builder()
+            .map_err(backend)?
+            .commit_from_file(dir.join("model.onnx"))
+            .map_err(backend)?;
+        let needs_token_type_ids = session
+            .inputs()
+            .iter()
+            .any(|input| input.name() == "token_type_ids");
+
+        Ok(Self {
+            session,
+            tokenizer,
+            needs_token_type_ids,
+            entailment_index,
+            batch_size: DEFAULT_BATCH_SIZE,
+            sum: 0.0,
+            count: 0,
+        })
+    }
+
+    /// Score at most `batch_size` pairs per model call (default `32`).
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+        self.batch_size = batch_size;
+        self
+    }
+
+    fn score_batch(
+        &mut self,
+        summaries: &[&str],
+        sources: &[&str],
+    ) -> Result<Vec<f64>, MetricError> {
+        let pairs: Vec<(&str, &str)> = sources
+            .iter()
+            .copied()
+            .zip(summaries.iter().copied())
+            .collect();
+        let encodings = self.tokenizer.encode_batch(pairs, true).map_err(backend)?;
+        let length = encodings.first().map_or(0, |encoding| encoding.len());
+        let shape = [encodings.len(), length];
+        let flatten = |field: fn(&tokenizers::Encoding) -> &[u32]| -> Vec<i64> {
+            encodings
+                .iter()
+                .flat_map(|encoding| field(encoding).iter().map(|&id| i64::from(id)))
+                .collect()
+        };
+
+        let mut inputs = ort::inputs![
+            "input_ids" => Tensor::from_array((shape, flatten(|e| e.get_ids()))).map_err(backend)?,
+            "attention_mask" => Tensor::from_array((shape, flatten(|e| e.get_attention_mask()))).map_err(backend)?,
+        ];
+        if self.needs_token_type_ids {
+            inputs.push((
+                "token_type_ids".into(),
+                Tensor::from_array((shape, flatten(|e| e.get_type_ids())))
+                    .map_err(backend)?
+                    .into(),
+            ));
+        }
+
+        let outputs = self.session.run(inputs).map_err(backend)?;
+        let logits = outputs
+            .get("logits")
+            .ok_or_else(|| MetricError::Backend("model has no `logits` output".to_string()))?;
+        let (logits_shape, logits) = logits.try_extract_tensor::<f32>().map_err(backend)?;
+        let labels = logits_shape.last().copied().unwrap_or(0) as usize;
+        if self.entailment_index >= labels {
+            return Err(MetricError::IncompatibleInput {
+                expected: format!("entailment_index below the {} model labels", labels),
+                got: self.entailment_index.to_string(),
+            });
+        }
+        Ok(logits
+            .chunks(labels)
+            .map(|row| entailment_probability(row, self.entailment_index))
+            .collect())
+    }
+}
+
+fn backend(err: impl std::fmt::Display) -> MetricError {
+    MetricError::Backend(err.to_string())
+}
+
+/// Softmax probability of label `index`, computed stably from raw logits.
+fn entailment_probability(logits: &[f32], index: usize) -> f64 {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let total: f64 = logits.iter().map(|&l| f64::from(l - max).exp()).sum();
+    f64::from(logits[index] - max).exp() / total
+}
+
+impl Metric<(&[&str], &[&str])> for FactualConsistency {
+    type Output = f64;
+
+    fn update(&mut self, (summaries, sources): (&[&str], &[&str])) -> Result<(), MetricError> {
+        if summaries.len() != sources.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: summaries.len(),
+                targets: sources.len(),
+            });
+        }
+
+        let mut scores = Vec::with_capacity(summaries.len());
+        for (summaries, sources) in summaries
+            .chunks(self.batch_size)
+            .zip(sources.chunks(self.batch_size))
+        {
+            scores.extend(self.score_batch(summaries, sources)?);
+        }
+        self.sum += scores.iter().sum::<f64>();
+        self.count += scores.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.count = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.sum / self.count as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::entailment_probability;
+
+    #[test]
+    fn entailment_probability_is_a_softmax() {
+        let probability = entailment_probability(&[1.0, 3.0, 0.5], 1);
+        let expected = 3.0_f64.exp() / (1.0_f64.exp() + 3.0_f64.exp() + 0.5_f64.exp());
+        assert!((probability - expected).abs() < 1e-6);
+
+        // large logits do not overflow
+        assert!((entailment_probability(&[1000.0, 1000.0], 0) - 0.5).abs() < 1e-12);
+    }
+}
@@ -1,4 +1,5 @@
-//! Text generation metrics: BLEU, edit distance, and optional BERT similarities.
+//! Text generation metrics: BLEU, edit distance, error rates, and optional BERT similarities
+//! and NLI factual consistency.
 //!
 //! All types implement [`Metric`](crate::core::Metric) for batched updates.
 
@@ -6,12 +7,38 @@
 pub mod bert;
 
 #[cfg(feature = "text-bert")]
-pub use bert::SentenceEmbeddingSimilarity;
+pub use bert::{
+    EmbeddingModel, InitOptionsUserDefined, Pooling, SentenceEmbeddingSimilarity, TextInitOptions,
+};
 
+#[cfg(feature = "nli")]
+pub mod factual_consistency;
+
+#[cfg(feature = "nli")]
+pub use factual_consistency::FactualConsistency;
+
+pub mod abstractiveness;
 pub mod bleu;
+pub mod chrf;
+pub mod code_bleu;
+pub mod diversity;
 pub mod edit;
+pub mod entity_overlap;
+pub mod error_rate;
+pub mod perplexity;
+pub mod repetition;
 pub mod rouge;
+pub mod squad;
 
-pub use bleu::Bleu;
+pub use abstractiveness::{ExtractiveFragmentCoverage, ExtractiveFragmentDensity, NovelNGramRatio};
+pub use bleu::{Bleu, BleuSmoothing};
+pub use chrf::ChrF;
+pub use code_bleu::{CodeBleu, CodeBleuWeights};
+pub use diversity::{DistinctNGrams, SelfBleu};
 pub use edit::EditDistance;
-pub use rouge::RougeScore;
+pub use entity_overlap::{EntityRecall, HallucinatedEntityRate};
+pub use error_rate::{CharErrorRate, WordErrorRate};
+pub use perplexity::Perplexity;
+pub use repetition::{MaxConsecutiveRepeat, RepetitionRate};
+pub use rouge::{MultiReferenceRouge, RougeScore};
+pub use squad::{SquadScore, SquadScores};
@@ -0,0 +1,142 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{verify_label, verify_range};
+
+/// Perplexity of a language model, `exp(-mean log p(target))` over all scored tokens.
+///
+/// [`update`](Metric::update) consumes the natural-log probabilities the model assigned to each
+/// target token. [`update_logits`](Perplexity::update_logits) instead takes one row of raw logits
+/// per token plus the target token ids and applies a numerically stable log-softmax internally;
+/// tokens whose target equals `ignore_index` (typically padding) are skipped.
+///
+/// ```
+/// use rust_metrics::{Metric, Perplexity};
+///
+/// let mut metric = Perplexity::default();
+/// metric.update(&[0.25_f64.ln(), 0.25_f64.ln()]).unwrap();
+/// assert!((metric.compute().unwrap() - 4.0).abs() < 1e-12);
+///
+/// let logits: [&[f64]; 3] = [&[0.0, 0.0], &[1000.0, 1000.0], &[3.0, -2.0]];
+/// let targets = [1, 0, 2];
+/// let mut metric = Perplexity::new(Some(2));
+/// metric.update_logits((&logits, &targets)).unwrap();
+/// assert!((metric.compute().unwrap() - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Perplexity {
+    ignore_index: Option<usize>,
+    sum_log_prob: f64,
+    total: usize,
+}
+
+impl Perplexity {
+    pub fn new(ignore_index: Option<usize>) -> Self {
+        Self {
+            ignore_index,
+            sum_log_prob: 0.0,
+            total: 0,
+        }
+    }
+
+    /// Incorporate a batch of per-token logit rows and their target token ids.
+    pub fn update_logits(
+        &mut self,
+        (logits, targets): (&[&[f64]], &[usize]),
+    ) -> Result<(), MetricError> {
+        if logits.len() != targets.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: logits.len(),
+                targets: targets.len(),
+            });
+        }
+
+        let mut sum_log_prob = 0.0;
+        let mut total = 0;
+        for (&row, &target) in logits.iter().zip(targets.iter()) {
+            if Some(target) == self.ignore_index {
+                continue;
+            }
+            verify_label(target, row.len())?;
+            if let Some(&logit) = row.iter().find(|logit| !logit.is_finite()) {
+                return Err(MetricError::IncompatibleInput {
+                    expected: "finite logits".to_string(),
+                    got: format!("{}", logit),
+                });
+            }
+            sum_log_prob += log_softmax(row, target);
+            total += 1;
+        }
+
+        self.sum_log_prob += sum_log_prob;
+        self.total += total;
+        Ok(())
+    }
+}
+
+fn log_softmax(row: &[f64], index: usize) -> f64 {
+    let max = row.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let log_sum_exp = max + row.iter().map(|&x| (x - max).exp()).sum::<f64>().ln();
+    row[index] - log_sum_exp
+}
+
+impl Metric<&[f64]> for Perplexity {
+    type Output = f64;
+
+    fn update(&mut self, log_probs: &[f64]) -> Result<(), MetricError> {
+        for &log_prob in log_probs {
+            verify_range(log_prob, f64::NEG_INFINITY, 0.0)?;
+        }
+        self.sum_log_prob += log_probs.iter().sum::<f64>();
+        self.total += log_probs.len();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.sum_log_prob = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some((-self.sum_log_prob / self.total as f64).exp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Perplexity;
+    use crate::core::{Metric, MetricError};
+
+    #[test]
+    fn perplexity_over_batches() {
+        let mut metric = Perplexity::default();
+        metric.update(&[0.5_f64.ln()]).unwrap();
+        metric.update(&[0.125_f64.ln()]).unwrap();
+        assert!((metric.compute().unwrap() - 4.0).abs() < 1e-12);
+
+        assert!(metric.update(&[0.5]).is_err());
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn perplexity_from_logits() {
+        let mut metric = Perplexity::new(Some(99));
+        let logits: [&[f64]; 3] = [&[2.0, 1.0, 0.1], &[0.5, 2.5, 0.3], &[9.0, 9.0, 9.0]];
+        let targets = [0, 1, 99];
+        metric.update_logits((&logits, &targets)).unwrap();
+
+        let log_prob = |row: &[f64], idx: usize| {
+            (row[idx].exp() / row.iter().map(|x| x.exp()).sum::<f64>()).ln()
+        };
+        let expected = (-(log_prob(logits[0], 0) + log_prob(logits[1], 1)) / 2.0).exp();
+        assert!((metric.compute().unwrap() - expected).abs() < 1e-12);
+
+        let err = metric
+            .update_logits((&[&[1.0, 2.0][..]], &[2]))
+            .expect_err("out of vocabulary target should fail");
+        assert!(matches!(err, MetricError::IncompatibleInput { .. }));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::core::{Metric, MetricError};
+use crate::utils::{MetricAggregator, Reduction, count_ngrams, normalize, tokenize};
+
+/// Fraction of n-grams in each generation that repeat an earlier n-gram of the same generation,
+/// averaged over samples.
+///
+/// This is a reference-free degeneration signal: looping output drives the rate towards `1`.
+/// Text is normalized like [`RougeScore`](crate::RougeScore) and generations shorter than `n`
+/// tokens are skipped.
+///
+/// ```
+/// use rust_metrics::{Metric, RepetitionRate};
+///
+/// let generations = ["the cat sat the cat sat"];
+///
+/// let mut metric = RepetitionRate::new(2);
+/// metric.update(&generations).unwrap();
+/// // 5 bigrams, of which "the cat" and "cat sat" repeat once each
+/// assert!((metric.compute().unwrap() - 2.0 / 5.0).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RepetitionRate {
+    n_gram: usize,
+    metric_aggregator: MetricAggregator,
+}
+
+impl Default for RepetitionRate {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl RepetitionRate {
+    pub fn new(n_gram: usize) -> Self {
+        assert!(n_gram >= 1, "n_gram must be at least 1");
+        Self {
+            n_gram,
+            metric_aggregator: MetricAggregator::new(Reduction::Mean),
+        }
+    }
+}
+
+impl Metric<&[&str]> for RepetitionRate {
+    type Output = f64;
+
+    fn update(&mut self, generations: &[&str]) -> Result<(), MetricError> {
+        for generation in generations {
+            let normalized = normalize(generation);
+            let counts = count_ngrams(&tokenize(&normalized), self.n_gram);
+            let total: usize = counts.values().sum();
+            if total == 0 {
+                continue;
+            }
+            let repeated = total - counts.len();
+            self.metric_aggregator
+                .update(repeated as f64 / total as f64);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+/// Longest run of back-to-back copies of the same token span, maximized over all generations.
+///
+/// Spans of `1..=max_span` tokens are considered, so `"a b a b a b"` has a run of `3` with
+/// `max_span >= 2`. Flagging generations whose run exceeds a threshold catches looping output.
+///
+/// ```
+/// use rust_metrics::{MaxConsecutiveRepeat, Metric};
+///
+/// let generations = ["i think i think i think so", "fine fine"];
+///
+/// let mut metric = MaxConsecutiveRepeat::new(3);
+/// metric.update(&generations).unwrap();
+/// assert_eq!(metric.compute(), Some(3.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxConsecutiveRepeat {
+    max_span: usize,
+    metric_aggregator: MetricAggregator,
+}
+
+impl Default for MaxConsecutiveRepeat {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl MaxConsecutiveRepeat {
+    pub fn new(max_span: usize) -> Self {
+        assert!(max_span >= 1, "max_span must be at least 1");
+        Self {
+            max_span,
+            metric_aggregator: MetricAggregator::new(Reduction::Max),
+        }
+    }
+}
+
+impl Metric<&[&str]> for MaxConsecutiveRepeat {
+    type Output = f64;
+
+    fn update(&mut self, generations: &[&str]) -> Result<(), MetricError> {
+        for generation in generations {
+            let normalized = normalize(generation);
+            let tokens = tokenize(&normalized);
+            self.metric_aggregator
+                .update(longest_repeat_run(&tokens, self.max_span) as f64);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.metric_aggregator.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.metric_aggregator.compute()
+    }
+}
+
+fn longest_repeat_run(tokens: &[&str], max_span: usize) -> usize {
+    if tokens.is_empty() {
+        return 0;
+    }
+    let mut longest = 1;
+    for span in 1..=max_span.min(tokens.len() / 2) {
+        for start in 0..=(tokens.len() - span) {
+            let pattern = &tokens[start..start + span];
+            let mut run = 1;
+            while start + (run + 1) * span <= tokens.len()
+                && &tokens[start + run * span..start + (run + 1) * span] == pattern
+            {
+                run += 1;
+            }
+            longest = longest.max(run);
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaxConsecutiveRepeat, RepetitionRate};
+    use crate::core::Metric;
+
+    #[test]
+    fn repetition_rate_averages_generations() {
+        let mut metric = RepetitionRate::new(1);
+        metric
+            .update(&["no repeats here", "go go go go", "short"])
+            .unwrap();
+        // 0 / 3, 3 / 4, 0 / 1
+        assert!((metric.compute().unwrap() - 0.75 / 3.0).abs() < 1e-12);
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+
+    #[test]
+    fn max_consecutive_repeat_respects_span() {
+        let mut metric = MaxConsecutiveRepeat::new(1);
+        metric.update(&["a b a b a b", "the the end"]).unwrap();
+        assert_eq!(metric.compute(), Some(2.0));
+
+        let mut metric = MaxConsecutiveRepeat::new(2);
+        metric.update(&["a b a b a b", ""]).unwrap();
+        assert_eq!(metric.compute(), Some(3.0));
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+    }
+}
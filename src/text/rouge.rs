@@ -1,12 +1,33 @@
 use crate::core::{Metric, MetricError};
-use crate::utils::{count_ngrams, normalize, tokenize};
+use crate::utils::{
+    TextPreprocessing, Tokenizer, WhitespaceTokenizer, count_ngrams, longest_common_subsequence,
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Calculate Rouge Score, used for automatic summarization.
 ///
 /// Normalizes text by replacing any non-alpha-numeric characters
-/// with spaces and converts to lowercase.
-/// Tokenizes text by splitting on spaces.
+/// with spaces and converts to lowercase; pass a different [`TextPreprocessing`] to
+/// [`with_preprocessing`](RougeScore::with_preprocessing) to change this.
+/// Tokenizes text by splitting on spaces, or with the [`Tokenizer`] set through
+/// [`with_tokenizer`](RougeScore::with_tokenizer).
+///
+/// `RougeL` scores the longest common subsequence in linear memory, in linear time for shared
+/// leading and trailing tokens and quadratic time for the differing middle; use
+/// [`with_max_tokens`](RougeScore::with_max_tokens) to reject oversized samples up front.
+///
+/// [`update_multi_reference`](RougeScore::update_multi_reference) scores each prediction against
+/// several references and keeps, per ROUGE key, the reference with the highest F-measure;
+/// [`MultiReferenceRouge`] offers the same update through [`Metric`].
+///
+/// [`with_sample_scores`](RougeScore::with_sample_scores) additionally keeps the scores of every
+/// sample for error analysis.
+///
+/// With the `parallel` feature, the samples of each batch are scored on the rayon thread pool;
+/// results are accumulated in input order, so scores match the sequential build exactly.
 ///
 /// ```
 /// use rust_metrics::{text::rouge::RougeKey, Metric, RougeScore};
@@ -21,10 +42,15 @@ use std::collections::HashMap;
 /// assert_eq!(score.get(&RougeKey::Rouge1).unwrap().recall, 0.75);
 /// assert_eq!(score.get(&RougeKey::Rouge1).unwrap().fmeasure, 0.75);
 /// ```
+#[derive(Debug, Clone)]
 pub struct RougeScore {
     rouge_keys: Vec<RougeKey>,
+    max_tokens: Option<usize>,
+    preprocessing: TextPreprocessing,
+    tokenizer: Arc<dyn Tokenizer>,
     stats: HashMap<RougeKey, RougeStats>,
     total: usize,
+    sample_scores: Option<Vec<HashMap<RougeKey, RougeStats>>>,
 }
 
 impl Default for RougeScore {
@@ -37,8 +63,138 @@ impl RougeScore {
     pub fn new(rouge_keys: Vec<RougeKey>) -> Self {
         Self {
             rouge_keys,
+            max_tokens: None,
+            preprocessing: TextPreprocessing {
+                lowercase: true,
+                strip_punctuation: true,
+                ..TextPreprocessing::default()
+            },
+            tokenizer: Arc::new(WhitespaceTokenizer),
             stats: HashMap::new(),
             total: 0,
+            sample_scores: None,
+        }
+    }
+
+    /// Reject batches containing a prediction or target of more than `max_tokens` tokens.
+    ///
+    /// Tokens are counted after preprocessing and tokenization. A sample over the cap fails the
+    /// whole update with [`MetricError::IncompatibleInput`] and no sample of that batch is
+    /// recorded. The cap bounds ROUGE-L at `max_tokens²` token comparisons and `max_tokens`
+    /// memory per pair.
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Replace the default lowercasing and punctuation stripping with `preprocessing`.
+    pub fn with_preprocessing(mut self, preprocessing: TextPreprocessing) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
+    /// Split normalized predictions and targets with `tokenizer` instead of on whitespace.
+    pub fn with_tokenizer(mut self, tokenizer: Box<dyn Tokenizer>) -> Self {
+        self.tokenizer = Arc::from(tokenizer);
+        self
+    }
+
+    /// Also record the scores of every sample, see
+    /// [`sample_scores`](RougeScore::sample_scores).
+    pub fn with_sample_scores(mut self) -> Self {
+        self.sample_scores = Some(Vec::new());
+        self
+    }
+
+    /// Per-key scores of each sample in update order; keys a sample is too short for are
+    /// missing. `None` unless enabled with [`with_sample_scores`](RougeScore::with_sample_scores).
+    pub fn sample_scores(&self) -> Option<&[HashMap<RougeKey, RougeStats>]> {
+        self.sample_scores.as_deref()
+    }
+
+    /// Score each prediction against all of its references, keeping the best-matching reference
+    /// (highest F-measure) separately for every ROUGE key.
+    ///
+    /// ```
+    /// use rust_metrics::{text::rouge::RougeKey, Metric, RougeScore};
+    ///
+    /// let preds = ["the cat sat on the mat"];
+    /// let references: [&[&str]; 1] = [&["a dog barked", "the cat sat on a mat"]];
+    ///
+    /// let mut metric = RougeScore::new(vec![RougeKey::Rouge1]);
+    /// metric.update_multi_reference(&preds, &references).unwrap();
+    /// let score = metric.compute().unwrap();
+    /// assert!((score[&RougeKey::Rouge1].fmeasure - 5.0 / 6.0).abs() < 1e-12);
+    /// ```
+    pub fn update_multi_reference(
+        &mut self,
+        predictions: &[&str],
+        references: &[&[&str]],
+    ) -> Result<(), MetricError> {
+        if predictions.len() != references.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: references.len(),
+            });
+        }
+        if let Some(empty) = references.iter().position(|refs| refs.is_empty()) {
+            return Err(MetricError::IncompatibleInput {
+                expected: "at least one reference per prediction".to_string(),
+                got: format!("no references for prediction {}", empty),
+            });
+        }
+
+        #[cfg(feature = "parallel")]
+        let sample_scores: Vec<Vec<(RougeKey, RougeStats)>> = predictions
+            .par_iter()
+            .zip(references.par_iter())
+            .map(|(prediction, refs)| {
+                score_best_reference(
+                    prediction,
+                    refs,
+                    &self.rouge_keys,
+                    self.max_tokens,
+                    &self.preprocessing,
+                    self.tokenizer.as_ref(),
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        #[cfg(not(feature = "parallel"))]
+        let sample_scores: Vec<Vec<(RougeKey, RougeStats)>> = predictions
+            .iter()
+            .zip(references.iter())
+            .map(|(prediction, refs)| {
+                score_best_reference(
+                    prediction,
+                    refs,
+                    &self.rouge_keys,
+                    self.max_tokens,
+                    &self.preprocessing,
+                    self.tokenizer.as_ref(),
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        self.accumulate(sample_scores);
+        Ok(())
+    }
+
+    fn accumulate(&mut self, sample_scores: Vec<Vec<(RougeKey, RougeStats)>>) {
+        for scores in sample_scores {
+            if let Some(sample_scores) = &mut self.sample_scores {
+                sample_scores.push(scores.iter().copied().collect());
+            }
+            for (rouge_key, rouge) in scores {
+                self.stats
+                    .entry(rouge_key)
+                    .and_modify(|stats| {
+                        stats.precision += rouge.precision;
+                        stats.recall += rouge.recall;
+                        stats.fmeasure += rouge.fmeasure;
+                    })
+                    .or_insert(rouge);
+            }
+            self.total += 1;
         }
     }
 }
@@ -48,6 +204,8 @@ pub enum RougeKey {
     Rouge1,
     Rouge2,
     Rouge3,
+    /// Longest common subsequence.
+    RougeL,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -77,39 +235,47 @@ impl Metric<(&[&str], &[&str])> for RougeScore {
             });
         }
 
-        for (prediction, target) in predictions.iter().zip(targets.iter()) {
-            let prediction_norm = normalize(prediction);
-            let target_norm = normalize(target);
-
-            let prediction_tokens = tokenize(&prediction_norm);
-            let target_tokens = tokenize(&target_norm);
-            for rouge_key in &self.rouge_keys {
-                let rouge = match rouge_key {
-                    RougeKey::Rouge1 => rouge_n(&prediction_tokens, &target_tokens, 1),
-                    RougeKey::Rouge2 => rouge_n(&prediction_tokens, &target_tokens, 2),
-                    RougeKey::Rouge3 => rouge_n(&prediction_tokens, &target_tokens, 3),
-                };
-
-                if let Some(rouge) = rouge {
-                    self.stats
-                        .entry(*rouge_key)
-                        .and_modify(|stats| {
-                            stats.precision += rouge.precision;
-                            stats.recall += rouge.recall;
-                            stats.fmeasure += rouge.fmeasure;
-                        })
-                        .or_insert(rouge);
-                }
-            }
-            self.total += 1;
-        }
+        #[cfg(feature = "parallel")]
+        let sample_scores: Vec<Vec<(RougeKey, RougeStats)>> = predictions
+            .par_iter()
+            .zip(targets.par_iter())
+            .map(|(prediction, target)| {
+                score_sample(
+                    prediction,
+                    target,
+                    &self.rouge_keys,
+                    self.max_tokens,
+                    &self.preprocessing,
+                    self.tokenizer.as_ref(),
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        #[cfg(not(feature = "parallel"))]
+        let sample_scores: Vec<Vec<(RougeKey, RougeStats)>> = predictions
+            .iter()
+            .zip(targets.iter())
+            .map(|(prediction, target)| {
+                score_sample(
+                    prediction,
+                    target,
+                    &self.rouge_keys,
+                    self.max_tokens,
+                    &self.preprocessing,
+                    self.tokenizer.as_ref(),
+                )
+            })
+            .collect::<Result<_, _>>()?;
 
+        self.accumulate(sample_scores);
         Ok(())
     }
 
     fn reset(&mut self) {
         self.total = 0;
         self.stats.clear();
+        if let Some(sample_scores) = &mut self.sample_scores {
+            sample_scores.clear();
+        }
     }
 
     fn compute(&self) -> Option<Self::Output> {
@@ -132,6 +298,125 @@ impl Metric<(&[&str], &[&str])> for RougeScore {
     }
 }
 
+/// [`RougeScore`] taking several references per prediction through [`Metric`], so multi-reference
+/// ROUGE composes with [`GroupedMetric`](crate::GroupedMetric) and
+/// [`CompositeMetric`](crate::CompositeMetric).
+///
+/// Each update is [`RougeScore::update_multi_reference`]; the wrapped metric's configuration
+/// (keys, preprocessing, token cap, sample scores) applies unchanged.
+///
+/// ```
+/// use rust_metrics::{text::rouge::RougeKey, Metric, MultiReferenceRouge, RougeScore};
+///
+/// let preds = ["the cat sat on the mat"];
+/// let references: [&[&str]; 1] = [&["a dog barked", "the cat sat on a mat"]];
+///
+/// let mut metric = MultiReferenceRouge::new(RougeScore::new(vec![RougeKey::Rouge1]));
+/// metric.update((&preds, &references)).unwrap();
+/// let score = metric.compute().unwrap();
+/// assert!((score[&RougeKey::Rouge1].fmeasure - 5.0 / 6.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiReferenceRouge {
+    rouge: RougeScore,
+}
+
+impl MultiReferenceRouge {
+    pub fn new(rouge: RougeScore) -> Self {
+        Self { rouge }
+    }
+
+    /// Wrapped metric, e.g. for its [`sample_scores`](RougeScore::sample_scores).
+    pub fn rouge(&self) -> &RougeScore {
+        &self.rouge
+    }
+}
+
+impl Metric<(&[&str], &[&[&str]])> for MultiReferenceRouge {
+    type Output = HashMap<RougeKey, RougeStats>;
+
+    fn update(
+        &mut self,
+        (predictions, references): (&[&str], &[&[&str]]),
+    ) -> Result<(), MetricError> {
+        self.rouge.update_multi_reference(predictions, references)
+    }
+
+    fn reset(&mut self) {
+        self.rouge.reset();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        self.rouge.compute()
+    }
+}
+
+/// Score one prediction/target pair for every requested key.
+fn score_sample(
+    prediction: &str,
+    target: &str,
+    rouge_keys: &[RougeKey],
+    max_tokens: Option<usize>,
+    preprocessing: &TextPreprocessing,
+    tokenizer: &dyn Tokenizer,
+) -> Result<Vec<(RougeKey, RougeStats)>, MetricError> {
+    let prediction_norm = preprocessing.apply(prediction)?;
+    let target_norm = preprocessing.apply(target)?;
+
+    let prediction_tokens = tokenizer.tokenize(&prediction_norm);
+    let target_tokens = tokenizer.tokenize(&target_norm);
+    if let Some(max_tokens) = max_tokens {
+        let longest = prediction_tokens.len().max(target_tokens.len());
+        if longest > max_tokens {
+            return Err(MetricError::IncompatibleInput {
+                expected: format!("at most {} tokens", max_tokens),
+                got: format!("{} tokens", longest),
+            });
+        }
+    }
+    Ok(rouge_keys
+        .iter()
+        .filter_map(|rouge_key| {
+            let rouge = match rouge_key {
+                RougeKey::Rouge1 => rouge_n(&prediction_tokens, &target_tokens, 1),
+                RougeKey::Rouge2 => rouge_n(&prediction_tokens, &target_tokens, 2),
+                RougeKey::Rouge3 => rouge_n(&prediction_tokens, &target_tokens, 3),
+                RougeKey::RougeL => rouge_l(&prediction_tokens, &target_tokens),
+            };
+            rouge.map(|rouge| (*rouge_key, rouge))
+        })
+        .collect())
+}
+
+/// Score one prediction against several references, keeping the highest F-measure per key.
+fn score_best_reference(
+    prediction: &str,
+    references: &[&str],
+    rouge_keys: &[RougeKey],
+    max_tokens: Option<usize>,
+    preprocessing: &TextPreprocessing,
+    tokenizer: &dyn Tokenizer,
+) -> Result<Vec<(RougeKey, RougeStats)>, MetricError> {
+    let mut best: Vec<(RougeKey, RougeStats)> = Vec::with_capacity(rouge_keys.len());
+    for reference in references {
+        for (rouge_key, rouge) in score_sample(
+            prediction,
+            reference,
+            rouge_keys,
+            max_tokens,
+            preprocessing,
+            tokenizer,
+        )? {
+            match best.iter_mut().find(|(key, _)| *key == rouge_key) {
+                Some((_, current)) if rouge.fmeasure > current.fmeasure => *current = rouge,
+                Some(_) => {}
+                None => best.push((rouge_key, rouge)),
+            }
+        }
+    }
+    Ok(best)
+}
+
 fn rouge_n(pred_tokens: &[&str], target_tokens: &[&str], n: usize) -> Option<RougeStats> {
     if pred_tokens.len() < n || target_tokens.len() < n {
         return None;
@@ -150,6 +435,18 @@ fn rouge_n(pred_tokens: &[&str], target_tokens: &[&str], n: usize) -> Option<Rou
         return None;
     }
 
+    Some(rouge_stats(overlap, pred_total, target_total))
+}
+
+fn rouge_l(pred_tokens: &[&str], target_tokens: &[&str]) -> Option<RougeStats> {
+    if pred_tokens.is_empty() || target_tokens.is_empty() {
+        return None;
+    }
+    let lcs = longest_common_subsequence(pred_tokens, target_tokens);
+    Some(rouge_stats(lcs, pred_tokens.len(), target_tokens.len()))
+}
+
+fn rouge_stats(overlap: usize, pred_total: usize, target_total: usize) -> RougeStats {
     let precision = overlap as f64 / pred_total as f64;
     let recall = overlap as f64 / target_total as f64;
     let fmeasure = if precision + recall == 0.0 {
@@ -157,16 +454,17 @@ fn rouge_n(pred_tokens: &[&str], target_tokens: &[&str], n: usize) -> Option<Rou
     } else {
         2.0 * precision * recall / (precision + recall)
     };
-    Some(RougeStats {
+    RougeStats {
         precision,
         recall,
         fmeasure,
-    })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{RougeKey, RougeScore};
+    use super::{MultiReferenceRouge, RougeKey, RougeScore};
+    use crate::GroupedMetric;
     use crate::core::Metric;
 
     #[test]
@@ -200,4 +498,87 @@ mod tests {
         let score = metric.compute().unwrap();
         assert_eq!(score.get(&RougeKey::Rouge1).unwrap().precision, 0.0);
     }
+
+    #[test]
+    fn rouge_batch_matches_sequential_updates() {
+        let preds = ["My name is John", "The quick brown fox", "cats sleep often"];
+        let targets = [
+            "Is your name John",
+            "The quick brown fox",
+            "dogs bark loudly",
+        ];
+
+        let mut batched = RougeScore::default();
+        batched.update((&preds, &targets)).unwrap();
+
+        let mut sequential = RougeScore::default();
+        for (pred, target) in preds.iter().zip(targets.iter()) {
+            sequential.update((&[*pred], &[*target])).unwrap();
+        }
+
+        let batched = batched.compute().unwrap();
+        let sequential = sequential.compute().unwrap();
+        for key in [RougeKey::Rouge1, RougeKey::Rouge2] {
+            assert_eq!(batched[&key].fmeasure, sequential[&key].fmeasure);
+        }
+    }
+
+    #[test]
+    fn rouge_l_with_token_cap() {
+        let mut metric = RougeScore::new(vec![RougeKey::RougeL]).with_max_tokens(6);
+        metric
+            .update((&["the cat sat on the mat"], &["the cat lay on a mat"]))
+            .unwrap();
+        let score = metric.compute().unwrap();
+        assert!((score[&RougeKey::RougeL].fmeasure - 4.0 / 6.0).abs() < 1e-12);
+
+        let result = metric.update((&["a b c d e f g"], &["a"]));
+        assert!(result.is_err());
+        assert_eq!(metric.compute().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rouge_multi_reference_keeps_best_match() {
+        let mut metric = RougeScore::new(vec![RougeKey::Rouge1, RougeKey::Rouge2]);
+        let references: [&[&str]; 2] = [
+            &["Is your name John", "My name is John"],
+            &["dogs bark loudly"],
+        ];
+        metric
+            .update_multi_reference(&["My name is John", "cats sleep often"], &references)
+            .unwrap();
+        let score = metric.compute().unwrap();
+        assert_eq!(score[&RougeKey::Rouge1].fmeasure, 0.5);
+        assert_eq!(score[&RougeKey::Rouge2].fmeasure, 0.5);
+
+        let result = metric.update_multi_reference(&["a"], &[&[]]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rouge_multi_reference_through_grouped_metric() {
+        let references: [&[&str]; 2] = [&["Is your name John", "My name is John"], &["dogs"]];
+        let mut metric = GroupedMetric::new(MultiReferenceRouge::new(RougeScore::new(vec![
+            RougeKey::Rouge1,
+        ])));
+        metric
+            .update((&["en", "de"], &["My name is John", "cats"], &references))
+            .unwrap();
+        let output = metric.compute().unwrap();
+        assert_eq!(output.overall[&RougeKey::Rouge1].fmeasure, 0.5);
+        assert_eq!(output.groups["en"][&RougeKey::Rouge1].fmeasure, 1.0);
+        assert_eq!(output.groups["de"][&RougeKey::Rouge1].fmeasure, 0.0);
+    }
+
+    #[test]
+    fn rouge_sample_scores() {
+        let mut metric = RougeScore::new(vec![RougeKey::Rouge1]).with_sample_scores();
+        metric
+            .update((&["My name is John", "cats"], &["Is your name John", "dogs"]))
+            .unwrap();
+        let samples = metric.sample_scores().unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0][&RougeKey::Rouge1].fmeasure, 0.75);
+        assert_eq!(samples[1][&RougeKey::Rouge1].fmeasure, 0.0);
+    }
 }
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use crate::core::{Metric, MetricError};
+
+/// Mean exact-match and token-F1 returned by [`SquadScore`], both in `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SquadScores {
+    pub exact_match: f64,
+    pub f1: f64,
+}
+
+/// Exact match and token-overlap F1 for extractive question answering, following the official
+/// SQuAD evaluation script.
+///
+/// Answers are lowercased, stripped of ASCII punctuation and the articles `a`, `an`, and `the`,
+/// and split on whitespace. Each prediction is scored against all of its gold answers and keeps
+/// the best exact match and the best F1 separately. Unlike the script, scores are fractions
+/// rather than percentages.
+///
+/// ```
+/// use rust_metrics::{Metric, SquadScore};
+///
+/// let preds = ["1976", "the Broncos"];
+/// let answers: [&[&str]; 2] = [&["1976"], &["Denver Broncos", "Broncos"]];
+///
+/// let mut squad = SquadScore::default();
+/// squad.update((&preds, &answers)).unwrap();
+/// let scores = squad.compute().unwrap();
+/// assert_eq!(scores.exact_match, 1.0);
+/// assert_eq!(scores.f1, 1.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SquadScore {
+    exact_match: f64,
+    f1: f64,
+    total: usize,
+}
+
+impl SquadScore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric<(&[&str], &[&[&str]])> for SquadScore {
+    type Output = SquadScores;
+
+    fn update(&mut self, (predictions, answers): (&[&str], &[&[&str]])) -> Result<(), MetricError> {
+        if predictions.len() != answers.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: answers.len(),
+            });
+        }
+        if let Some(empty) = answers.iter().position(|gold| gold.is_empty()) {
+            return Err(MetricError::IncompatibleInput {
+                expected: "at least one gold answer per prediction".to_string(),
+                got: format!("no answers for prediction {}", empty),
+            });
+        }
+
+        for (prediction, gold) in predictions.iter().zip(answers.iter()) {
+            let prediction = normalize_answer(prediction);
+            let prediction_tokens: Vec<&str> = prediction.split_whitespace().collect();
+
+            let mut best_exact = 0.0_f64;
+            let mut best_f1 = 0.0_f64;
+            for answer in gold.iter() {
+                let answer = normalize_answer(answer);
+                if answer == prediction {
+                    best_exact = 1.0;
+                }
+                let answer_tokens: Vec<&str> = answer.split_whitespace().collect();
+                best_f1 = best_f1.max(token_f1(&prediction_tokens, &answer_tokens));
+            }
+            self.exact_match += best_exact;
+            self.f1 += best_f1;
+            self.total += 1;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.exact_match = 0.0;
+        self.f1 = 0.0;
+        self.total = 0;
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(SquadScores {
+            exact_match: self.exact_match / self.total as f64,
+            f1: self.f1 / self.total as f64,
+        })
+    }
+}
+
+/// Lowercase, drop ASCII punctuation and articles, and collapse whitespace.
+fn normalize_answer(text: &str) -> String {
+    let without_punctuation: String = text
+        .to_lowercase()
+        .chars()
+        .filter(|ch| !ch.is_ascii_punctuation())
+        .collect();
+    without_punctuation
+        .split_whitespace()
+        .filter(|token| !matches!(*token, "a" | "an" | "the"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Bag-of-tokens F1; two empty answers match perfectly.
+fn token_f1(prediction: &[&str], answer: &[&str]) -> f64 {
+    if prediction.is_empty() || answer.is_empty() {
+        return if prediction.len() == answer.len() {
+            1.0
+        } else {
+            0.0
+        };
+    }
+    let mut answer_counts: HashMap<&str, usize> = HashMap::new();
+    for token in answer {
+        *answer_counts.entry(token).or_insert(0) += 1;
+    }
+    let mut common = 0usize;
+    for token in prediction {
+        if let Some(count) = answer_counts.get_mut(token)
+            && *count > 0
+        {
+            *count -= 1;
+            common += 1;
+        }
+    }
+    if common == 0 {
+        return 0.0;
+    }
+    let precision = common as f64 / prediction.len() as f64;
+    let recall = common as f64 / answer.len() as f64;
+    2.0 * precision * recall / (precision + recall)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SquadScore, normalize_answer};
+    use crate::core::Metric;
+
+    #[test]
+    fn squad_over_batches() {
+        let mut squad = SquadScore::default();
+        let answers: [&[&str]; 1] = [&["Denver Broncos"]];
+        squad.update((&["the Denver Broncos!"], &answers)).unwrap();
+        let answers: [&[&str]; 1] = [&["Carolina Panthers", "Panthers"]];
+        squad.update((&["the Carolina team"], &answers)).unwrap();
+
+        let scores = squad.compute().unwrap();
+        assert_eq!(scores.exact_match, 0.5);
+        // second sample: 1 shared token, precision 1/2 and recall 1/2 against "Carolina Panthers"
+        assert!((scores.f1 - 0.75).abs() < 1e-12);
+
+        squad.reset();
+        assert_eq!(squad.compute(), None);
+    }
+
+    #[test]
+    fn normalization_matches_official_script() {
+        assert_eq!(
+            normalize_answer("  The  Eiffel-Tower, an icon."),
+            "eiffeltower icon"
+        );
+    }
+}
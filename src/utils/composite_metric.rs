@@ -0,0 +1,334 @@
+use std::any::Any;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::core::{Metric, MetricError};
+
+/// Whether larger values of a component are better.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Objective {
+    #[default]
+    Maximize,
+    Minimize,
+}
+
+/// Weight, direction, and optional normalization of one [`CompositeMetric`] component.
+///
+/// With a range `[min, max]` the value is mapped linearly to `[0, 1]` (clamped) and, for
+/// [`Objective::Minimize`], flipped to `1 - x`. Without a range, minimized values are negated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComponentSpec {
+    weight: f64,
+    objective: Objective,
+    range: Option<(f64, f64)>,
+}
+
+impl ComponentSpec {
+    pub fn maximize(weight: f64) -> Self {
+        Self {
+            weight,
+            objective: Objective::Maximize,
+            range: None,
+        }
+    }
+
+    pub fn minimize(weight: f64) -> Self {
+        Self {
+            weight,
+            objective: Objective::Minimize,
+            range: None,
+        }
+    }
+
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        assert!(min < max, "range minimum must be below its maximum");
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Oriented, normalized value of a component, where larger is always better.
+    fn transform(&self, value: f64) -> f64 {
+        match (self.range, self.objective) {
+            (Some((min, max)), objective) => {
+                let scaled = ((value - min) / (max - min)).clamp(0.0, 1.0);
+                match objective {
+                    Objective::Maximize => scaled,
+                    Objective::Minimize => 1.0 - scaled,
+                }
+            }
+            (None, Objective::Maximize) => value,
+            (None, Objective::Minimize) => -value,
+        }
+    }
+}
+
+/// Typed reference to a metric registered with a [`CompositeMetric`].
+pub struct ComponentHandle<M> {
+    index: usize,
+    _metric: PhantomData<fn() -> M>,
+}
+
+impl<M> Clone for ComponentHandle<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for ComponentHandle<M> {}
+
+impl<M> fmt::Debug for ComponentHandle<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentHandle")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// One component of a [`CompositeOutput`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentValue {
+    pub name: String,
+    /// Value reported by the wrapped metric.
+    pub value: f64,
+    /// Oriented, normalized value that enters the weighted sum.
+    pub normalized: f64,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositeOutput {
+    /// `sum_i weight_i * normalized_i`.
+    pub score: f64,
+    /// Components in registration order.
+    pub components: Vec<ComponentValue>,
+}
+
+/// Weighted combination of heterogeneous metrics into a single scalar objective.
+///
+/// Each registered metric keeps its own input type: [`register`](CompositeMetric::register)
+/// returns a typed handle that routes batches to it through
+/// [`update`](CompositeMetric::update). Metrics whose output is not an `f64` are added with
+/// [`register_with`](CompositeMetric::register_with) and a function extracting the scalar.
+/// `compute` is `None` until every component can be computed.
+///
+/// ```
+/// use rust_metrics::utils::{ComponentSpec, CompositeMetric};
+/// use rust_metrics::{BinaryF1Score, EditDistance};
+///
+/// let mut composite = CompositeMetric::new();
+/// let f1 = composite.register("f1", BinaryF1Score::default(), ComponentSpec::maximize(0.7));
+/// let edit = composite.register(
+///     "edit",
+///     EditDistance::default(),
+///     ComponentSpec::minimize(0.3).with_range(0.0, 10.0),
+/// );
+///
+/// composite.update(f1, (&[0.9, 0.2], &[1, 0])).unwrap();
+/// assert_eq!(composite.compute(), None);
+/// composite.update(edit, (&["rain"], &["shine"])).unwrap();
+///
+/// let output = composite.compute().unwrap();
+/// // 0.7 * 1.0 + 0.3 * (1 - 3 / 10)
+/// assert!((output.score - 0.91).abs() < 1e-12);
+/// assert_eq!(output.components[1].value, 3.0);
+/// ```
+#[derive(Default)]
+pub struct CompositeMetric {
+    components: Vec<Component>,
+}
+
+struct Component {
+    name: String,
+    spec: ComponentSpec,
+    metric: Box<dyn ErasedMetric>,
+}
+
+impl fmt::Debug for CompositeMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.components
+                    .iter()
+                    .map(|component| (&component.name, &component.spec)),
+            )
+            .finish()
+    }
+}
+
+impl CompositeMetric {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a metric with a scalar output.
+    pub fn register<M, I>(
+        &mut self,
+        name: &str,
+        metric: M,
+        spec: ComponentSpec,
+    ) -> ComponentHandle<M>
+    where
+        M: Metric<I, Output = f64> + 'static,
+    {
+        self.register_with(name, metric, spec, compute_scalar::<M, I>)
+    }
+
+    /// Add a metric whose scalar value is read with `extract`, e.g. one field of a report.
+    pub fn register_with<M, I>(
+        &mut self,
+        name: &str,
+        metric: M,
+        spec: ComponentSpec,
+        extract: fn(&M) -> Option<f64>,
+    ) -> ComponentHandle<M>
+    where
+        M: Metric<I> + 'static,
+    {
+        self.components.push(Component {
+            name: name.to_string(),
+            spec,
+            metric: Box::new(Registered {
+                metric,
+                extract,
+                reset: reset_metric::<M, I>,
+            }),
+        });
+        ComponentHandle {
+            index: self.components.len() - 1,
+            _metric: PhantomData,
+        }
+    }
+
+    /// Feed a batch to one component.
+    pub fn update<M, I>(&mut self, handle: ComponentHandle<M>, input: I) -> Result<(), MetricError>
+    where
+        M: Metric<I> + 'static,
+    {
+        self.metric_mut(handle).update(input)
+    }
+
+    /// Registered metric behind `handle`.
+    ///
+    /// Panics if `handle` was issued by a different `CompositeMetric`.
+    pub fn metric<M: 'static>(&self, handle: ComponentHandle<M>) -> &M {
+        &self.components[handle.index]
+            .metric
+            .as_any()
+            .downcast_ref::<Registered<M>>()
+            .expect("component handle belongs to another CompositeMetric")
+            .metric
+    }
+
+    /// Mutable access to the metric behind `handle`, see [`metric`](CompositeMetric::metric).
+    pub fn metric_mut<M: 'static>(&mut self, handle: ComponentHandle<M>) -> &mut M {
+        &mut self.components[handle.index]
+            .metric
+            .as_any_mut()
+            .downcast_mut::<Registered<M>>()
+            .expect("component handle belongs to another CompositeMetric")
+            .metric
+    }
+
+    pub fn reset(&mut self) {
+        for component in &mut self.components {
+            component.metric.reset();
+        }
+    }
+
+    pub fn compute(&self) -> Option<CompositeOutput> {
+        if self.components.is_empty() {
+            return None;
+        }
+        let mut score = 0.0;
+        let mut components = Vec::with_capacity(self.components.len());
+        for component in &self.components {
+            let value = component.metric.value()?;
+            let normalized = component.spec.transform(value);
+            score += component.spec.weight * normalized;
+            components.push(ComponentValue {
+                name: component.name.clone(),
+                value,
+                normalized,
+                weight: component.spec.weight,
+            });
+        }
+        Some(CompositeOutput { score, components })
+    }
+}
+
+fn compute_scalar<M: Metric<I, Output = f64>, I>(metric: &M) -> Option<f64> {
+    metric.compute()
+}
+
+fn reset_metric<M: Metric<I>, I>(metric: &mut M) {
+    metric.reset();
+}
+
+trait ErasedMetric {
+    fn value(&self) -> Option<f64>;
+    fn reset(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// A metric together with its input-specific operations, erased behind [`ErasedMetric`].
+struct Registered<M> {
+    metric: M,
+    extract: fn(&M) -> Option<f64>,
+    reset: fn(&mut M),
+}
+
+impl<M: 'static> ErasedMetric for Registered<M> {
+    fn value(&self) -> Option<f64> {
+        (self.extract)(&self.metric)
+    }
+
+    fn reset(&mut self) {
+        (self.reset)(&mut self.metric);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentSpec, CompositeMetric};
+    use crate::text::rouge::RougeKey;
+    use crate::{MeanSquaredError, Metric, RougeScore};
+
+    #[test]
+    fn composite_over_heterogeneous_outputs() {
+        let mut composite = CompositeMetric::new();
+        let rouge = composite.register_with(
+            "rouge1",
+            RougeScore::new(vec![RougeKey::Rouge1]),
+            ComponentSpec::maximize(0.5),
+            |metric| Some(metric.compute()?[&RougeKey::Rouge1].fmeasure),
+        );
+        let mse = composite.register(
+            "mse",
+            MeanSquaredError::default(),
+            ComponentSpec::minimize(2.0),
+        );
+
+        composite
+            .update(rouge, (&["the cat sat"], &["the cat sat"]))
+            .unwrap();
+        composite.update(mse, (&[1.0, 3.0], &[2.0, 3.0])).unwrap();
+
+        let output = composite.compute().unwrap();
+        // 0.5 * 1.0 + 2.0 * (-0.5)
+        assert!((output.score + 0.5).abs() < 1e-12);
+        assert_eq!(output.components[1].normalized, -0.5);
+        assert_eq!(composite.metric(mse).compute(), Some(0.5));
+
+        composite.reset();
+        assert_eq!(composite.compute(), None);
+    }
+}
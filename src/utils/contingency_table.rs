@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use crate::core::MetricError;
+use crate::utils::{stable_sum, verify_reverted_count};
+
+/// Sparse co-occurrence counts between two labelings, with cached marginals.
+///
+/// Rows are indexed by the first labeling (e.g. true classes) and columns by the second (e.g.
+/// predicted clusters). Only observed cells are stored, so arbitrary label ids are supported.
+/// Cells and marginals are kept in key order and reduced with compensated summation, so derived
+/// scores are bit-for-bit reproducible regardless of insertion order.
+///
+/// ```
+/// use rust_metrics::utils::ContingencyTable;
+///
+/// let mut table = ContingencyTable::new();
+/// table.update(&[0, 0, 1, 1], &[0, 0, 1, 0]).unwrap();
+/// assert_eq!(table.count(0, 0), 2);
+/// assert_eq!(table.row_totals()[&1], 2);
+/// assert_eq!(table.column_totals()[&0], 3);
+/// assert!((table.row_entropy() - 2.0_f64.ln()).abs() < f64::EPSILON);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContingencyTable {
+    joint: BTreeMap<(usize, usize), usize>,
+    row_totals: BTreeMap<usize, usize>,
+    column_totals: BTreeMap<usize, usize>,
+    total: usize,
+}
+
+impl ContingencyTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a single co-occurrence of `row` and `column`.
+    pub fn insert(&mut self, row: usize, column: usize) {
+        *self.joint.entry((row, column)).or_insert(0) += 1;
+        *self.row_totals.entry(row).or_insert(0) += 1;
+        *self.column_totals.entry(column).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    /// Record paired labels.
+    pub fn update(&mut self, rows: &[usize], columns: &[usize]) -> Result<(), MetricError> {
+        if rows.len() != columns.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: columns.len(),
+                targets: rows.len(),
+            });
+        }
+        for (&row, &column) in rows.iter().zip(columns.iter()) {
+            self.insert(row, column);
+        }
+        Ok(())
+    }
+
+    /// Add all counts of `other` into `self`.
+    pub fn merge(&mut self, other: &ContingencyTable) {
+        for (&cell, &count) in &other.joint {
+            *self.joint.entry(cell).or_insert(0) += count;
+        }
+        for (&row, &count) in &other.row_totals {
+            *self.row_totals.entry(row).or_insert(0) += count;
+        }
+        for (&column, &count) in &other.column_totals {
+            *self.column_totals.entry(column).or_insert(0) += count;
+        }
+        self.total += other.total;
+    }
+
+    /// Remove all counts of `other` from `self`.
+    ///
+    /// Fails without modifying `self` if `other` holds a cell with more pairs than `self`.
+    pub fn subtract(&mut self, other: &ContingencyTable) -> Result<(), MetricError> {
+        for (&(row, column), &count) in &other.joint {
+            verify_reverted_count(count, self.count(row, column))?;
+        }
+        for (&cell, &count) in &other.joint {
+            decrement(&mut self.joint, cell, count);
+            decrement(&mut self.row_totals, cell.0, count);
+            decrement(&mut self.column_totals, cell.1, count);
+        }
+        self.total -= other.total;
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.joint.clear();
+        self.row_totals.clear();
+        self.column_totals.clear();
+        self.total = 0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Number of recorded pairs.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn count(&self, row: usize, column: usize) -> usize {
+        self.joint.get(&(row, column)).copied().unwrap_or(0)
+    }
+
+    /// Non-zero cells as `((row, column), count)`.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), usize)> + '_ {
+        self.joint.iter().map(|(&cell, &count)| (cell, count))
+    }
+
+    pub fn row_totals(&self) -> &BTreeMap<usize, usize> {
+        &self.row_totals
+    }
+
+    pub fn column_totals(&self) -> &BTreeMap<usize, usize> {
+        &self.column_totals
+    }
+
+    /// Entropy (in nats) of the row labeling.
+    pub fn row_entropy(&self) -> f64 {
+        entropy(self.row_totals.values().copied(), self.total)
+    }
+
+    /// Entropy (in nats) of the column labeling.
+    pub fn column_entropy(&self) -> f64 {
+        entropy(self.column_totals.values().copied(), self.total)
+    }
+
+    /// Joint entropy (in nats) of both labelings.
+    pub fn joint_entropy(&self) -> f64 {
+        entropy(self.joint.values().copied(), self.total)
+    }
+
+    /// Mutual information (in nats) between the row and column labelings.
+    pub fn mutual_information(&self) -> f64 {
+        let total = self.total as f64;
+        stable_sum(self.joint.iter().map(|(&(row, column), &count)| {
+            let count = count as f64;
+            let row_count = self.row_totals[&row] as f64;
+            let column_count = self.column_totals[&column] as f64;
+            (count / total) * ((total * count) / (row_count * column_count)).ln()
+        }))
+    }
+}
+
+fn decrement<K: Ord>(counts: &mut BTreeMap<K, usize>, key: K, amount: usize) {
+    if let Some(count) = counts.get_mut(&key) {
+        *count -= amount;
+        if *count == 0 {
+            counts.remove(&key);
+        }
+    }
+}
+
+fn entropy(counts: impl Iterator<Item = usize>, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let total = total as f64;
+    -stable_sum(counts.filter(|&count| count > 0).map(|count| {
+        let p = count as f64 / total;
+        p * p.ln()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContingencyTable;
+
+    #[test]
+    fn contingency_table_merge_and_entropy() {
+        let mut left = ContingencyTable::new();
+        left.update(&[0, 2, 1], &[2, 1, 0]).unwrap();
+        let mut right = ContingencyTable::new();
+        right.update(&[1, 0], &[1, 0]).unwrap();
+
+        let mut merged = left.clone();
+        merged.merge(&right);
+
+        let mut direct = ContingencyTable::new();
+        direct.update(&[0, 2, 1, 1, 0], &[2, 1, 0, 1, 0]).unwrap();
+        assert_eq!(merged, direct);
+        assert_eq!(merged.total(), 5);
+
+        let mi = merged.mutual_information();
+        let identity = merged.row_entropy() + merged.column_entropy() - merged.joint_entropy();
+        assert!((mi - identity).abs() < 1e-12);
+
+        merged.subtract(&right).unwrap();
+        assert_eq!(merged, left);
+        assert!(merged.subtract(&right).is_err());
+
+        // reversed insertion order gives bit-identical scores
+        let mut reversed = ContingencyTable::new();
+        reversed.update(&[0, 1, 1, 2, 0], &[0, 1, 0, 1, 2]).unwrap();
+        assert_eq!(
+            reversed.mutual_information().to_bits(),
+            direct.mutual_information().to_bits()
+        );
+        assert_eq!(
+            reversed.joint_entropy().to_bits(),
+            direct.joint_entropy().to_bits()
+        );
+
+        merged.clear();
+        assert!(merged.is_empty());
+        assert_eq!(merged.row_entropy(), 0.0);
+    }
+}
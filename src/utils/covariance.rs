@@ -0,0 +1,156 @@
+use crate::core::MetricError;
+
+/// Streaming mean and covariance of fixed-dimension vectors (Welford's algorithm).
+///
+/// ```
+/// use rust_metrics::utils::CovarianceAccumulator;
+///
+/// let mut acc = CovarianceAccumulator::new(2);
+/// acc.update(&[1.0, 2.0]).unwrap();
+/// acc.update(&[3.0, 6.0]).unwrap();
+/// assert_eq!(acc.mean(), &[2.0, 4.0]);
+/// assert_eq!(acc.covariance(), Some(vec![2.0, 4.0, 4.0, 8.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CovarianceAccumulator {
+    dim: usize,
+    count: usize,
+    mean: Vec<f64>,
+    comoment: Vec<f64>,
+}
+
+impl CovarianceAccumulator {
+    pub fn new(dim: usize) -> Self {
+        assert!(dim >= 1, "dim must be at least 1");
+        Self {
+            dim,
+            count: 0,
+            mean: vec![0.0; dim],
+            comoment: vec![0.0; dim * dim],
+        }
+    }
+
+    pub fn update(&mut self, sample: &[f64]) -> Result<(), MetricError> {
+        if sample.len() != self.dim {
+            return Err(MetricError::IncompatibleInput {
+                expected: format!("vector of dimension {}", self.dim),
+                got: format!("dimension {}", sample.len()),
+            });
+        }
+        self.count += 1;
+        let count = self.count as f64;
+        let delta: Vec<f64> = sample.iter().zip(&self.mean).map(|(x, m)| x - m).collect();
+        for (mean, d) in self.mean.iter_mut().zip(&delta) {
+            *mean += d / count;
+        }
+        for (row, (x, mean)) in self
+            .comoment
+            .chunks_mut(self.dim)
+            .zip(sample.iter().zip(&self.mean))
+        {
+            let delta_after = x - mean;
+            for (cell, d) in row.iter_mut().zip(&delta) {
+                *cell += d * delta_after;
+            }
+        }
+        Ok(())
+    }
+
+    /// Combine the statistics of `other` into `self` (Chan et al.'s parallel update).
+    pub fn merge(&mut self, other: &CovarianceAccumulator) -> Result<(), MetricError> {
+        if other.dim != self.dim {
+            return Err(MetricError::IncompatibleInput {
+                expected: format!("accumulator of dimension {}", self.dim),
+                got: format!("dimension {}", other.dim),
+            });
+        }
+        if other.count == 0 {
+            return Ok(());
+        }
+        let total = (self.count + other.count) as f64;
+        let weight = self.count as f64 * other.count as f64 / total;
+        let delta: Vec<f64> = other
+            .mean
+            .iter()
+            .zip(&self.mean)
+            .map(|(o, s)| o - s)
+            .collect();
+        for i in 0..self.dim {
+            for j in 0..self.dim {
+                self.comoment[i * self.dim + j] +=
+                    other.comoment[i * self.dim + j] + delta[i] * delta[j] * weight;
+            }
+        }
+        for (mean, d) in self.mean.iter_mut().zip(&delta) {
+            *mean += d * other.count as f64 / total;
+        }
+        self.count += other.count;
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.mean.fill(0.0);
+        self.comoment.fill(0.0);
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// Unbiased sample covariance as a row-major `dim x dim` matrix; `None` with fewer than two
+    /// samples.
+    pub fn covariance(&self) -> Option<Vec<f64>> {
+        if self.count < 2 {
+            return None;
+        }
+        let denominator = (self.count - 1) as f64;
+        Some(self.comoment.iter().map(|c| c / denominator).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CovarianceAccumulator;
+
+    #[test]
+    fn merge_matches_sequential_updates() {
+        let samples = [[1.0, -2.0], [0.5, 4.0], [3.0, 1.0], [-1.0, 0.0], [2.0, 2.5]];
+
+        let mut sequential = CovarianceAccumulator::new(2);
+        for sample in &samples {
+            sequential.update(sample).unwrap();
+        }
+
+        let mut left = CovarianceAccumulator::new(2);
+        let mut right = CovarianceAccumulator::new(2);
+        for sample in &samples[..2] {
+            left.update(sample).unwrap();
+        }
+        for sample in &samples[2..] {
+            right.update(sample).unwrap();
+        }
+        left.merge(&right).unwrap();
+
+        assert_eq!(left.count(), 5);
+        for (a, b) in left.mean().iter().zip(sequential.mean()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        let merged = left.covariance().unwrap();
+        for (a, b) in merged.iter().zip(sequential.covariance().unwrap()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        assert!(left.update(&[1.0]).is_err());
+        left.reset();
+        assert_eq!(left.covariance(), None);
+    }
+}
@@ -28,6 +28,17 @@ pub fn verify_binary_label(input: usize) -> Result<(), MetricError> {
     verify_label(input, 2)
 }
 
+pub fn verify_reverted_count(reverted: usize, observed: usize) -> Result<(), MetricError> {
+    if reverted <= observed {
+        Ok(())
+    } else {
+        Err(MetricError::IncompatibleInput {
+            expected: format!("at most {} previously observed samples", observed),
+            got: format!("{} reverted samples", reverted),
+        })
+    }
+}
+
 pub fn cosine_similarity(v1: &[f32], v2: &[f32]) -> f64 {
     let dot: f64 = v1
         .iter()
@@ -43,6 +54,46 @@ pub fn cosine_similarity(v1: &[f32], v2: &[f32]) -> f64 {
     }
 }
 
+/// Similarity between two embedding vectors; higher always means more similar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SimilarityFunction {
+    /// Cosine of the angle between the vectors, in `[-1, 1]`.
+    #[default]
+    Cosine,
+    /// Raw inner product, for models trained for dot-product retrieval.
+    DotProduct,
+    /// `1 / (1 + d)` for the euclidean distance `d`, in `(0, 1]`.
+    Euclidean,
+    /// `1 - angle / pi`, a proper metric-derived similarity in `[0, 1]`.
+    Angular,
+}
+
+impl SimilarityFunction {
+    pub fn similarity(&self, v1: &[f32], v2: &[f32]) -> f64 {
+        match self {
+            SimilarityFunction::Cosine => cosine_similarity(v1, v2),
+            SimilarityFunction::DotProduct => v1
+                .iter()
+                .zip(v2.iter())
+                .map(|(a, b)| (*a as f64) * (*b as f64))
+                .sum(),
+            SimilarityFunction::Euclidean => {
+                let distance = v1
+                    .iter()
+                    .zip(v2.iter())
+                    .map(|(a, b)| (*a as f64 - *b as f64).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                1.0 / (1.0 + distance)
+            }
+            SimilarityFunction::Angular => {
+                let cosine = cosine_similarity(v1, v2).clamp(-1.0, 1.0);
+                1.0 - cosine.acos() / std::f64::consts::PI
+            }
+        }
+    }
+}
+
 pub fn tokenize(input: &str) -> Vec<&str> {
     input.split_whitespace().collect()
 }
@@ -72,43 +123,178 @@ pub fn count_ngrams<'a>(tokens: &[&'a str], n: usize) -> HashMap<Vec<&'a str>, u
 }
 
 pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
-    if s1.is_empty() {
-        return s2.chars().count();
+    let s1_chars: Vec<char> = s1.chars().collect();
+    let s2_chars: Vec<char> = s2.chars().collect();
+    sequence_levenshtein_distance(&s1_chars, &s2_chars)
+}
+
+/// Levenshtein distance between two token sequences (characters, words, ...).
+pub fn sequence_levenshtein_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    if a.is_empty() {
+        return b.len();
+    }
+    if b.is_empty() {
+        return a.len();
     }
-    if s2.is_empty() {
-        return s1.chars().count();
+    levenshtein_table(a, b)[a.len()][b.len()]
+}
+
+/// Substitutions, insertions, and deletions of a minimal alignment of a prediction to a target.
+///
+/// Insertions are prediction tokens absent from the target and deletions are target tokens
+/// missing from the prediction, as in ASR scoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EditOperations {
+    pub substitutions: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl EditOperations {
+    /// Total number of edits, i.e. the Levenshtein distance.
+    pub fn total(&self) -> usize {
+        self.substitutions + self.insertions + self.deletions
+    }
+
+    pub(crate) fn add(&mut self, other: EditOperations) {
+        self.substitutions += other.substitutions;
+        self.insertions += other.insertions;
+        self.deletions += other.deletions;
     }
+}
 
-    let len1 = s1.chars().count();
-    let len2 = s2.chars().count();
+/// Edit operations turning `prediction` into `target`, preferring substitutions, then
+/// insertions, then deletions among equally short alignments.
+///
+/// ```
+/// use rust_metrics::utils::sequence_edit_operations;
+///
+/// let ops = sequence_edit_operations(&["the", "cat", "sat", "down"], &["a", "cat", "sat"]);
+/// assert_eq!((ops.substitutions, ops.insertions, ops.deletions), (1, 1, 0));
+/// ```
+pub fn sequence_edit_operations<T: PartialEq>(prediction: &[T], target: &[T]) -> EditOperations {
+    let dp = levenshtein_table(prediction, target);
+    let mut operations = EditOperations::default();
+    let (mut i, mut j) = (prediction.len(), target.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let cost = usize::from(prediction[i - 1] != target[j - 1]);
+            if dp[i][j] == dp[i - 1][j - 1] + cost {
+                operations.substitutions += cost;
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && dp[i][j] == dp[i - 1][j] + 1 {
+            operations.insertions += 1;
+            i -= 1;
+        } else {
+            operations.deletions += 1;
+            j -= 1;
+        }
+    }
+    operations
+}
 
-    let mut dp = vec![vec![0usize; len2 + 1]; len1 + 1];
+/// Full Levenshtein DP table, `dp[i][j]` being the distance between `a[..i]` and `b[..j]`.
+fn levenshtein_table<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Vec<usize>> {
+    let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
 
-    for (i, item) in dp.iter_mut().enumerate().take(len1 + 1) {
+    for (i, item) in dp.iter_mut().enumerate() {
         item[0] = i;
     }
-    for j in 0..=len2 {
-        dp[0][j] = j;
+    for (j, item) in dp[0].iter_mut().enumerate() {
+        *item = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = *[dp[i - 1][j] + 1, dp[i][j - 1] + 1, dp[i - 1][j - 1] + cost]
+                .iter()
+                .min()
+                .unwrap();
+        }
     }
+    dp
+}
 
+/// Levenshtein distance if it is at most `max_distance`, otherwise `None`.
+///
+/// Only the diagonal band `|i - j| <= max_distance` of the DP table is evaluated and the scan
+/// stops as soon as every cell of a row exceeds the bound, so the cost is
+/// `O(max_distance * len)` time and `O(len)` memory instead of quadratic.
+pub fn bounded_levenshtein_distance(s1: &str, s2: &str, max_distance: usize) -> Option<usize> {
     let s1_chars: Vec<char> = s1.chars().collect();
     let s2_chars: Vec<char> = s2.chars().collect();
+    let (len1, len2) = (s1_chars.len(), s2_chars.len());
+    if len1.abs_diff(len2) > max_distance {
+        return None;
+    }
 
+    let over = max_distance + 1;
+    let mut prev: Vec<usize> = (0..=len2).map(|j| j.min(over)).collect();
+    let mut curr = vec![over; len2 + 1];
     for i in 1..=len1 {
-        for j in 1..=len2 {
-            let cost = if s1_chars[i - 1] == s2_chars[j - 1] {
-                0
+        let lo = i.saturating_sub(max_distance).max(1);
+        let hi = (i + max_distance).min(len2);
+        curr[0] = i.min(over);
+        if lo > 1 {
+            curr[lo - 1] = over;
+        }
+
+        let mut row_min = curr[lo - 1];
+        for j in lo..=hi {
+            let cost = usize::from(s1_chars[i - 1] != s2_chars[j - 1]);
+            curr[j] = (prev[j] + 1)
+                .min(curr[j - 1] + 1)
+                .min(prev[j - 1] + cost)
+                .min(over);
+            row_min = row_min.min(curr[j]);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    Some(prev[len2]).filter(|&distance| distance <= max_distance)
+}
+
+/// Length of the longest common subsequence of two token sequences.
+///
+/// Shared leading and trailing tokens are matched directly, so near-identical sequences cost
+/// linear time; the remaining middle is compared in `O(len_a * len_b)` time with two rows of
+/// `O(min(len_a, len_b))` memory.
+pub fn longest_common_subsequence(a: &[&str], b: &[&str]) -> usize {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+    if a.is_empty() || b.is_empty() {
+        return prefix + suffix;
+    }
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+
+    let mut prev = vec![0usize; b.len() + 1];
+    let mut curr = vec![0usize; b.len() + 1];
+    for token_a in a {
+        for (j, token_b) in b.iter().enumerate() {
+            curr[j + 1] = if token_a == token_b {
+                prev[j] + 1
             } else {
-                1
+                prev[j + 1].max(curr[j])
             };
-            dp[i][j] = *[dp[i - 1][j] + 1, dp[i][j - 1] + 1, dp[i - 1][j - 1] + cost]
-                .iter()
-                .min()
-                .unwrap();
         }
+        std::mem::swap(&mut prev, &mut curr);
     }
-
-    dp[len1][len2]
+    prefix + prev[b.len()] + suffix
 }
 
 #[derive(Debug, Clone, Default)]
@@ -118,3 +304,112 @@ pub enum AverageMethod {
     Macro,
     Weighted,
 }
+
+/// How raw model outputs are mapped to probabilities before a metric validates them.
+///
+/// Argmax-based multiclass metrics accept logits as they are, since neither transform changes
+/// the argmax.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputTransform {
+    /// Scores already are probabilities in `[0, 1]`.
+    #[default]
+    None,
+    /// Element-wise logistic function, for binary or one-vs-rest logits.
+    Sigmoid,
+    /// Softmax over each row of class logits; only meaningful for multiclass inputs.
+    Softmax,
+}
+
+impl InputTransform {
+    /// Probability of a single binary score; softmax needs a whole row and is rejected.
+    pub(crate) fn probability(&self, score: f64) -> Result<f64, MetricError> {
+        match self {
+            InputTransform::None => {
+                verify_range(score, 0.0, 1.0)?;
+                Ok(score)
+            }
+            InputTransform::Sigmoid => {
+                verify_range(score, f64::MIN, f64::MAX)?;
+                Ok(1.0 / (1.0 + (-score).exp()))
+            }
+            InputTransform::Softmax => Err(MetricError::IncompatibleInput {
+                expected: "a row of class scores for softmax".to_string(),
+                got: "a single binary score".to_string(),
+            }),
+        }
+    }
+
+    /// Probabilities of one row of class scores.
+    pub fn probabilities(&self, scores: &[f64]) -> Result<Vec<f64>, MetricError> {
+        match self {
+            InputTransform::None | InputTransform::Sigmoid => scores
+                .iter()
+                .map(|&score| self.probability(score))
+                .collect(),
+            InputTransform::Softmax => {
+                for &score in scores {
+                    verify_range(score, f64::MIN, f64::MAX)?;
+                }
+                let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let exps: Vec<f64> = scores.iter().map(|&score| (score - max).exp()).collect();
+                let sum: f64 = exps.iter().sum();
+                Ok(exps.into_iter().map(|e| e / sum).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InputTransform, SimilarityFunction, bounded_levenshtein_distance, levenshtein_distance,
+        longest_common_subsequence,
+    };
+
+    #[test]
+    fn bounded_levenshtein_matches_full_dp() {
+        let words = [
+            "", "a", "kitten", "sitting", "saturday", "sunday", "flaw", "lawn", "ab",
+        ];
+        for a in words {
+            for b in words {
+                let full = levenshtein_distance(a, b);
+                for bound in 0..=8 {
+                    let expected = (full <= bound).then_some(full);
+                    assert_eq!(bounded_levenshtein_distance(a, b, bound), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lcs_of_token_sequences() {
+        let a = ["the", "cat", "sat", "on", "the", "mat"];
+        let b = ["the", "cat", "lay", "on", "a", "mat"];
+        assert_eq!(longest_common_subsequence(&a, &b), 4);
+        assert_eq!(longest_common_subsequence(&a, &[]), 0);
+        assert_eq!(longest_common_subsequence(&b, &a), 4);
+        assert_eq!(longest_common_subsequence(&a, &a), 6);
+        assert_eq!(longest_common_subsequence(&a[..2], &a), 2);
+        assert_eq!(longest_common_subsequence(&["a", "x", "b"], &["a", "b"]), 2);
+    }
+
+    #[test]
+    fn similarity_functions() {
+        let a = [3.0, 0.0];
+        let b = [0.0, 4.0];
+        assert_eq!(SimilarityFunction::Cosine.similarity(&a, &b), 0.0);
+        assert_eq!(SimilarityFunction::DotProduct.similarity(&a, &a), 9.0);
+        assert!((SimilarityFunction::Euclidean.similarity(&a, &b) - 1.0 / 6.0).abs() < 1e-12);
+        assert!((SimilarityFunction::Angular.similarity(&a, &b) - 0.5).abs() < 1e-12);
+        assert_eq!(SimilarityFunction::Angular.similarity(&a, &a), 1.0);
+    }
+
+    #[test]
+    fn softmax_rejects_single_scores() {
+        assert_eq!(InputTransform::Sigmoid.probability(0.0), Ok(0.5));
+        assert!(InputTransform::Softmax.probability(0.0).is_err());
+        let row = InputTransform::Softmax.probabilities(&[0.0, 0.0]).unwrap();
+        assert_eq!(row, vec![0.5, 0.5]);
+    }
+}
@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::core::{Metric, MetricError};
+
+use super::composite_metric::Objective;
+
+/// Value of a [`GroupedMetric`]: the wrapped metric over all samples plus one value per group.
+#[derive(Debug, Clone)]
+pub struct GroupedOutput<K, O> {
+    pub overall: O,
+    pub groups: HashMap<K, O>,
+}
+
+impl<K, O: PartialOrd> GroupedOutput<K, O> {
+    /// Group with the lowest value under [`Objective::Maximize`] (e.g. accuracy) or the highest
+    /// under [`Objective::Minimize`] (e.g. error); `None` without any computed group. Ties are
+    /// broken arbitrarily.
+    pub fn worst_group(&self, objective: Objective) -> Option<(&K, &O)> {
+        let order = |a: &&O, b: &&O| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let groups = self.groups.iter();
+        match objective {
+            Objective::Maximize => groups.min_by(|a, b| order(&a.1, &b.1)),
+            Objective::Minimize => groups.max_by(|a, b| order(&a.1, &b.1)),
+        }
+    }
+}
+
+impl<K: Eq + Hash, O: PartialEq> PartialEq for GroupedOutput<K, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.overall == other.overall && self.groups == other.groups
+    }
+}
+
+/// Evaluates a metric independently on every slice of the data.
+///
+/// Each update takes a group key per sample alongside the wrapped metric's `(predictions,
+/// targets)` pair. Samples are routed to a per-group clone of the metric passed to
+/// [`GroupedMetric::new`], and an extra clone sees every sample for the overall value. Groups
+/// whose metric cannot be computed yet are left out of [`GroupedOutput::groups`];
+/// [`GroupedOutput::worst_group`] picks the weakest of the rest.
+///
+/// ```
+/// use rust_metrics::utils::Objective;
+/// use rust_metrics::{BinaryAccuracy, GroupedMetric, Metric};
+///
+/// let groups = ["mobile", "desktop", "mobile", "desktop"];
+/// let preds = [0.9, 0.2, 0.1, 0.7];
+/// let target = [1_usize, 0, 1, 1];
+///
+/// let mut metric = GroupedMetric::new(BinaryAccuracy::default());
+/// metric.update((&groups, &preds, &target)).unwrap();
+///
+/// let output = metric.compute().unwrap();
+/// assert_eq!(output.overall, 0.75);
+/// assert_eq!(output.groups["mobile"], 0.5);
+/// assert_eq!(output.groups["desktop"], 1.0);
+/// assert_eq!(output.worst_group(Objective::Maximize), Some((&"mobile", &0.5)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GroupedMetric<K, M> {
+    template: M,
+    overall: M,
+    groups: HashMap<K, M>,
+}
+
+impl<K, M: Clone> GroupedMetric<K, M> {
+    /// Wrap `metric`; its current state is the starting point for every group.
+    pub fn new(metric: M) -> Self {
+        Self {
+            template: metric.clone(),
+            overall: metric,
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl<K, M> GroupedMetric<K, M> {
+    /// Wrapped metric of `group`, if any sample of it was seen.
+    pub fn group(&self, group: &K) -> Option<&M>
+    where
+        K: Eq + Hash,
+    {
+        self.groups.get(group)
+    }
+
+    pub fn overall(&self) -> &M {
+        &self.overall
+    }
+}
+
+impl<'a, K, A, B, M> Metric<(&'a [K], &'a [A], &'a [B])> for GroupedMetric<K, M>
+where
+    K: Eq + Hash + Clone,
+    A: Clone,
+    B: Clone,
+    M: Clone + for<'b> Metric<(&'b [A], &'b [B])>,
+{
+    type Output = GroupedOutput<K, <M as Metric<(&'a [A], &'a [B])>>::Output>;
+
+    fn update(
+        &mut self,
+        (groups, predictions, targets): (&'a [K], &'a [A], &'a [B]),
+    ) -> Result<(), MetricError> {
+        if groups.len() != predictions.len() {
+            return Err(MetricError::LengthMismatch {
+                predictions: predictions.len(),
+                targets: groups.len(),
+            });
+        }
+        self.overall.update((predictions, targets))?;
+
+        let mut slices: Vec<(&K, Vec<A>, Vec<B>)> = Vec::new();
+        let mut slice_of: HashMap<&K, usize> = HashMap::new();
+        for ((group, prediction), target) in groups.iter().zip(predictions).zip(targets) {
+            let idx = *slice_of.entry(group).or_insert_with(|| {
+                slices.push((group, Vec::new(), Vec::new()));
+                slices.len() - 1
+            });
+            slices[idx].1.push(prediction.clone());
+            slices[idx].2.push(target.clone());
+        }
+        for (group, group_predictions, group_targets) in slices {
+            self.groups
+                .entry(group.clone())
+                .or_insert_with(|| self.template.clone())
+                .update((&group_predictions[..], &group_targets[..]))?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.overall.reset();
+        self.groups.clear();
+    }
+
+    fn compute(&self) -> Option<Self::Output> {
+        let overall = self.overall.compute()?;
+        let groups = self
+            .groups
+            .iter()
+            .filter_map(|(group, metric)| Some((group.clone(), metric.compute()?)))
+            .collect();
+        Some(GroupedOutput { overall, groups })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GroupedMetric;
+    use crate::core::Metric;
+    use crate::utils::Objective;
+    use crate::{MeanSquaredError, RougeScore};
+
+    #[test]
+    fn grouped_metric_over_batches() {
+        let mut metric = GroupedMetric::new(MeanSquaredError::default());
+        metric
+            .update((&[1, 2, 1], &[1.0, 2.0, 3.0], &[1.0, 4.0, 1.0]))
+            .unwrap();
+        metric.update((&[2], &[0.0], &[0.0])).unwrap();
+
+        let output = metric.compute().unwrap();
+        assert_eq!(output.overall, 2.0);
+        assert_eq!(output.groups[&1], 2.0);
+        assert_eq!(output.groups[&2], 2.0);
+        assert_eq!(output.groups.len(), 2);
+
+        metric.update((&[3], &[1.0], &[4.0])).unwrap();
+        let output = metric.compute().unwrap();
+        assert_eq!(output.worst_group(Objective::Minimize), Some((&3, &9.0)));
+
+        assert!(metric.update((&[1], &[1.0, 2.0], &[1.0, 2.0])).is_err());
+
+        metric.reset();
+        assert_eq!(metric.compute(), None);
+        assert!(metric.group(&1).is_none());
+    }
+
+    #[test]
+    fn grouped_text_metric() {
+        let mut metric = GroupedMetric::new(RougeScore::default());
+        let preds = ["the cat sat", "hello world"];
+        let targets = ["the cat sat", "goodbye moon"];
+        metric.update((&["en", "de"], &preds, &targets)).unwrap();
+        let output = metric.compute().unwrap();
+        assert_eq!(output.groups.len(), 2);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::utils::NeumaierSum;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Reduction {
     Sum,
@@ -11,7 +13,7 @@ pub enum Reduction {
 pub struct MetricAggregator {
     reduction: Reduction,
     total: usize,
-    sum: f64,
+    sum: NeumaierSum,
     min: Option<f64>,
     max: Option<f64>,
 }
@@ -21,7 +23,7 @@ impl MetricAggregator {
         Self {
             reduction,
             total: 0,
-            sum: 0.0,
+            sum: NeumaierSum::new(),
             min: None,
             max: None,
         }
@@ -29,13 +31,13 @@ impl MetricAggregator {
 
     pub fn update(&mut self, value: f64) {
         self.total += 1;
-        self.sum += value;
+        self.sum.add(value);
         self.min = Some(self.min.map_or(value, |m| m.min(value)));
         self.max = Some(self.max.map_or(value, |m| m.max(value)));
     }
     pub fn reset(&mut self) {
         self.total = 0;
-        self.sum = 0.0;
+        self.sum = NeumaierSum::new();
         self.min = None;
         self.max = None;
     }
@@ -45,8 +47,8 @@ impl MetricAggregator {
             return None;
         }
         match self.reduction {
-            Reduction::Sum => Some(self.sum),
-            Reduction::Mean => Some(self.sum / self.total as f64),
+            Reduction::Sum => Some(self.sum.value()),
+            Reduction::Mean => Some(self.sum.value() / self.total as f64),
             Reduction::Min => self.min,
             Reduction::Max => self.max,
         }
@@ -1,8 +1,34 @@
+pub mod composite_metric;
+pub mod contingency_table;
+pub mod covariance;
 pub mod general;
+pub mod grouped_metric;
 pub mod metric_aggregator;
+pub mod p2_quantile;
+pub mod preprocessing;
+pub mod sample_buffer;
+pub mod stats;
+pub mod summation;
+pub mod tokenizer;
 
+pub use composite_metric::{
+    ComponentHandle, ComponentSpec, ComponentValue, CompositeMetric, CompositeOutput, Objective,
+};
+pub use contingency_table::ContingencyTable;
+pub use covariance::CovarianceAccumulator;
 pub use general::{
-    AverageMethod, cosine_similarity, count_ngrams, levenshtein_distance, normalize, tokenize,
-    verify_binary_label, verify_label, verify_range,
+    AverageMethod, EditOperations, InputTransform, SimilarityFunction,
+    bounded_levenshtein_distance, cosine_similarity, count_ngrams, levenshtein_distance,
+    longest_common_subsequence, normalize, sequence_edit_operations, sequence_levenshtein_distance,
+    tokenize, verify_binary_label, verify_label, verify_range, verify_reverted_count,
 };
+pub use grouped_metric::{GroupedMetric, GroupedOutput};
 pub use metric_aggregator::{MetricAggregator, Reduction};
+pub use p2_quantile::P2Quantile;
+pub use preprocessing::{TextPreprocessing, UnicodeNormalization};
+pub use sample_buffer::SampleBuffer;
+pub use stats::{chi_squared_sf, kolmogorov_sf, normal_cdf, normal_quantile};
+pub use summation::{NeumaierSum, stable_sum};
+#[cfg(feature = "regex")]
+pub use tokenizer::RegexTokenizer;
+pub use tokenizer::{Tokenizer, WhitespaceTokenizer};
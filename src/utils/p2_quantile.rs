@@ -0,0 +1,172 @@
+use super::stats::interpolated_quantile;
+
+/// Streaming estimate of one quantile with the P² algorithm (Jain & Chlamtac, 1985).
+///
+/// Keeps five markers whose heights are adjusted with piecewise-parabolic interpolation as
+/// observations arrive, so memory stays constant regardless of the stream length. The estimate
+/// is exact for up to five observations. NaN observations are ignored.
+///
+/// ```
+/// use rust_metrics::utils::P2Quantile;
+///
+/// let mut median = P2Quantile::new(0.5);
+/// for value in 1..=101 {
+///     median.observe(value as f64);
+/// }
+/// assert!((median.estimate().unwrap() - 51.0).abs() < 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct P2Quantile {
+    quantile: f64,
+    count: usize,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    pub fn new(quantile: f64) -> Self {
+        assert!(
+            quantile > 0.0 && quantile < 1.0,
+            "quantile must lie in (0, 1)"
+        );
+        Self {
+            quantile,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [0.0, 1.0, 2.0, 3.0, 4.0],
+            desired: [
+                0.0,
+                2.0 * quantile,
+                4.0 * quantile,
+                2.0 + 2.0 * quantile,
+                4.0,
+            ],
+            increments: [0.0, quantile / 2.0, quantile, (1.0 + quantile) / 2.0, 1.0],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if self.count < 5 {
+            self.heights[self.count] = value;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+
+        let cell = if value < self.heights[0] {
+            self.heights[0] = value;
+            0
+        } else if value >= self.heights[4] {
+            self.heights[4] = value;
+            3
+        } else {
+            (0..4)
+                .find(|&i| value < self.heights[i + 1])
+                .expect("value lies below the last marker")
+        };
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            let room_above = self.positions[i + 1] - self.positions[i];
+            let room_below = self.positions[i - 1] - self.positions[i];
+            if (offset >= 1.0 && room_above > 1.0) || (offset <= -1.0 && room_below < -1.0) {
+                let step = offset.signum();
+                let parabolic = self.parabolic(i, step);
+                self.heights[i] =
+                    if self.heights[i - 1] < parabolic && parabolic < self.heights[i + 1] {
+                        parabolic
+                    } else {
+                        self.linear(i, step)
+                    };
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    /// Current estimate; `None` before the first observation.
+    pub fn estimate(&self) -> Option<f64> {
+        match self.count {
+            0 => None,
+            1..=4 => {
+                let mut sorted = self.heights[..self.count].to_vec();
+                sorted.sort_by(f64::total_cmp);
+                Some(interpolated_quantile(&sorted, self.quantile))
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.quantile);
+    }
+
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, step: f64) -> f64 {
+        let j = if step > 0.0 { i + 1 } else { i - 1 };
+        self.heights[i]
+            + step * (self.heights[j] - self.heights[i]) / (self.positions[j] - self.positions[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::P2Quantile;
+
+    #[test]
+    fn p2_tracks_quantiles_of_a_shuffled_stream() {
+        // deterministic permutation of 0..1000
+        let values: Vec<f64> = (0..1000).map(|i| ((i * 379) % 1000) as f64).collect();
+        for (quantile, expected) in [(0.5, 499.5), (0.9, 899.1), (0.1, 99.9)] {
+            let mut sketch = P2Quantile::new(quantile);
+            for &value in &values {
+                sketch.observe(value);
+            }
+            assert!((sketch.estimate().unwrap() - expected).abs() < 15.0);
+        }
+
+        let mut small = P2Quantile::new(0.5);
+        assert_eq!(small.estimate(), None);
+        for value in [3.0, 1.0, 2.0, 10.0] {
+            small.observe(value);
+        }
+        assert_eq!(small.estimate(), Some(2.5));
+    }
+
+    #[test]
+    fn p2_ignores_nan() {
+        let mut sketch = P2Quantile::new(0.5);
+        for value in [1.0, f64::NAN, 2.0, 3.0, 4.0, 5.0, f64::NAN, 6.0, 7.0] {
+            sketch.observe(value);
+        }
+        assert_eq!(sketch.len(), 7);
+        assert!(!sketch.estimate().unwrap().is_nan());
+    }
+}
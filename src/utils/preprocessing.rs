@@ -0,0 +1,146 @@
+use std::borrow::Cow;
+
+use crate::core::MetricError;
+
+#[cfg(feature = "unicode")]
+use unicode_normalization::UnicodeNormalization as _;
+
+/// Unicode normalization form applied by [`TextPreprocessing`].
+///
+/// `Nfc` and `Nfkc` require the `unicode` feature; without it,
+/// [`TextPreprocessing::apply`] rejects them with [`MetricError::IncompatibleInput`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnicodeNormalization {
+    /// Leave the text as given.
+    #[default]
+    None,
+    /// Canonical composition, e.g. `e` + combining acute becomes `é`.
+    Nfc,
+    /// Compatibility composition, which additionally folds e.g. full-width forms and ligatures.
+    Nfkc,
+}
+
+/// Text cleanup applied before tokenization by [`Bleu`](crate::Bleu),
+/// [`RougeScore`](crate::RougeScore), and [`EditDistance`](crate::EditDistance).
+///
+/// Steps run in field order: Unicode normalization, then Unicode-aware lowercasing, then
+/// punctuation stripping, which replaces every character that is neither alphanumeric nor
+/// whitespace with a space. The default leaves text untouched.
+///
+/// ```
+/// use rust_metrics::utils::TextPreprocessing;
+///
+/// let preprocessing = TextPreprocessing {
+///     lowercase: true,
+///     strip_punctuation: true,
+///     ..TextPreprocessing::default()
+/// };
+/// assert_eq!(preprocessing.apply("Élan, VITAL!").unwrap(), "élan  vital ");
+/// assert_eq!(TextPreprocessing::default().apply("Élan!").unwrap(), "Élan!");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextPreprocessing {
+    pub unicode: UnicodeNormalization,
+    pub lowercase: bool,
+    pub strip_punctuation: bool,
+}
+
+impl TextPreprocessing {
+    /// Preprocess `text`; fails only for a Unicode normalization form whose feature is disabled.
+    pub fn apply<'a>(&self, text: &'a str) -> Result<Cow<'a, str>, MetricError> {
+        let mut text = match self.unicode {
+            UnicodeNormalization::None => Cow::Borrowed(text),
+            #[cfg(feature = "unicode")]
+            UnicodeNormalization::Nfc => Cow::Owned(text.nfc().collect()),
+            #[cfg(feature = "unicode")]
+            UnicodeNormalization::Nfkc => Cow::Owned(text.nfkc().collect()),
+            #[cfg(not(feature = "unicode"))]
+            form => {
+                return Err(MetricError::IncompatibleInput {
+                    expected: "the `unicode` feature for NFC/NFKC normalization".to_string(),
+                    got: format!("{:?}", form),
+                });
+            }
+        };
+        if self.lowercase {
+            text = Cow::Owned(text.to_lowercase());
+        }
+        if self.strip_punctuation {
+            text = Cow::Owned(
+                text.chars()
+                    .map(|ch| {
+                        if ch.is_alphanumeric() || ch.is_whitespace() {
+                            ch
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect(),
+            );
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextPreprocessing;
+    use crate::core::Metric;
+    use crate::text::rouge::RougeKey;
+    use crate::{Bleu, BleuSmoothing, EditDistance, RougeScore};
+
+    #[test]
+    fn preprocessing_applies_to_text_metrics() {
+        let lowercase = TextPreprocessing {
+            lowercase: true,
+            ..TextPreprocessing::default()
+        };
+
+        let mut edit = EditDistance::default().with_preprocessing(lowercase);
+        edit.update((&["ÉCOLE"], &["école"])).unwrap();
+        assert_eq!(edit.compute(), Some(0.0));
+
+        let mut bleu = Bleu::new(1, BleuSmoothing::None).with_preprocessing(lowercase);
+        bleu.update((&["Straße Über"], &["straße über"])).unwrap();
+        assert_eq!(bleu.compute(), Some(1.0));
+
+        // ROUGE lowercases and strips punctuation by default, now for non-ASCII letters too
+        let mut rouge = RougeScore::new(vec![RougeKey::Rouge1]);
+        rouge.update((&["Ärger, Öl!"], &["ärger öl"])).unwrap();
+        assert_eq!(rouge.compute().unwrap()[&RougeKey::Rouge1].fmeasure, 1.0);
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn unicode_normalization_forms() {
+        use super::UnicodeNormalization;
+
+        let nfc = TextPreprocessing {
+            unicode: UnicodeNormalization::Nfc,
+            ..TextPreprocessing::default()
+        };
+        assert_eq!(nfc.apply("e\u{301}").unwrap(), "\u{e9}");
+
+        let nfkc = TextPreprocessing {
+            unicode: UnicodeNormalization::Nfkc,
+            ..TextPreprocessing::default()
+        };
+        assert_eq!(nfkc.apply("ｆｉ").unwrap(), "fi");
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn unicode_normalization_needs_the_feature() {
+        use super::UnicodeNormalization;
+
+        let nfc = TextPreprocessing {
+            unicode: UnicodeNormalization::Nfc,
+            ..TextPreprocessing::default()
+        };
+        assert!(nfc.apply("e\u{301}").is_err());
+
+        let mut edit = EditDistance::default().with_preprocessing(nfc);
+        assert!(edit.update((&["a"], &["a"])).is_err());
+        assert_eq!(edit.compute(), None);
+    }
+}
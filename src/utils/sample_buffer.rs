@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+/// Append-only sample storage made of immutable, reference-counted batches.
+///
+/// Cloning only copies one pointer per stored batch, which lets metrics that keep every sample
+/// hand out cheap snapshots while they keep accumulating.
+///
+/// ```
+/// use rust_metrics::utils::SampleBuffer;
+///
+/// let mut buffer = SampleBuffer::new();
+/// buffer.push_batch(vec![1, 2]);
+/// let snapshot = buffer.clone();
+/// buffer.push_batch(vec![3]);
+///
+/// assert_eq!(snapshot.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+/// assert_eq!(buffer.len(), 3);
+/// ```
+#[derive(Debug)]
+pub struct SampleBuffer<T> {
+    batches: Vec<Arc<[T]>>,
+    len: usize,
+}
+
+impl<T> Clone for SampleBuffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            batches: self.batches.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for SampleBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SampleBuffer<T> {
+    pub fn new() -> Self {
+        Self {
+            batches: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Store a batch of samples; empty batches are ignored.
+    pub fn push_batch(&mut self, batch: Vec<T>) {
+        if batch.is_empty() {
+            return;
+        }
+        self.len += batch.len();
+        self.batches.push(batch.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.batches.clear();
+        self.len = 0;
+    }
+
+    /// Iterate over all samples in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.batches.iter().flat_map(|batch| batch.iter())
+    }
+}
+
+impl<T: Clone> SampleBuffer<T> {
+    /// Copy all samples into a contiguous vector.
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut samples = Vec::with_capacity(self.len);
+        for batch in &self.batches {
+            samples.extend_from_slice(batch);
+        }
+        samples
+    }
+}
@@ -0,0 +1,218 @@
+/// Standard normal cumulative distribution function.
+///
+/// Uses a Chebyshev approximation of `erfc` with a fractional error below `1.2e-7`.
+///
+/// ```
+/// use rust_metrics::utils::normal_cdf;
+///
+/// assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+/// assert!((normal_cdf(1.959964) - 0.975).abs() < 1e-6);
+/// ```
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Linearly interpolated quantile of an ascending, non-empty slice.
+pub(crate) fn interpolated_quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Inverse of [`normal_cdf`] for `p` in `(0, 1)` (Acklam's rational approximation).
+///
+/// Returns `-inf`/`inf` for `p <= 0`/`p >= 1` and `NaN` for `NaN` input.
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p.is_nan() {
+        return f64::NAN;
+    }
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+/// Survival function `P(X > statistic)` of a chi-squared distribution with `dof` degrees of
+/// freedom.
+pub fn chi_squared_sf(statistic: f64, dof: usize) -> f64 {
+    assert!(dof >= 1, "dof must be at least 1");
+    if statistic <= 0.0 {
+        return 1.0;
+    }
+    regularized_gamma_q(dof as f64 / 2.0, statistic / 2.0)
+}
+
+/// Survival function of the asymptotic Kolmogorov distribution,
+/// `2 sum_{j>=1} (-1)^(j-1) exp(-2 j^2 lambda^2)`.
+pub fn kolmogorov_sf(lambda: f64) -> f64 {
+    if lambda <= 0.0 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let term = (-2.0 * (j * j) as f64 * lambda * lambda).exp();
+        sum += sign * term;
+        if term < 1e-16 {
+            return (2.0 * sum).clamp(0.0, 1.0);
+        }
+        sign = -sign;
+    }
+    // The series has not converged, which only happens for tiny `lambda`.
+    1.0
+}
+
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    for (idx, coefficient) in COEFFICIENTS.iter().enumerate() {
+        series += coefficient / (x + 1.0 + idx as f64);
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x)`.
+fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 500;
+    const TOLERANCE: f64 = 1e-15;
+    let log_prefactor = -x + a * x.ln() - ln_gamma(a);
+
+    if x < a + 1.0 {
+        // series for P(a, x)
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * TOLERANCE {
+                break;
+            }
+        }
+        1.0 - sum * log_prefactor.exp()
+    } else {
+        // Lentz continued fraction for Q(a, x)
+        let tiny = f64::MIN_POSITIVE / TOLERANCE;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..MAX_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < TOLERANCE {
+                break;
+            }
+        }
+        log_prefactor.exp() * h
+    }
+}
+
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let value = t * poly.exp();
+    if x >= 0.0 { value } else { 2.0 - value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chi_squared_sf, kolmogorov_sf, normal_cdf, normal_quantile};
+
+    #[test]
+    fn quantile_inverts_cdf() {
+        assert!((normal_quantile(0.975) - 1.959_963_985).abs() < 1e-8);
+        assert!((normal_quantile(0.5)).abs() < 1e-12);
+        for &p in &[0.001, 0.02, 0.3, 0.8, 0.99] {
+            assert!((normal_cdf(normal_quantile(p)) - p).abs() < 1e-7);
+        }
+        assert_eq!(normal_quantile(0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn chi_squared_and_kolmogorov_tails() {
+        assert!((chi_squared_sf(3.841_458_820_694_124, 1) - 0.05).abs() < 1e-9);
+        assert!((chi_squared_sf(5.0, 2) - (-2.5_f64).exp()).abs() < 1e-12);
+        assert!((chi_squared_sf(18.307_038_053_275_146, 10) - 0.05).abs() < 1e-9);
+        assert_eq!(chi_squared_sf(0.0, 3), 1.0);
+
+        assert!((kolmogorov_sf(1.358_098_8) - 0.05).abs() < 1e-6);
+        assert_eq!(kolmogorov_sf(0.0), 1.0);
+    }
+}
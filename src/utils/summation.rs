@@ -0,0 +1,85 @@
+/// Compensated floating-point sum (Neumaier's variant of Kahan summation).
+///
+/// Keeps the rounding error of every addition in a separate term, so long streams of values with
+/// mixed magnitudes lose far less precision than naive `+=` accumulation. Once the sum overflows
+/// or an infinite value is added, the result is the plain infinite (or NaN) sum.
+///
+/// ```
+/// use rust_metrics::utils::NeumaierSum;
+///
+/// let mut sum = NeumaierSum::new();
+/// for value in [1.0, 1e100, 1.0, -1e100] {
+///     sum.add(value);
+/// }
+/// assert_eq!(sum.value(), 2.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NeumaierSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl NeumaierSum {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, value: f64) {
+        let total = self.sum + value;
+        if !total.is_finite() {
+            // `inf - inf` would poison the compensation with NaN
+            self.sum = total;
+            return;
+        }
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - total) + value;
+        } else {
+            self.compensation += (value - total) + self.sum;
+        }
+        self.sum = total;
+    }
+
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+impl FromIterator<f64> for NeumaierSum {
+    fn from_iter<I: IntoIterator<Item = f64>>(values: I) -> Self {
+        let mut sum = NeumaierSum::new();
+        for value in values {
+            sum.add(value);
+        }
+        sum
+    }
+}
+
+/// Compensated sum of `values` in iteration order, see [`NeumaierSum`].
+pub fn stable_sum(values: impl IntoIterator<Item = f64>) -> f64 {
+    values.into_iter().collect::<NeumaierSum>().value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NeumaierSum, stable_sum};
+    use crate::utils::{MetricAggregator, Reduction};
+
+    #[test]
+    fn infinite_values_propagate() {
+        assert_eq!(stable_sum([1.0, f64::INFINITY, 2.0]), f64::INFINITY);
+        assert_eq!(stable_sum([f64::NEG_INFINITY, 1e100]), f64::NEG_INFINITY);
+        assert_eq!(stable_sum([f64::MAX, f64::MAX]), f64::INFINITY);
+        assert!(stable_sum([f64::INFINITY, f64::NEG_INFINITY]).is_nan());
+
+        let mut sum = NeumaierSum::new();
+        sum.add(f64::INFINITY);
+        sum.add(1.0);
+        assert_eq!(sum.value(), f64::INFINITY);
+
+        let mut mean = MetricAggregator::new(Reduction::Mean);
+        for value in [1.0, f64::INFINITY, 3.0] {
+            mean.update(value);
+        }
+        assert_eq!(mean.compute(), Some(f64::INFINITY));
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::Debug;
+
+/// Splits text into the tokens scored by the text metrics.
+///
+/// Tokens borrow from the input, so implementations only decide where the boundaries are.
+/// Metrics such as [`Bleu`](crate::Bleu), [`RougeScore`](crate::RougeScore), and
+/// [`EditDistance`](crate::EditDistance) accept a boxed tokenizer through `with_tokenizer`, which
+/// lets CJK or domain-specific segmentation be injected without forking the metric.
+///
+/// ```
+/// use rust_metrics::utils::Tokenizer;
+///
+/// /// One token per character, e.g. for unsegmented CJK text.
+/// #[derive(Debug)]
+/// struct CharTokenizer;
+///
+/// impl Tokenizer for CharTokenizer {
+///     fn tokenize<'a>(&self, input: &'a str) -> Vec<&'a str> {
+///         input
+///             .char_indices()
+///             .filter(|(_, ch)| !ch.is_whitespace())
+///             .map(|(idx, ch)| &input[idx..idx + ch.len_utf8()])
+///             .collect()
+///     }
+/// }
+///
+/// assert_eq!(CharTokenizer.tokenize("猫が座る"), vec!["猫", "が", "座", "る"]);
+/// ```
+pub trait Tokenizer: Debug + Send + Sync {
+    fn tokenize<'a>(&self, input: &'a str) -> Vec<&'a str>;
+}
+
+/// Splits on Unicode whitespace; the default tokenizer of every text metric.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize<'a>(&self, input: &'a str) -> Vec<&'a str> {
+        input.split_whitespace().collect()
+    }
+}
+
+/// Emits every non-overlapping match of a regular expression as a token.
+///
+/// ```
+/// use rust_metrics::utils::{RegexTokenizer, Tokenizer};
+///
+/// let tokenizer = RegexTokenizer::new(r"\w+|[^\w\s]").unwrap();
+/// assert_eq!(tokenizer.tokenize("foo(x, y);"), vec!["foo", "(", "x", ",", "y", ")", ";"]);
+/// ```
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[derive(Debug, Clone)]
+pub struct RegexTokenizer {
+    pattern: regex::Regex,
+}
+
+#[cfg(feature = "regex")]
+impl RegexTokenizer {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            pattern: regex::Regex::new(pattern)?,
+        })
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Tokenizer for RegexTokenizer {
+    fn tokenize<'a>(&self, input: &'a str) -> Vec<&'a str> {
+        self.pattern.find_iter(input).map(|m| m.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tokenizer, WhitespaceTokenizer};
+    use crate::core::Metric;
+    use crate::text::rouge::RougeKey;
+    use crate::{Bleu, EditDistance, RougeScore};
+
+    #[derive(Debug)]
+    struct CharTokenizer;
+
+    impl Tokenizer for CharTokenizer {
+        fn tokenize<'a>(&self, input: &'a str) -> Vec<&'a str> {
+            input
+                .char_indices()
+                .filter(|(_, ch)| !ch.is_whitespace())
+                .map(|(idx, ch)| &input[idx..idx + ch.len_utf8()])
+                .collect()
+        }
+    }
+
+    #[test]
+    fn text_metrics_use_injected_tokenizer() {
+        let preds = ["猫がマットの上に座った"];
+        let targets = ["猫がマットの上に座った"];
+
+        let mut bleu = Bleu::default();
+        bleu.update((&preds, &targets)).unwrap();
+        assert!((bleu.compute().unwrap() - 0.0).abs() < f64::EPSILON);
+
+        let mut bleu = Bleu::default().with_tokenizer(Box::new(CharTokenizer));
+        bleu.update((&preds, &targets)).unwrap();
+        assert!((bleu.compute().unwrap() - 1.0).abs() < 1e-12);
+
+        let mut rouge =
+            RougeScore::new(vec![RougeKey::Rouge1]).with_tokenizer(Box::new(CharTokenizer));
+        rouge.update((&["猫が座った"], &["犬が座った"])).unwrap();
+        assert!((rouge.compute().unwrap()[&RougeKey::Rouge1].fmeasure - 0.8).abs() < 1e-12);
+
+        let mut edit = EditDistance::default().with_tokenizer(Box::new(WhitespaceTokenizer));
+        edit.update((&["the cat sat"], &["the dog sat down"]))
+            .unwrap();
+        assert_eq!(edit.compute(), Some(2.0));
+    }
+}